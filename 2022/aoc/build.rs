@@ -0,0 +1,31 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+// Writes a test for every `examples/dayNN.toml`, so adding a manifest is all
+// it takes for a day's example to be checked by `cargo test`.
+fn main() {
+    let examples_dir = Path::new("../examples");
+    println!("cargo:rerun-if-changed={}", examples_dir.display());
+
+    let mut days: Vec<u8> = fs::read_dir(examples_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| {
+                    let name = entry.ok()?.file_name().into_string().ok()?;
+                    name.strip_prefix("day")?.strip_suffix(".toml")?.parse().ok()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort();
+
+    let tests: String = days
+        .iter()
+        .map(|day| format!("#[test]\nfn day{day:02}() {{\n    check_day({day});\n}}\n\n"))
+        .collect();
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("example_tests.rs"), tests).unwrap();
+}
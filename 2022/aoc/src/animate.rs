@@ -0,0 +1,266 @@
+//! Writing out a simulation's frames: as text, as a numbered sequence of PPM
+//! images, or as an animated GIF.
+//!
+//! Frames can change size as a simulation goes (the elves spread out, the
+//! sand piles up), so images are all drawn on a canvas big enough for every
+//! one of them, lined up by their origins.
+
+use std::cmp::{max, min};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use common::{Frame, Grid};
+
+type Rgb = [u8; 3];
+
+const BACKGROUND: Rgb = [15, 15, 35];
+
+// Anything not in here stands out
+const UNKNOWN: Rgb = [255, 0, 255];
+
+// Colours for the chars the simulations draw with
+const PALETTE: &[(char, Rgb)] = &[
+    ('.', BACKGROUND),
+    (' ', BACKGROUND),
+    // Rock, walls, elves
+    ('#', [130, 130, 140]),
+    ('|', [200, 200, 200]),
+    ('-', [200, 200, 200]),
+    ('+', [255, 255, 255]),
+    // Sand
+    ('o', [230, 190, 90]),
+    // Rope
+    ('s', [90, 200, 90]),
+    ('H', [230, 70, 70]),
+    ('T', [240, 150, 70]),
+    ('1', [240, 150, 70]),
+    ('2', [240, 150, 70]),
+    ('3', [240, 150, 70]),
+    ('4', [240, 150, 70]),
+    ('5', [240, 150, 70]),
+    ('6', [240, 150, 70]),
+    ('7', [240, 150, 70]),
+    ('8', [240, 150, 70]),
+    ('9', [240, 150, 70]),
+    // Wind and the expedition dodging it
+    ('^', [110, 170, 230]),
+    ('>', [110, 170, 230]),
+    ('v', [110, 170, 230]),
+    ('<', [110, 170, 230]),
+    ('E', [90, 220, 90]),
+    ('?', [255, 0, 0]),
+];
+
+// Hundredths of a second each GIF frame shows for
+const GIF_DELAY: u16 = 5;
+
+fn palette_index(cell: char) -> usize {
+    PALETTE.iter().position(|(c, _)| *c == cell).unwrap_or(PALETTE.len())
+}
+
+pub fn colour(cell: char) -> Rgb {
+    PALETTE.get(palette_index(cell)).map(|(_, rgb)| *rgb).unwrap_or(UNKNOWN)
+}
+
+/// Area covering a set of frames, in the simulation's coordinates.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Canvas {
+    left: isize,
+    top: isize,
+    width: usize,
+    height: usize,
+}
+
+impl Canvas {
+    pub fn around(frame: &Frame) -> Canvas {
+        let (left, top) = frame.origin;
+        Canvas { left, top, width: frame.cells.width(), height: frame.cells.height() }
+    }
+
+    /// Grows the canvas to cover `frame` as well.
+    pub fn include(&mut self, frame: &Frame) {
+        let other = Canvas::around(frame);
+        let right = max(self.left + self.width as isize, other.left + other.width as isize);
+        let bottom = max(self.top + self.height as isize, other.top + other.height as isize);
+
+        self.left = min(self.left, other.left);
+        self.top = min(self.top, other.top);
+        self.width = (right - self.left) as usize;
+        self.height = (bottom - self.top) as usize;
+    }
+
+    /// `frame`, drawn in its place on the canvas. Whatever it doesn't cover
+    /// is left blank.
+    pub fn place(&self, frame: &Frame) -> Grid<char> {
+        let mut cells = Grid::new(self.width, self.height, ' ');
+        let (dx, dy) = ((frame.origin.0 - self.left) as usize, (frame.origin.1 - self.top) as usize);
+
+        for ((x, y), cell) in frame.cells.iter() {
+            cells[(x + dx, y + dy)] = *cell;
+        }
+
+        cells
+    }
+}
+
+/// Each frame as it is, under a heading saying which step it's from.
+pub fn write_text(frames: impl Iterator<Item = (usize, Frame)>, out: &mut dyn Write) -> io::Result<usize> {
+    let mut count = 0;
+
+    for (step, frame) in frames {
+        writeln!(out, "== Step {step} ==\n{}\n", frame.cells)?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Binary PPM image of `cells`, with each one a `scale` pixel square.
+pub fn ppm(cells: &Grid<char>, scale: usize) -> Vec<u8> {
+    let mut image = format!("P6\n{} {}\n255\n", cells.width() * scale, cells.height() * scale).into_bytes();
+
+    for row in cells.rows() {
+        let line: Vec<u8> = row
+            .iter()
+            .flat_map(|cell| colour(*cell).repeat(scale))
+            .collect();
+
+        for _ in 0..scale {
+            image.extend(&line);
+        }
+    }
+
+    image
+}
+
+/// Writes `dir/frame-00000.ppm` and on, one image per frame.
+pub fn write_ppms(frames: impl Iterator<Item = Frame>, canvas: &Canvas, scale: usize, dir: &Path) -> Result<usize, String> {
+    fs::create_dir_all(dir).map_err(|e| format!("couldn't create {}: {e}", dir.display()))?;
+    let mut count = 0;
+
+    for (i, frame) in frames.enumerate() {
+        let path = dir.join(format!("frame-{i:05}.ppm"));
+        fs::write(&path, ppm(&canvas.place(&frame), scale))
+            .map_err(|e| format!("couldn't write {}: {e}", path.display()))?;
+
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Writes an animated GIF to `path`, looping forever.
+pub fn write_gif(frames: impl Iterator<Item = Frame>, canvas: &Canvas, scale: usize, path: &Path) -> Result<usize, String> {
+    let (width, height) = (canvas.width * scale, canvas.height * scale);
+
+    if width > u16::MAX as usize || height > u16::MAX as usize {
+        return Err(format!("{width}x{height} is too big for a GIF, try a smaller --scale"));
+    }
+
+    let global_palette: Vec<u8> = PALETTE
+        .iter()
+        .map(|(_, rgb)| *rgb)
+        .chain([UNKNOWN])
+        .flatten()
+        .collect();
+
+    let file = File::create(path).map_err(|e| format!("couldn't write {}: {e}", path.display()))?;
+    let gif_error = |e: gif::EncodingError| format!("couldn't write {}: {e}", path.display());
+
+    let mut encoder = gif::Encoder::new(BufWriter::new(file), width as u16, height as u16, &global_palette)
+        .map_err(gif_error)?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(gif_error)?;
+
+    let mut count = 0;
+
+    for frame in frames {
+        let cells = canvas.place(&frame);
+        let mut pixels = Vec::with_capacity(width * height);
+
+        for row in cells.rows() {
+            let line: Vec<u8> = row
+                .iter()
+                .flat_map(|cell| [palette_index(*cell) as u8].repeat(scale))
+                .collect();
+
+            for _ in 0..scale {
+                pixels.extend(&line);
+            }
+        }
+
+        let mut gif_frame = gif::Frame::from_indexed_pixels(width as u16, height as u16, pixels, None);
+        gif_frame.delay = GIF_DELAY;
+        encoder.write_frame(&gif_frame).map_err(gif_error)?;
+
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::animate::*;
+
+    fn frame(origin: (isize, isize), rows: &[&str]) -> Frame {
+        let lines: Vec<String> = rows.iter().map(|r| r.to_string()).collect();
+        Frame::new(origin, Grid::parse(&lines, |c| c))
+    }
+
+    #[test]
+    fn test_canvas() {
+        let first = frame((0, 0), &["##", "#."]);
+        let second = frame((-1, 1), &["o.", ".o"]);
+
+        let mut canvas = Canvas::around(&first);
+        canvas.include(&second);
+
+        assert_eq!(canvas, Canvas { left: -1, top: 0, width: 3, height: 3 });
+        assert_eq!(canvas.place(&first).to_string(), " ##\n #.\n   ");
+        assert_eq!(canvas.place(&second).to_string(), "   \no. \n.o ");
+    }
+
+    #[test]
+    fn test_ppm() {
+        let image = ppm(&Grid::parse(&["#.".to_string()], |c| c), 2);
+        let header = b"P6\n4 2\n255\n";
+
+        assert_eq!(&image[..header.len()], header);
+        assert_eq!(image.len(), header.len() + 4 * 2 * 3);
+        assert_eq!(&image[header.len()..header.len() + 6], &[130, 130, 140, 130, 130, 140]);
+        assert_eq!(&image[header.len() + 6..header.len() + 12], &[15, 15, 35, 15, 15, 35]);
+
+        assert_eq!(colour('~'), UNKNOWN);
+    }
+
+    #[test]
+    fn test_write_gif() {
+        let frames = vec![frame((0, 0), &["#."]), frame((0, 1), &["o"])];
+        let mut canvas = Canvas::around(&frames[0]);
+        canvas.include(&frames[1]);
+
+        let path = std::env::temp_dir().join(format!("aoc-test-{}.gif", std::process::id()));
+        assert_eq!(write_gif(frames.into_iter(), &canvas, 3, &path), Ok(2));
+
+        let mut decoder = gif::DecodeOptions::new().read_info(File::open(&path).unwrap()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (6, 6));
+
+        let mut count = 0;
+        while decoder.read_next_frame().unwrap().is_some() {
+            count += 1;
+        }
+
+        assert_eq!(count, 2);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_write_text() {
+        let frames = vec![(0, frame((0, 0), &["#."])), (5, frame((0, 0), &["##"]))];
+        let mut out = vec![];
+
+        assert_eq!(write_text(frames.into_iter(), &mut out).unwrap(), 2);
+        assert_eq!(String::from_utf8(out).unwrap(), "== Step 0 ==\n#.\n\n== Step 5 ==\n##\n\n");
+    }
+}
@@ -0,0 +1,293 @@
+//! Timing how long each day takes to parse its input and solve each part, and
+//! keeping a baseline of those timings to spot when something got slower.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use common::{Params, Part};
+use json::JsonValue;
+
+use crate::days::Day;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+pub enum Stage {
+    Parse,
+    Solve(Part),
+}
+
+impl Stage {
+    // Name in the baseline file
+    fn key(&self) -> String {
+        match self {
+            Stage::Parse => "parse".to_string(),
+            Stage::Solve(part) => format!("part{}", part.number()),
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Stage> {
+        match key {
+            "parse" => Some(Stage::Parse),
+            _ => key.strip_prefix("part").and_then(Part::parse).map(Stage::Solve),
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Solve(part) => write!(f, "part {}", part.number()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Timings {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timings {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+
+        Timings {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Timings for one stage of one day.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Measurement {
+    pub day: u8,
+    pub stage: Stage,
+    pub timings: Timings,
+}
+
+/// Parses `input` with `params` and solves `parts` from it, `runs` times over,
+/// timing each stage separately.
+pub fn bench_day(day: &Day, input: &[String], params: &Params, parts: &[Part], runs: usize) -> Result<Vec<Measurement>, String> {
+    let mut parse_samples = vec![];
+    let mut part_samples = vec![vec![]; parts.len()];
+
+    for _ in 0..runs {
+        let start = Instant::now();
+        let parsed = (day.parse)(input, params)
+            .map_err(|e| format!("couldn't parse day {} input, {}", day.number, e.diagnostic(input)))?;
+        parse_samples.push(start.elapsed());
+
+        for (samples, part) in part_samples.iter_mut().zip(parts) {
+            let start = Instant::now();
+            parsed.solve(*part);
+            samples.push(start.elapsed());
+        }
+    }
+
+    let mut measurements = vec![Measurement {
+        day: day.number,
+        stage: Stage::Parse,
+        timings: Timings::from_samples(parse_samples),
+    }];
+
+    for (samples, part) in part_samples.into_iter().zip(parts) {
+        measurements.push(Measurement {
+            day: day.number,
+            stage: Stage::Solve(*part),
+            timings: Timings::from_samples(samples),
+        });
+    }
+
+    Ok(measurements)
+}
+
+/// A stage that got slower than `threshold` percent over its baseline median.
+#[derive(Debug, PartialEq)]
+pub struct Regression {
+    pub day: u8,
+    pub stage: Stage,
+    pub before: Duration,
+    pub after: Duration,
+}
+
+/// Median change from `baseline`, in percent, for a stage that's in there.
+pub fn change(baseline: &[Measurement], measurement: &Measurement) -> Option<f64> {
+    let before = baseline
+        .iter()
+        .find(|b| b.day == measurement.day && b.stage == measurement.stage)?
+        .timings
+        .median
+        .as_nanos() as f64;
+
+    let after = measurement.timings.median.as_nanos() as f64;
+
+    if before == 0.0 {
+        return None;
+    }
+
+    Some((after - before) / before * 100.0)
+}
+
+pub fn regressions(baseline: &[Measurement], measurements: &[Measurement], threshold: f64) -> Vec<Regression> {
+    measurements
+        .iter()
+        .filter(|m| change(baseline, m).is_some_and(|c| c > threshold))
+        .map(|m| Regression {
+            day: m.day,
+            stage: m.stage,
+            before: baseline
+                .iter()
+                .find(|b| b.day == m.day && b.stage == m.stage)
+                .unwrap()
+                .timings
+                .median,
+            after: m.timings.median,
+        })
+        .collect()
+}
+
+/// Baseline file contents, with timings in nanoseconds:
+///
+/// ```json
+/// { "runs": 10, "days": { "15": { "parse": { "min_ns": 1, "median_ns": 2, "max_ns": 3 } } } }
+/// ```
+pub fn to_json(measurements: &[Measurement], runs: usize) -> String {
+    let mut days = JsonValue::new_object();
+
+    for m in measurements {
+        let mut timings = JsonValue::new_object();
+        timings["min_ns"] = (m.timings.min.as_nanos() as u64).into();
+        timings["median_ns"] = (m.timings.median.as_nanos() as u64).into();
+        timings["max_ns"] = (m.timings.max.as_nanos() as u64).into();
+
+        days[m.day.to_string().as_str()][m.stage.key().as_str()] = timings;
+    }
+
+    let mut baseline = JsonValue::new_object();
+    baseline["runs"] = runs.into();
+    baseline["days"] = days;
+
+    baseline.pretty(2)
+}
+
+pub fn from_json(text: &str) -> Result<Vec<Measurement>, String> {
+    let baseline = json::parse(text).map_err(|e| e.to_string())?;
+    let mut measurements = vec![];
+
+    for (day, stages) in baseline["days"].entries() {
+        let day: u8 = day.parse().map_err(|_| format!("`{day}` isn't a day"))?;
+
+        for (key, timings) in stages.entries() {
+            let stage = Stage::from_key(key).ok_or(format!("day {day} has unknown stage `{key}`"))?;
+            let nanos = |name: &str| {
+                timings[name]
+                    .as_u64()
+                    .map(Duration::from_nanos)
+                    .ok_or(format!("day {day} {stage} is missing {name}"))
+            };
+
+            measurements.push(Measurement {
+                day,
+                stage,
+                timings: Timings {
+                    min: nanos("min_ns")?,
+                    median: nanos("median_ns")?,
+                    max: nanos("max_ns")?,
+                },
+            });
+        }
+    }
+
+    measurements.sort_by_key(|m| (m.day, m.stage));
+    Ok(measurements)
+}
+
+/// Short human-friendly duration, like `850µs`, `12.3ms` or `26.10s`.
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+
+    if secs >= 1.0 {
+        format!("{secs:.2}s")
+    } else if secs >= 0.001 {
+        format!("{:.1}ms", secs * 1000.0)
+    } else if secs >= 0.000001 {
+        format!("{}µs", duration.as_micros())
+    } else {
+        format!("{}ns", duration.as_nanos())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bench::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    fn measurement(day: u8, stage: Stage, median: u64) -> Measurement {
+        Measurement {
+            day,
+            stage,
+            timings: Timings { min: ms(median - 1), median: ms(median), max: ms(median + 1) },
+        }
+    }
+
+    #[test]
+    fn test_timings_from_samples() {
+        assert_eq!(
+            Timings::from_samples(vec![ms(5), ms(1), ms(9), ms(3), ms(4)]),
+            Timings { min: ms(1), median: ms(4), max: ms(9) },
+        );
+
+        assert_eq!(Timings::from_samples(vec![ms(2)]).median, ms(2));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let measurements = vec![
+            measurement(1, Stage::Parse, 2),
+            measurement(1, Stage::Solve(Part::One), 3),
+            measurement(15, Stage::Solve(Part::Two), 26000),
+        ];
+
+        assert_eq!(from_json(&to_json(&measurements, 5)), Ok(measurements));
+        assert!(from_json(r#"{ "days": { "1": { "part3": {} } } }"#).is_err());
+    }
+
+    #[test]
+    fn test_regressions() {
+        let baseline = vec![
+            measurement(1, Stage::Parse, 10),
+            measurement(1, Stage::Solve(Part::One), 100),
+        ];
+
+        let now = vec![
+            measurement(1, Stage::Parse, 11),
+            measurement(1, Stage::Solve(Part::One), 150),
+            // Not in the baseline, so nothing to compare with
+            measurement(2, Stage::Parse, 500),
+        ];
+
+        assert_eq!(change(&baseline, &now[1]), Some(50.0));
+        assert_eq!(change(&baseline, &now[2]), None);
+
+        assert_eq!(
+            regressions(&baseline, &now, 20.0),
+            vec![Regression { day: 1, stage: Stage::Solve(Part::One), before: ms(100), after: ms(150) }],
+        );
+
+        assert_eq!(regressions(&baseline, &now, 5.0).len(), 2);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(212)), "212ns");
+        assert_eq!(format_duration(Duration::from_micros(850)), "850µs");
+        assert_eq!(format_duration(Duration::from_micros(12345)), "12.3ms");
+        assert_eq!(format_duration(Duration::from_millis(26100)), "26.10s");
+    }
+}
@@ -0,0 +1,1537 @@
+use std::path::PathBuf;
+
+use common::Part;
+
+use crate::{fetch, leaderboard, site, submit};
+
+pub const USAGE: &str = "\
+Usage:
+  aoc run --day <N> [--part <1|2>] [--input <path>] [--record] [--ledger <path>] [--format <text|json>]
+  aoc run --all [--part <1|2>] [--input-dir <dir>] [--record] [--ledger <path>] [--format <text|json>]
+          [--jobs <N>]
+  aoc batch --day <N> --inputs <dir> [--part <1|2>] [--jobs <N>]
+  aoc bench (--day <N> [--input <path>] | --all [--input-dir <dir>]) [--part <1|2>]
+            [--runs <N>] [--baseline <path>] [--save] [--threshold <percent>]
+  aoc animate --day <N> [--part <1|2>] [--input <path>] [--format <text|ppm|gif>]
+              [--out <path>] [--every <N>] [--scale <N>]
+  aoc play --day <N> [--part <1|2>] [--input <path>] [--speed <steps per second>]
+  aoc params --day <N> [--param <name>=<value>] [--config <path>]
+  aoc generate --day <N> [--seed <N>] [--size <N>] [--out <path>]
+  aoc new --day <N> [--year <YYYY>]
+  aoc fetch --day <N> [--year <YYYY>] [--out <path>] [--cache <dir>] [--server <url>]
+            [--session-file <path>]
+  aoc submit --day <N> --part <1|2> [--answer <value> | --input <path>] [--year <YYYY>]
+             [--attempts <path>] [--server <url>] [--session-file <path>]
+  aoc leaderboard (--file <path> | --id <N> [--year <YYYY>] [--cache <dir>] [--server <url>] [--session-file <path>])
+                  [--format <text|markdown|csv>]
+  aoc extract --day <N> --page <path> [--list] [--block <N>] [--answer-block [<part>=]<N>]... [--fixture <path>] [--force]
+  aoc help
+
+Any command takes -v to show progress and more of what's going on, or -vv for
+debugging output as well. It all goes to stderr. A -v after `--` is left alone,
+for a value that really is -v. Bars from days running at the same time share
+the line, each cut down to how far it's got.
+
+Some days take params, numbers the puzzle fixes that are worth changing, like
+day 11's rounds or day 16's minutes; `aoc params --day <N>` lists a day's, with
+what they're for and their defaults from the puzzle. run, batch, bench, animate,
+play and params set them from the --config file (default aoc.toml, if there is
+one), which has a [dayNN] table of `name = value` for each day, and then from
+--param <name>=<value>, which can be given more than once for a single --day.
+
+Without --input (or with `--input -`), a single day reads its input from stdin.
+With --all, each day reads `<dir>/aocNN/input.txt`, where <dir> defaults to the
+current directory; days without an input file are skipped. The days and their
+parts are solved side by side on --jobs threads (default one per core), sharing
+one parse of each input, with the answers still coming out in order of day, then
+a table of how long each day took.
+A day that fails doesn't stop the others, but run fails at the end.
+
+Each answer is checked against the --ledger file (default answers.json) of
+answers accepted before for the same input, and run fails if any differ.
+--record saves the answers to it instead.
+
+With `--format json`, run writes one JSON object per line for each part of each
+day, with its answer, how it compares to the ledger, how long parsing and solving
+took in milliseconds, the input's hash and any error. A day that fails doesn't
+stop the others, and anything else the days have to say goes to stderr.
+
+batch runs one day on every file in the --inputs directory, such as everyone's
+own puzzle inputs, side by side on --jobs threads (default one per core). It
+prints a table of each file's answers and timings; a file that can't be read or
+parsed is reported without stopping the rest.
+
+bench times parsing and each part over --runs runs (default 10). The first time,
+the timings are saved to the --baseline file (default bench.json); after that
+they're compared against it, and anything with a median more than --threshold
+percent (default 10) slower than before counts as a regression. --save replaces
+the baseline with the new timings.
+
+animate plays out one of the step-by-step days (9, 14, 17, 23 and 24) the way
+solving --part (default 1) would, writing a frame for every --every'th step
+(default 1). As text, frames go to stdout unless --out names a file. As ppm,
+--out is a directory to fill with numbered images, and as gif, the file to write
+the animation to. Images draw each cell as a --scale (default 4) pixel square.
+
+play shows the same frames in the terminal, redrawn in place at --speed steps a
+second (default 10). Space pauses, the arrow keys step back and forth, + and -
+change the speed, g jumps to a step, 0 goes back to the start and q quits.
+
+generate makes up a random input for a day, the same one every time for the
+same --seed (default 0). --size (default 100) scales it, with 100 about the size
+of a real puzzle input. It goes to stdout unless --out names a file, so it can be
+piped straight into `aoc run`.
+
+new sets up the crate for a day that hasn't been started, in the --year (default
+2022) directory of the repository: `aocNN` copied from `template/`, added to the
+workspace and the runner, with an empty `examples/dayNN.toml` to fill in. It
+won't touch a day that's already there. The repository is whichever one it's
+run from, inside a year's directory or at the top.
+
+fetch downloads a day's input for the --year (default 2022) from the --server
+(default https://adventofcode.com), as the user whose session token is in
+AOC_SESSION, or else the --session-file (default .aoc-session). It's kept in the
+--cache directory (default .cache/inputs) and never downloaded again. It goes to
+stdout unless --out names a file, the same as generate's. Set AOC_CONTACT to an
+email address or the like to have it sent along with the requests.
+
+submit sends an --answer for a day's --part to the same --server, as the same
+user, or without one, solves the part from the --input the way run does and
+sends that. It says whether the answer was right, too high or too low, or how
+long the site wants to wait before the next one, and fails unless it was right.
+Every answer sent is kept in the --attempts file (default attempts.json), and
+one that can't be right going by those, because it was turned down before, is
+past an answer that was too high or too low, or the part's solved already, is
+refused without sending it. So is anything sent before the site's wait is up.
+
+leaderboard reports on a private leaderboard: its members ranked by local score
+with their stars, then for each day, how long after the puzzle unlocked each
+member finished part one and part two, and the time in between. It reads the
+leaderboard's JSON from a --file saved from the site, or downloads leaderboard
+--id for the --year (default 2022) from the --server as the same user as fetch,
+reusing the copy in the --cache directory (default .cache/leaderboards) for 15
+minutes, as the site asks. With
+--format markdown it's Markdown tables, and with csv, a row for each member and
+day, with the times in seconds.
+
+extract takes the examples out of a day's puzzle page, saved from the browser as
+the --page file: its <pre><code> blocks, and the answers emphasised at the end of
+each part. --list shows the blocks, numbered, and the answers, each of which
+is for the first block in its part (part two without one goes with part one's).
+--answer-block says otherwise, as <part>=<N> for the one part or <N> for both.
+The --block (default 1) is the example, written to examples/dayNN.toml with
+whichever answers are for it (the rest are left out, with a warning), replacing
+the empty one `aoc new` leaves but no other unless --force is given.
+With --fixture, the block goes to that file on its own instead, for a test to
+read.";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunArgs),
+    Batch(BatchArgs),
+    Bench(BenchArgs),
+    Animate(AnimateArgs),
+    Play(PlayArgs),
+    Params(ParamsArgs),
+    Generate(GenerateArgs),
+    New(NewArgs),
+    Fetch(FetchArgs),
+    Submit(SubmitArgs),
+    Leaderboard(LeaderboardArgs),
+    Extract(ExtractArgs),
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunArgs {
+    pub target: Target,
+    // None runs both parts
+    pub part: Option<Part>,
+    // Save the answers to the ledger rather than only checking them
+    pub record: bool,
+    pub ledger: PathBuf,
+    pub format: OutputFormat,
+    // Threads to solve on; None is one per core
+    pub jobs: Option<usize>,
+    pub params: ParamSources,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BatchArgs {
+    pub day: u8,
+    // None runs both parts
+    pub part: Option<Part>,
+    // Every file in here is an input
+    pub inputs: PathBuf,
+    // Threads to solve on; None is one per core
+    pub jobs: Option<usize>,
+    pub params: ParamSources,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchArgs {
+    pub target: Target,
+    // None times both parts
+    pub part: Option<Part>,
+    pub runs: usize,
+    pub baseline: PathBuf,
+    // Overwrite the baseline rather than only comparing against it
+    pub save: bool,
+    // How much slower than the baseline, in percent, counts as a regression
+    pub threshold: f64,
+    pub params: ParamSources,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct AnimateArgs {
+    pub day: u8,
+    pub source: InputSource,
+    pub part: Part,
+    pub format: Format,
+    // Where the frames go; None is stdout, which only text can go to
+    pub out: Option<PathBuf>,
+    // Keep only every this many frames
+    pub every: usize,
+    // Pixels per cell, each way
+    pub scale: usize,
+    pub params: ParamSources,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PlayArgs {
+    pub day: u8,
+    pub source: InputSource,
+    pub part: Part,
+    // Steps per second to start off at
+    pub speed: usize,
+    pub params: ParamSources,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParamsArgs {
+    pub day: u8,
+    pub params: ParamSources,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GenerateArgs {
+    pub day: u8,
+    pub seed: u64,
+    pub size: usize,
+    // None is stdout
+    pub out: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct NewArgs {
+    pub day: u8,
+    // Names the directory the year's workspace is in
+    pub year: u16,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FetchArgs {
+    pub day: u8,
+    pub year: u16,
+    // None is stdout
+    pub out: Option<PathBuf>,
+    pub cache: PathBuf,
+    // Where the site is, which tests point at a stand-in
+    pub server: String,
+    // Where the session token is, if AOC_SESSION isn't set
+    pub session_file: PathBuf,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SubmitArgs {
+    pub day: u8,
+    pub year: u16,
+    pub part: Part,
+    // None solves the part from the input, and sends that
+    pub answer: Option<String>,
+    pub source: InputSource,
+    pub attempts: PathBuf,
+    // Where the site is, which tests point at a stand-in
+    pub server: String,
+    // Where the session token is, if AOC_SESSION isn't set
+    pub session_file: PathBuf,
+    pub params: ParamSources,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct LeaderboardArgs {
+    pub source: LeaderboardSource,
+    pub format: ReportFormat,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum LeaderboardSource {
+    // A copy of the JSON saved from the site
+    File(PathBuf),
+    // Downloaded, as whoever the session token is for
+    Site { id: u64, year: u16, cache: PathBuf, server: String, session_file: PathBuf },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ExtractArgs {
+    pub day: u8,
+    // The puzzle page, saved from the browser
+    pub page: PathBuf,
+    // Show the blocks and answers rather than writing anything
+    pub list: bool,
+    // Which block is the example, from 1
+    pub block: usize,
+    // Which block each part's answer is for, where it isn't the first in
+    // its part
+    pub answer_blocks: [Option<usize>; 2],
+    // Write the block here on its own, rather than the day's manifest
+    pub fixture: Option<PathBuf>,
+    // Replace an example that's there already
+    pub force: bool,
+}
+
+// Where the days' params come from, on top of their defaults
+#[derive(Debug, PartialEq, Default)]
+pub struct ParamSources {
+    // None is aoc.toml, if there is one
+    pub config: Option<PathBuf>,
+    // From --param, as (name, value)
+    pub overrides: Vec<(String, String)>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Format {
+    Text,
+    Ppm,
+    Gif,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ReportFormat {
+    Text,
+    Markdown,
+    Csv,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Target {
+    Day(u8, InputSource),
+    All(PathBuf),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    fn from_arg(arg: &str) -> InputSource {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+}
+
+/// Parses the arguments following the program name.
+pub fn parse(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter();
+
+    match args.next().map(|a| a.as_str()) {
+        Some("run") => parse_run(args).map(Command::Run),
+        Some("batch") => parse_batch(args).map(Command::Batch),
+        Some("bench") => parse_bench(args).map(Command::Bench),
+        Some("animate") => parse_animate(args).map(Command::Animate),
+        Some("play") => parse_play(args).map(Command::Play),
+        Some("params") => parse_params(args).map(Command::Params),
+        Some("generate") => parse_generate(args).map(Command::Generate),
+        Some("new") => parse_new(args).map(Command::New),
+        Some("fetch") => parse_fetch(args).map(Command::Fetch),
+        Some("submit") => parse_submit(args).map(Command::Submit),
+        Some("leaderboard") => parse_leaderboard(args).map(Command::Leaderboard),
+        Some("extract") => parse_extract(args).map(Command::Extract),
+        Some("help") | Some("--help") | Some("-h") | None => Ok(Command::Help),
+        Some(other) => Err(format!("unknown command `{other}`")),
+    }
+}
+
+// Flags picking which days to run and where their input comes from, shared by
+// the commands that run days
+#[derive(Default)]
+struct DayFlags {
+    day: Option<u8>,
+    all: bool,
+    part: Option<Part>,
+    input: Option<InputSource>,
+    input_dir: Option<PathBuf>,
+    params: ParamSources,
+}
+
+impl DayFlags {
+    // Takes `arg` (and its value) if it's one of these flags. Returns false
+    // for anything else, so the command can look at it.
+    fn take<'a>(&mut self, arg: &str, args: &mut impl Iterator<Item = &'a String>) -> Result<bool, String> {
+        match arg {
+            "--day" | "-d" => {
+                let value = take_value(args, arg)?;
+                self.day = Some(
+                    value
+                        .parse::<u8>()
+                        .ok()
+                        .filter(|d| (1..=25).contains(d))
+                        .ok_or(format!("invalid day `{value}`, expected 1 to 25"))?,
+                );
+            }
+
+            "--part" | "-p" => {
+                let value = take_value(args, arg)?;
+                self.part = Some(Part::parse(value).ok_or(format!("invalid part `{value}`, expected 1 or 2"))?);
+            }
+
+            "--input" | "-i" => {
+                self.input = Some(InputSource::from_arg(take_value(args, arg)?));
+            }
+
+            "--input-dir" => {
+                self.input_dir = Some(PathBuf::from(take_value(args, arg)?));
+            }
+
+            "--all" => { self.all = true; }
+
+            "--param" | "-P" => {
+                let value = take_value(args, arg)?;
+                let (name, value) = value
+                    .split_once('=')
+                    .ok_or(format!("invalid param `{value}`, expected <name>=<value>"))?;
+
+                self.params.overrides.push((name.trim().to_string(), value.to_string()));
+            }
+
+            "--config" => {
+                self.params.config = Some(PathBuf::from(take_value(args, arg)?));
+            }
+
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    // Takes the params out, for commands that use them. Which day a --param
+    // is meant for is only clear with the one.
+    fn param_sources(&mut self) -> Result<ParamSources, String> {
+        if self.all && !self.params.overrides.is_empty() {
+            return Err("--param needs a single --day, use --config to set params for --all".to_string());
+        }
+
+        Ok(std::mem::take(&mut self.params))
+    }
+
+    // For the commands that don't solve anything, so have no use for params
+    fn no_params(&self, command: &str) -> Result<(), String> {
+        if self.params != ParamSources::default() {
+            return Err(format!("{command} doesn't take --param or --config"));
+        }
+
+        Ok(())
+    }
+
+    // For the commands that only work on one day at a time
+    fn single_day(self, command: &str) -> Result<(u8, InputSource), String> {
+        match self.target()? {
+            Target::Day(day, source) => Ok((day, source)),
+            Target::All(_) => Err(format!("{command} takes a single --day")),
+        }
+    }
+
+    fn target(self) -> Result<Target, String> {
+        match (self.day, self.all) {
+            (Some(_), true) => Err("--day and --all can't be combined".to_string()),
+            (None, false) => Err("either --day or --all is required".to_string()),
+
+            (Some(day), false) => {
+                if self.input_dir.is_some() {
+                    return Err("--input-dir only applies to --all, use --input".to_string());
+                }
+
+                Ok(Target::Day(day, self.input.unwrap_or(InputSource::Stdin)))
+            }
+
+            (None, true) => {
+                if self.input.is_some() {
+                    return Err("--input only applies to --day, use --input-dir".to_string());
+                }
+
+                Ok(Target::All(self.input_dir.unwrap_or(PathBuf::from("."))))
+            }
+        }
+    }
+}
+
+fn parse_run<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<RunArgs, String> {
+    let mut flags = DayFlags::default();
+    let mut record = false;
+    let mut ledger = PathBuf::from("answers.json");
+    let mut format = OutputFormat::Text;
+    let mut jobs = None;
+
+    while let Some(arg) = args.next() {
+        if flags.take(arg, &mut args)? {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--record" => { record = true; }
+
+            "--ledger" => {
+                ledger = PathBuf::from(take_value(&mut args, arg)?);
+            }
+
+            "--format" | "-f" => {
+                format = match take_value(&mut args, arg)? {
+                    "text" => OutputFormat::Text,
+                    "json" => OutputFormat::Json,
+                    other => return Err(format!("invalid format `{other}`, expected text or json")),
+                };
+            }
+
+            "--jobs" | "-j" => {
+                jobs = Some(parse_jobs(take_value(&mut args, arg)?)?);
+            }
+
+            other => return Err(format!("unexpected argument `{other}`")),
+        }
+    }
+
+    let part = flags.part;
+    let params = flags.param_sources()?;
+
+    Ok(RunArgs {
+        target: flags.target()?,
+        part,
+        record,
+        ledger,
+        format,
+        jobs,
+        params,
+    })
+}
+
+fn parse_batch<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<BatchArgs, String> {
+    let mut flags = DayFlags::default();
+    let mut inputs = None;
+    let mut jobs = None;
+
+    while let Some(arg) = args.next() {
+        if flags.take(arg, &mut args)? {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--inputs" => {
+                inputs = Some(PathBuf::from(take_value(&mut args, arg)?));
+            }
+
+            "--jobs" | "-j" => {
+                jobs = Some(parse_jobs(take_value(&mut args, arg)?)?);
+            }
+
+            other => return Err(format!("unexpected argument `{other}`")),
+        }
+    }
+
+    if flags.input.is_some() || flags.input_dir.is_some() {
+        return Err("batch reads its inputs from --inputs".to_string());
+    }
+
+    let part = flags.part;
+    let params = flags.param_sources()?;
+    let (day, _) = flags.single_day("batch")?;
+    let inputs = inputs.ok_or("batch needs a directory of --inputs")?;
+
+    Ok(BatchArgs { day, part, inputs, jobs, params })
+}
+
+fn parse_jobs(value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|j| *j > 0)
+        .ok_or(format!("invalid job count `{value}`, expected at least 1"))
+}
+
+fn parse_bench<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<BenchArgs, String> {
+    let mut flags = DayFlags::default();
+    let mut runs = 10;
+    let mut baseline = PathBuf::from("bench.json");
+    let mut save = false;
+    let mut threshold = 10.0;
+
+    while let Some(arg) = args.next() {
+        if flags.take(arg, &mut args)? {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--runs" | "-n" => {
+                let value = take_value(&mut args, arg)?;
+                runs = value
+                    .parse()
+                    .ok()
+                    .filter(|r| *r > 0)
+                    .ok_or(format!("invalid run count `{value}`, expected at least 1"))?;
+            }
+
+            "--baseline" => {
+                baseline = PathBuf::from(take_value(&mut args, arg)?);
+            }
+
+            "--threshold" => {
+                let value = take_value(&mut args, arg)?;
+                threshold = value
+                    .parse()
+                    .ok()
+                    .filter(|t: &f64| *t >= 0.0)
+                    .ok_or(format!("invalid threshold `{value}`, expected a percentage"))?;
+            }
+
+            "--save" => { save = true; }
+
+            other => return Err(format!("unexpected argument `{other}`")),
+        }
+    }
+
+    let part = flags.part;
+    let params = flags.param_sources()?;
+
+    Ok(BenchArgs {
+        target: flags.target()?,
+        part,
+        runs,
+        baseline,
+        save,
+        threshold,
+        params,
+    })
+}
+
+fn parse_animate<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<AnimateArgs, String> {
+    let mut flags = DayFlags::default();
+    let mut format = Format::Text;
+    let mut out = None;
+    let mut every = 1;
+    let mut scale = 4;
+
+    while let Some(arg) = args.next() {
+        if flags.take(arg, &mut args)? {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--format" | "-f" => {
+                format = match take_value(&mut args, arg)? {
+                    "text" => Format::Text,
+                    "ppm" => Format::Ppm,
+                    "gif" => Format::Gif,
+                    other => return Err(format!("invalid format `{other}`, expected text, ppm or gif")),
+                };
+            }
+
+            "--out" | "-o" => {
+                out = Some(PathBuf::from(take_value(&mut args, arg)?));
+            }
+
+            "--every" => {
+                let value = take_value(&mut args, arg)?;
+                every = value
+                    .parse()
+                    .ok()
+                    .filter(|e| *e > 0)
+                    .ok_or(format!("invalid frame interval `{value}`, expected at least 1"))?;
+            }
+
+            "--scale" => {
+                let value = take_value(&mut args, arg)?;
+                scale = value
+                    .parse()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or(format!("invalid scale `{value}`, expected at least 1"))?;
+            }
+
+            other => return Err(format!("unexpected argument `{other}`")),
+        }
+    }
+
+    if format != Format::Text && out.is_none() {
+        return Err("--out is needed for images".to_string());
+    }
+
+    let part = flags.part.unwrap_or(Part::One);
+    let params = flags.param_sources()?;
+    let (day, source) = flags.single_day("animate")?;
+
+    Ok(AnimateArgs { day, source, part, format, out, every, scale, params })
+}
+
+fn parse_play<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<PlayArgs, String> {
+    let mut flags = DayFlags::default();
+    let mut speed = 10;
+
+    while let Some(arg) = args.next() {
+        if flags.take(arg, &mut args)? {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--speed" | "-s" => {
+                let value = take_value(&mut args, arg)?;
+                speed = value
+                    .parse()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or(format!("invalid speed `{value}`, expected at least 1 step per second"))?;
+            }
+
+            other => return Err(format!("unexpected argument `{other}`")),
+        }
+    }
+
+    let part = flags.part.unwrap_or(Part::One);
+    let params = flags.param_sources()?;
+    let (day, source) = flags.single_day("play")?;
+
+    Ok(PlayArgs { day, source, part, speed, params })
+}
+
+fn parse_params<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<ParamsArgs, String> {
+    let mut flags = DayFlags::default();
+
+    while let Some(arg) = args.next() {
+        if !flags.take(arg, &mut args)? {
+            return Err(format!("unexpected argument `{arg}`"));
+        }
+    }
+
+    if flags.part.is_some() || flags.input.is_some() {
+        return Err("params doesn't take --part or --input".to_string());
+    }
+
+    let params = flags.param_sources()?;
+    let (day, _) = flags.single_day("params")?;
+
+    Ok(ParamsArgs { day, params })
+}
+
+fn parse_generate<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<GenerateArgs, String> {
+    let mut flags = DayFlags::default();
+    let mut seed = 0;
+    let mut size = 100;
+    let mut out = None;
+
+    while let Some(arg) = args.next() {
+        if flags.take(arg, &mut args)? {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--seed" => {
+                let value = take_value(&mut args, arg)?;
+                seed = value.parse().map_err(|_| format!("invalid seed `{value}`, expected a number"))?;
+            }
+
+            "--size" => {
+                let value = take_value(&mut args, arg)?;
+                size = value
+                    .parse()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or(format!("invalid size `{value}`, expected at least 1"))?;
+            }
+
+            "--out" | "-o" => {
+                out = Some(PathBuf::from(take_value(&mut args, arg)?));
+            }
+
+            other => return Err(format!("unexpected argument `{other}`")),
+        }
+    }
+
+    // Nothing gets read or solved
+    if flags.part.is_some() || flags.input.is_some() {
+        return Err("generate doesn't take --part or --input".to_string());
+    }
+
+    flags.no_params("generate")?;
+
+    let (day, _) = flags.single_day("generate")?;
+
+    Ok(GenerateArgs { day, seed, size, out })
+}
+
+fn parse_new<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<NewArgs, String> {
+    let mut flags = DayFlags::default();
+    let mut year = 2022;
+
+    while let Some(arg) = args.next() {
+        if flags.take(arg, &mut args)? {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--year" | "-y" => {
+                year = parse_year(take_value(&mut args, arg)?)?;
+            }
+
+            other => return Err(format!("unexpected argument `{other}`")),
+        }
+    }
+
+    if flags.part.is_some() || flags.input.is_some() {
+        return Err("new doesn't take --part or --input".to_string());
+    }
+
+    flags.no_params("new")?;
+
+    let (day, _) = flags.single_day("new")?;
+
+    Ok(NewArgs { day, year })
+}
+
+fn parse_fetch<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<FetchArgs, String> {
+    let mut flags = DayFlags::default();
+    let mut year = 2022;
+    let mut out = None;
+    let mut cache = PathBuf::from(fetch::DEFAULT_CACHE);
+    let mut server = site::DEFAULT_SERVER.to_string();
+    let mut session_file = PathBuf::from(site::DEFAULT_SESSION_FILE);
+
+    while let Some(arg) = args.next() {
+        if flags.take(arg, &mut args)? {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--year" | "-y" => {
+                year = parse_year(take_value(&mut args, arg)?)?;
+            }
+
+            "--out" | "-o" => {
+                out = Some(PathBuf::from(take_value(&mut args, arg)?));
+            }
+
+            "--cache" => {
+                cache = PathBuf::from(take_value(&mut args, arg)?);
+            }
+
+            "--server" => {
+                server = take_value(&mut args, arg)?.to_string();
+            }
+
+            "--session-file" => {
+                session_file = PathBuf::from(take_value(&mut args, arg)?);
+            }
+
+            other => return Err(format!("unexpected argument `{other}`")),
+        }
+    }
+
+    if flags.part.is_some() || flags.input.is_some() {
+        return Err("fetch doesn't take --part or --input".to_string());
+    }
+
+    flags.no_params("fetch")?;
+
+    let (day, _) = flags.single_day("fetch")?;
+
+    Ok(FetchArgs { day, year, out, cache, server, session_file })
+}
+
+fn parse_submit<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<SubmitArgs, String> {
+    let mut flags = DayFlags::default();
+    let mut year = 2022;
+    let mut answer = None;
+    let mut attempts = PathBuf::from(submit::DEFAULT_ATTEMPTS);
+    let mut server = site::DEFAULT_SERVER.to_string();
+    let mut session_file = PathBuf::from(site::DEFAULT_SESSION_FILE);
+
+    while let Some(arg) = args.next() {
+        if flags.take(arg, &mut args)? {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--year" | "-y" => {
+                year = parse_year(take_value(&mut args, arg)?)?;
+            }
+
+            "--answer" | "-a" => {
+                let value = take_value(&mut args, arg)?.trim();
+
+                if value.is_empty() {
+                    return Err("--answer can't be empty".to_string());
+                }
+
+                answer = Some(value.to_string());
+            }
+
+            "--attempts" => {
+                attempts = PathBuf::from(take_value(&mut args, arg)?);
+            }
+
+            "--server" => {
+                server = take_value(&mut args, arg)?.to_string();
+            }
+
+            "--session-file" => {
+                session_file = PathBuf::from(take_value(&mut args, arg)?);
+            }
+
+            other => return Err(format!("unexpected argument `{other}`")),
+        }
+    }
+
+    let part = flags.part.ok_or("submit needs a --part")?;
+
+    if answer.is_some() && flags.input.is_some() {
+        return Err("--answer and --input can't be combined".to_string());
+    }
+
+    if answer.is_some() {
+        flags.no_params("submit with --answer")?;
+    } else if year != 2022 {
+        return Err(format!("only 2022 can be solved here, give the --answer for {year}"));
+    }
+
+    let params = flags.param_sources()?;
+    let (day, source) = flags.single_day("submit")?;
+
+    Ok(SubmitArgs { day, year, part, answer, source, attempts, server, session_file, params })
+}
+
+fn parse_leaderboard<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<LeaderboardArgs, String> {
+    let mut file = None;
+    let mut id = None;
+    let mut year = None;
+    let mut cache = None;
+    let mut server = None;
+    let mut session_file = None;
+    let mut format = ReportFormat::Text;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--file" => {
+                file = Some(PathBuf::from(take_value(&mut args, arg)?));
+            }
+
+            "--id" => {
+                let value = take_value(&mut args, arg)?;
+                id = Some(value.parse().map_err(|_| format!("invalid leaderboard id `{value}`, expected a number"))?);
+            }
+
+            "--year" | "-y" => {
+                year = Some(parse_year(take_value(&mut args, arg)?)?);
+            }
+
+            "--cache" => {
+                cache = Some(PathBuf::from(take_value(&mut args, arg)?));
+            }
+
+            "--server" => {
+                server = Some(take_value(&mut args, arg)?.to_string());
+            }
+
+            "--session-file" => {
+                session_file = Some(PathBuf::from(take_value(&mut args, arg)?));
+            }
+
+            "--format" | "-f" => {
+                format = match take_value(&mut args, arg)? {
+                    "text" => ReportFormat::Text,
+                    "markdown" | "md" => ReportFormat::Markdown,
+                    "csv" => ReportFormat::Csv,
+                    other => return Err(format!("invalid format `{other}`, expected text, markdown or csv")),
+                };
+            }
+
+            other => return Err(format!("unexpected argument `{other}`")),
+        }
+    }
+
+    let source = match (file, id) {
+        (Some(_), Some(_)) => return Err("--file and --id can't be combined".to_string()),
+        (None, None) => return Err("either --file or --id is required".to_string()),
+
+        (Some(file), None) => {
+            if year.is_some() || cache.is_some() || server.is_some() || session_file.is_some() {
+                return Err("--year, --cache, --server and --session-file only apply to --id".to_string());
+            }
+
+            LeaderboardSource::File(file)
+        }
+
+        (None, Some(id)) => LeaderboardSource::Site {
+            id,
+            year: year.unwrap_or(2022),
+            cache: cache.unwrap_or(PathBuf::from(leaderboard::DEFAULT_CACHE)),
+            server: server.unwrap_or(site::DEFAULT_SERVER.to_string()),
+            session_file: session_file.unwrap_or(PathBuf::from(site::DEFAULT_SESSION_FILE)),
+        },
+    };
+
+    Ok(LeaderboardArgs { source, format })
+}
+
+fn parse_extract<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<ExtractArgs, String> {
+    let mut flags = DayFlags::default();
+    let mut page = None;
+    let mut list = false;
+    let mut block = 1;
+    let mut answer_blocks = [None, None];
+    let mut fixture = None;
+    let mut force = false;
+
+    while let Some(arg) = args.next() {
+        if flags.take(arg, &mut args)? {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--page" => {
+                page = Some(PathBuf::from(take_value(&mut args, arg)?));
+            }
+
+            "--list" => { list = true; }
+
+            "--block" | "-b" => {
+                let value = take_value(&mut args, arg)?;
+                block = value
+                    .parse()
+                    .ok()
+                    .filter(|b| *b > 0)
+                    .ok_or(format!("invalid block `{value}`, expected a number from 1"))?;
+            }
+
+            "--answer-block" => {
+                let value = take_value(&mut args, arg)?;
+                let (parts, number) = match value.split_once('=') {
+                    Some(("1", number)) => (&mut answer_blocks[..1], number),
+                    Some(("2", number)) => (&mut answer_blocks[1..], number),
+                    Some(_) => return Err(format!("invalid answer block `{value}`, expected <part>=<block> with a part of 1 or 2")),
+                    None => (&mut answer_blocks[..], value),
+                };
+
+                let number = number
+                    .parse()
+                    .ok()
+                    .filter(|b| *b > 0)
+                    .ok_or(format!("invalid answer block `{value}`, expected a block number from 1"))?;
+
+                parts.fill(Some(number));
+            }
+
+            "--fixture" => {
+                fixture = Some(PathBuf::from(take_value(&mut args, arg)?));
+            }
+
+            "--force" => { force = true; }
+
+            other => return Err(format!("unexpected argument `{other}`")),
+        }
+    }
+
+    if flags.part.is_some() || flags.input.is_some() {
+        return Err("extract doesn't take --part or --input".to_string());
+    }
+
+    flags.no_params("extract")?;
+
+    let (day, _) = flags.single_day("extract")?;
+    let page = page.ok_or("extract needs the --page to take the examples from")?;
+
+    Ok(ExtractArgs { day, page, list, block, answer_blocks, fixture, force })
+}
+
+fn parse_year(value: &str) -> Result<u16, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|y| *y >= 2015)
+        .ok_or(format!("invalid year `{value}`, expected 2015 or later"))
+}
+
+fn take_value<'a>(
+    args: &mut impl Iterator<Item = &'a String>,
+    flag: &str,
+) -> Result<&'a str, String> {
+    args.next()
+        .map(|v| v.as_str())
+        .ok_or(format!("{flag} expects a value"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::*;
+
+    fn args(input: &str) -> Vec<String> {
+        input.split(' ').map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_run_day() {
+        assert_eq!(
+            parse(&args("run --day 7 --part 2 --input input.txt")),
+            Ok(Command::Run(RunArgs {
+                target: Target::Day(7, InputSource::File(PathBuf::from("input.txt"))),
+                part: Some(Part::Two),
+                record: false,
+                ledger: PathBuf::from("answers.json"),
+                format: OutputFormat::Text,
+                jobs: None,
+                params: ParamSources::default(),
+            })),
+        );
+
+        assert_eq!(
+            parse(&args("run -d 12")),
+            Ok(Command::Run(RunArgs {
+                target: Target::Day(12, InputSource::Stdin),
+                part: None,
+                record: false,
+                ledger: PathBuf::from("answers.json"),
+                format: OutputFormat::Text,
+                jobs: None,
+                params: ParamSources::default(),
+            })),
+        );
+
+        assert_eq!(
+            parse(&args("run --day 3 --input -")),
+            Ok(Command::Run(RunArgs {
+                target: Target::Day(3, InputSource::Stdin),
+                part: None,
+                record: false,
+                ledger: PathBuf::from("answers.json"),
+                format: OutputFormat::Text,
+                jobs: None,
+                params: ParamSources::default(),
+            })),
+        );
+    }
+
+    #[test]
+    fn test_parse_run_record() {
+        assert_eq!(
+            parse(&args("run --day 7 --input input.txt --record --ledger mine.json")),
+            Ok(Command::Run(RunArgs {
+                target: Target::Day(7, InputSource::File(PathBuf::from("input.txt"))),
+                part: None,
+                record: true,
+                ledger: PathBuf::from("mine.json"),
+                format: OutputFormat::Text,
+                jobs: None,
+                params: ParamSources::default(),
+            })),
+        );
+
+        assert!(parse(&args("run --day 7 --ledger")).is_err());
+    }
+
+    #[test]
+    fn test_parse_run_format() {
+        assert_eq!(
+            parse(&args("run --all --format json --jobs 4")),
+            Ok(Command::Run(RunArgs {
+                target: Target::All(PathBuf::from(".")),
+                part: None,
+                record: false,
+                ledger: PathBuf::from("answers.json"),
+                format: OutputFormat::Json,
+                jobs: Some(4),
+                params: ParamSources::default(),
+            })),
+        );
+
+        assert!(parse(&args("run --all --jobs 0")).is_err());
+        assert!(parse(&args("run --all --format gif")).is_err());
+    }
+
+    #[test]
+    fn test_parse_run_all() {
+        assert_eq!(
+            parse(&args("run --all --input-dir 2022")),
+            Ok(Command::Run(RunArgs {
+                target: Target::All(PathBuf::from("2022")),
+                part: None,
+                record: false,
+                ledger: PathBuf::from("answers.json"),
+                format: OutputFormat::Text,
+                jobs: None,
+                params: ParamSources::default(),
+            })),
+        );
+
+        assert_eq!(
+            parse(&args("run --all --part 1")),
+            Ok(Command::Run(RunArgs {
+                target: Target::All(PathBuf::from(".")),
+                part: Some(Part::One),
+                record: false,
+                ledger: PathBuf::from("answers.json"),
+                format: OutputFormat::Text,
+                jobs: None,
+                params: ParamSources::default(),
+            })),
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse(&args("run")).is_err());
+        assert!(parse(&args("run --day 26")).is_err());
+        assert!(parse(&args("run --day 0")).is_err());
+        assert!(parse(&args("run --day 1 --part 3")).is_err());
+        assert!(parse(&args("run --day 1 --all")).is_err());
+        assert!(parse(&args("run --day")).is_err());
+        assert!(parse(&args("run --all --input foo.txt")).is_err());
+        assert!(parse(&args("jump")).is_err());
+    }
+
+    #[test]
+    fn test_parse_batch() {
+        assert_eq!(
+            parse(&args("batch --day 7 --inputs everyone --part 2 --jobs 3")),
+            Ok(Command::Batch(BatchArgs {
+                day: 7,
+                part: Some(Part::Two),
+                inputs: PathBuf::from("everyone"),
+                jobs: Some(3),
+                params: ParamSources::default(),
+            })),
+        );
+
+        assert!(parse(&args("batch --day 7")).is_err());
+        assert!(parse(&args("batch --all --inputs everyone")).is_err());
+        assert!(parse(&args("batch --day 7 --inputs everyone --input input.txt")).is_err());
+        assert!(parse(&args("batch --day 7 --inputs everyone --jobs 0")).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        assert_eq!(
+            parse(&args("bench --all --runs 3 --threshold 25 --save")),
+            Ok(Command::Bench(BenchArgs {
+                target: Target::All(PathBuf::from(".")),
+                part: None,
+                runs: 3,
+                baseline: PathBuf::from("bench.json"),
+                save: true,
+                threshold: 25.0,
+                params: ParamSources::default(),
+            })),
+        );
+
+        assert_eq!(
+            parse(&args("bench --day 15 --part 1 --input aoc15/input.txt --baseline base.json")),
+            Ok(Command::Bench(BenchArgs {
+                target: Target::Day(15, InputSource::File(PathBuf::from("aoc15/input.txt"))),
+                part: Some(Part::One),
+                runs: 10,
+                baseline: PathBuf::from("base.json"),
+                save: false,
+                threshold: 10.0,
+                params: ParamSources::default(),
+            })),
+        );
+
+        assert!(parse(&args("bench --all --runs 0")).is_err());
+        assert!(parse(&args("bench --all --threshold fast")).is_err());
+        assert!(parse(&args("bench --runs 5")).is_err());
+    }
+
+    #[test]
+    fn test_parse_animate() {
+        assert_eq!(
+            parse(&args("animate --day 14 --part 2 --input aoc14/input.txt --format gif --out sand.gif --every 10")),
+            Ok(Command::Animate(AnimateArgs {
+                day: 14,
+                source: InputSource::File(PathBuf::from("aoc14/input.txt")),
+                part: Part::Two,
+                format: Format::Gif,
+                out: Some(PathBuf::from("sand.gif")),
+                every: 10,
+                scale: 4,
+                params: ParamSources::default(),
+            })),
+        );
+
+        assert_eq!(
+            parse(&args("animate -d 23")),
+            Ok(Command::Animate(AnimateArgs {
+                day: 23,
+                source: InputSource::Stdin,
+                part: Part::One,
+                format: Format::Text,
+                out: None,
+                every: 1,
+                scale: 4,
+                params: ParamSources::default(),
+            })),
+        );
+
+        assert!(parse(&args("animate --all")).is_err());
+        assert!(parse(&args("animate --day 9 --format ppm")).is_err());
+        assert!(parse(&args("animate --day 9 --format png --out frames")).is_err());
+        assert!(parse(&args("animate --day 9 --every 0")).is_err());
+    }
+
+    #[test]
+    fn test_parse_play() {
+        assert_eq!(
+            parse(&args("play --day 24 --part 2 --input aoc24/input.txt --speed 50")),
+            Ok(Command::Play(PlayArgs {
+                day: 24,
+                source: InputSource::File(PathBuf::from("aoc24/input.txt")),
+                part: Part::Two,
+                speed: 50,
+                params: ParamSources::default(),
+            })),
+        );
+
+        assert_eq!(
+            parse(&args("play -d 9")),
+            Ok(Command::Play(PlayArgs {
+                day: 9,
+                source: InputSource::Stdin,
+                part: Part::One,
+                speed: 10,
+                params: ParamSources::default(),
+            })),
+        );
+
+        assert!(parse(&args("play --all")).is_err());
+        assert!(parse(&args("play --day 9 --speed 0")).is_err());
+    }
+
+    #[test]
+    fn test_parse_generate() {
+        assert_eq!(
+            parse(&args("generate --day 20 --seed 42 --size 5 --out gen.txt")),
+            Ok(Command::Generate(GenerateArgs { day: 20, seed: 42, size: 5, out: Some(PathBuf::from("gen.txt")) })),
+        );
+
+        assert_eq!(
+            parse(&args("generate -d 3")),
+            Ok(Command::Generate(GenerateArgs { day: 3, seed: 0, size: 100, out: None })),
+        );
+
+        assert!(parse(&args("generate --all")).is_err());
+        assert!(parse(&args("generate --day 3 --size 0")).is_err());
+        assert!(parse(&args("generate --day 3 --seed -1")).is_err());
+        assert!(parse(&args("generate --day 3 --input input.txt")).is_err());
+    }
+
+    #[test]
+    fn test_parse_new() {
+        assert_eq!(parse(&args("new --day 19")), Ok(Command::New(NewArgs { day: 19, year: 2022 })));
+        assert_eq!(parse(&args("new -d 1 --year 2023")), Ok(Command::New(NewArgs { day: 1, year: 2023 })));
+
+        assert!(parse(&args("new --all")).is_err());
+        assert!(parse(&args("new --day 19 --year 1999")).is_err());
+        assert!(parse(&args("new --day 19 --part 1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_params() {
+        let overrides = vec![("row".to_string(), "10".to_string()), ("limit".to_string(), "20".to_string())];
+
+        assert_eq!(
+            parse(&args("run --day 15 --param row=10 -P limit=20 --config mine.toml")),
+            Ok(Command::Run(RunArgs {
+                target: Target::Day(15, InputSource::Stdin),
+                part: None,
+                record: false,
+                ledger: PathBuf::from("answers.json"),
+                format: OutputFormat::Text,
+                jobs: None,
+                params: ParamSources { config: Some(PathBuf::from("mine.toml")), overrides: overrides.clone() },
+            })),
+        );
+
+        assert_eq!(
+            parse(&args("params --day 15 --param row=10 -P limit=20")),
+            Ok(Command::Params(ParamsArgs { day: 15, params: ParamSources { config: None, overrides } })),
+        );
+
+        assert!(parse(&args("run --day 15 --param row")).is_err());
+        assert!(parse(&args("run --all --param row=10")).is_err());
+        assert!(parse(&args("run --all --config mine.toml")).is_ok());
+        assert!(parse(&args("generate --day 15 --param row=10")).is_err());
+        assert!(parse(&args("params --all")).is_err());
+        assert!(parse(&args("params --day 15 --part 1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_fetch() {
+        assert_eq!(
+            parse(&args("fetch --day 7")),
+            Ok(Command::Fetch(FetchArgs {
+                day: 7,
+                year: 2022,
+                out: None,
+                cache: PathBuf::from(".cache/inputs"),
+                server: "https://adventofcode.com".to_string(),
+                session_file: PathBuf::from(".aoc-session"),
+            })),
+        );
+
+        assert_eq!(
+            parse(&args("fetch -d 1 -y 2021 -o aoc01/input.txt --cache inputs --server http://localhost:8080 --session-file token")),
+            Ok(Command::Fetch(FetchArgs {
+                day: 1,
+                year: 2021,
+                out: Some(PathBuf::from("aoc01/input.txt")),
+                cache: PathBuf::from("inputs"),
+                server: "http://localhost:8080".to_string(),
+                session_file: PathBuf::from("token"),
+            })),
+        );
+
+        assert!(parse(&args("fetch --all")).is_err());
+        assert!(parse(&args("fetch --day 7 --year 2014")).is_err());
+        assert!(parse(&args("fetch --day 7 --input input.txt")).is_err());
+    }
+
+    #[test]
+    fn test_parse_submit() {
+        assert_eq!(
+            parse(&args("submit --day 15 --part 2 --input aoc15/input.txt -P row=10")),
+            Ok(Command::Submit(SubmitArgs {
+                day: 15,
+                year: 2022,
+                part: Part::Two,
+                answer: None,
+                source: InputSource::File(PathBuf::from("aoc15/input.txt")),
+                attempts: PathBuf::from("attempts.json"),
+                server: "https://adventofcode.com".to_string(),
+                session_file: PathBuf::from(".aoc-session"),
+                params: ParamSources { config: None, overrides: vec![("row".to_string(), "10".to_string())] },
+            })),
+        );
+
+        assert_eq!(
+            parse(&args("submit -d 1 -p 1 -y 2021 -a 1234 --attempts tried.json --server http://localhost:8080 --session-file token")),
+            Ok(Command::Submit(SubmitArgs {
+                day: 1,
+                year: 2021,
+                part: Part::One,
+                answer: Some("1234".to_string()),
+                source: InputSource::Stdin,
+                attempts: PathBuf::from("tried.json"),
+                server: "http://localhost:8080".to_string(),
+                session_file: PathBuf::from("token"),
+                params: ParamSources::default(),
+            })),
+        );
+
+        assert!(parse(&args("submit --day 7")).is_err());
+        assert!(parse(&args("submit --all --part 1")).is_err());
+        assert!(parse(&args("submit --day 7 --part 1 --answer 95437 --input input.txt")).is_err());
+        assert!(parse(&args("submit --day 7 --part 1 --answer 95437 --param disk_size=100")).is_err());
+        assert!(parse(&args("submit --day 7 --part 1 --year 2021")).is_err());
+    }
+
+    #[test]
+    fn test_parse_leaderboard() {
+        assert_eq!(
+            parse(&args("leaderboard --file board.json --format md")),
+            Ok(Command::Leaderboard(LeaderboardArgs {
+                source: LeaderboardSource::File(PathBuf::from("board.json")),
+                format: ReportFormat::Markdown,
+            })),
+        );
+
+        assert_eq!(
+            parse(&args("leaderboard --id 123456 -y 2021 --server http://localhost:8080 -f csv")),
+            Ok(Command::Leaderboard(LeaderboardArgs {
+                source: LeaderboardSource::Site {
+                    id: 123456,
+                    year: 2021,
+                    cache: PathBuf::from(".cache/leaderboards"),
+                    server: "http://localhost:8080".to_string(),
+                    session_file: PathBuf::from(".aoc-session"),
+                },
+                format: ReportFormat::Csv,
+            })),
+        );
+
+        assert_eq!(
+            parse(&args("leaderboard --id 123456 --cache boards")),
+            Ok(Command::Leaderboard(LeaderboardArgs {
+                source: LeaderboardSource::Site {
+                    id: 123456,
+                    year: 2022,
+                    cache: PathBuf::from("boards"),
+                    server: "https://adventofcode.com".to_string(),
+                    session_file: PathBuf::from(".aoc-session"),
+                },
+                format: ReportFormat::Text,
+            })),
+        );
+
+        assert!(parse(&args("leaderboard")).is_err());
+        assert!(parse(&args("leaderboard --file board.json --id 123456")).is_err());
+        assert!(parse(&args("leaderboard --file board.json --year 2021")).is_err());
+        assert!(parse(&args("leaderboard --file board.json --cache boards")).is_err());
+        assert!(parse(&args("leaderboard --id abc")).is_err());
+        assert!(parse(&args("leaderboard --id 123456 --format json")).is_err());
+    }
+
+    #[test]
+    fn test_parse_extract() {
+        assert_eq!(
+            parse(&args("extract --day 9 --page day9.html")),
+            Ok(Command::Extract(ExtractArgs {
+                day: 9,
+                page: PathBuf::from("day9.html"),
+                list: false,
+                block: 1,
+                answer_blocks: [None, None],
+                fixture: None,
+                force: false,
+            })),
+        );
+
+        assert_eq!(
+            parse(&args("extract -d 23 --page day23.html -b 2 --fixture aoc23/src/larger.txt --force")),
+            Ok(Command::Extract(ExtractArgs {
+                day: 23,
+                page: PathBuf::from("day23.html"),
+                list: false,
+                block: 2,
+                answer_blocks: [None, None],
+                fixture: Some(PathBuf::from("aoc23/src/larger.txt")),
+                force: true,
+            })),
+        );
+
+        assert!(parse(&args("extract --day 9")).is_err());
+        assert!(parse(&args("extract --day 9 --page day9.html --block 0")).is_err());
+        assert!(parse(&args("extract --day 9 --page day9.html --answer-block 3=1")).is_err());
+        assert!(parse(&args("extract --day 9 --page day9.html --answer-block 1=0")).is_err());
+
+        let Ok(Command::Extract(extract)) = parse(&args("extract --day 23 --page day23.html --answer-block 2 --answer-block 1=3")) else {
+            panic!("extract with answer blocks didn't parse");
+        };
+        assert_eq!(extract.answer_blocks, [Some(3), Some(2)]);
+        assert!(parse(&args("extract --all --page day9.html")).is_err());
+    }
+}
@@ -0,0 +1,162 @@
+//! Params for the days from a config file, `aoc.toml` unless `--config` says
+//! otherwise, with a table for each day:
+//!
+//! ```toml
+//! [day11]
+//! rounds_two = 500
+//!
+//! [day16]
+//! start = "BB"
+//! minutes = 20
+//! ```
+//!
+//! Anything not in there gets the day's default, and `--param` beats both.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use common::Params;
+
+use crate::cli::ParamSources;
+use crate::days::Day;
+
+pub const DEFAULT_PATH: &str = "aoc.toml";
+
+#[derive(Debug, Default)]
+pub struct Config {
+    // None when there's no config file
+    path: Option<PathBuf>,
+    // Each day's (name, value) pairs, values as text
+    days: BTreeMap<u8, Vec<(String, String)>>,
+}
+
+impl Config {
+    /// Reads `path`, or `aoc.toml` if it's there when no path is given.
+    pub fn load(path: Option<&Path>) -> Result<Config, String> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None if Path::new(DEFAULT_PATH).exists() => PathBuf::from(DEFAULT_PATH),
+            None => return Ok(Config::default()),
+        };
+
+        let text = fs::read_to_string(&path).map_err(|e| format!("couldn't read {}: {e}", path.display()))?;
+        let mut config = Config::parse(&text).map_err(|e| format!("{}: {e}", path.display()))?;
+        config.path = Some(path);
+
+        Ok(config)
+    }
+
+    fn parse(text: &str) -> Result<Config, String> {
+        let table: toml::Table = text.parse().map_err(|e| format!("{e}"))?;
+        let mut days = BTreeMap::new();
+
+        for (key, value) in &table {
+            let day = key
+                .strip_prefix("day")
+                .filter(|n| n.len() == 2)
+                .and_then(|n| n.parse::<u8>().ok())
+                .filter(|d| (1..=25).contains(d))
+                .ok_or(format!("unexpected `{key}`, expected tables like [day07]"))?;
+
+            let values = value.as_table().ok_or(format!("{key} should be a table"))?;
+            let mut entries = vec![];
+
+            for (name, value) in values {
+                let raw = raw_value(value).ok_or(format!("[{key}] {name} should be a number or a string"))?;
+                entries.push((name.clone(), raw));
+            }
+
+            days.insert(day, entries);
+        }
+
+        Ok(Config { path: None, days })
+    }
+
+    /// The params to give `day`: its defaults, then anything set for it in
+    /// here, then `overrides` from `--param`.
+    pub fn params(&self, day: &Day, overrides: &[(String, String)]) -> Result<Params, String> {
+        let mut params = Params::new();
+
+        for (name, raw) in self.days.get(&day.number).into_iter().flatten() {
+            params.apply(day.params, name, raw).map_err(|e| {
+                let path = self.path.as_deref().unwrap_or(Path::new(DEFAULT_PATH));
+                format!("{} [day{:02}]: {e}", path.display(), day.number)
+            })?;
+        }
+
+        for (name, raw) in overrides {
+            params
+                .apply(day.params, name, raw)
+                .map_err(|e| format!("--param for day {}: {e}", day.number))?;
+        }
+
+        Ok(params)
+    }
+}
+
+/// Loads the config `sources` point at, for commands that only need the one
+/// day's params.
+pub fn day_params(day: &Day, sources: &ParamSources) -> Result<Params, String> {
+    Config::load(sources.config.as_deref())?.params(day, &sources.overrides)
+}
+
+/// A param as it'd be given to `--param`, for the TOML values that can be one.
+pub fn raw_value(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::Integer(n) => Some(n.to_string()),
+        toml::Value::String(s) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Value;
+
+    use crate::config::*;
+    use crate::days;
+
+    fn overrides(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn test_params() {
+        let config = Config::parse("[day15]\nrow = 10\nlimit = 20\n\n[day16]\nstart = \"BB\"\n").unwrap();
+        let day15 = days::find(15).unwrap();
+        let day16 = days::find(16).unwrap();
+
+        let params = config.params(day15, &[]).unwrap();
+        assert_eq!(params.iter().collect::<Vec<_>>(), vec![("limit", &Value::Number(20)), ("row", &Value::Number(10))]);
+
+        // --param beats the file, and going back to the default leaves it unset
+        let params = config.params(day15, &overrides(&[("row", "11"), ("limit", "4000000")])).unwrap();
+        assert_eq!(params.iter().collect::<Vec<_>>(), vec![("row", &Value::Number(11))]);
+
+        let params = config.params(day16, &overrides(&[("minutes", "20")])).unwrap();
+        assert_eq!(params.iter().collect::<Vec<_>>(), vec![("minutes", &Value::Number(20)), ("start", &Value::Text("BB".to_string()))]);
+
+        assert!(config.params(days::find(7).unwrap(), &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_params_errors() {
+        let day15 = days::find(15).unwrap();
+
+        assert_eq!(
+            Config::parse("[day15]\nrows = 10\n").unwrap().params(day15, &[]).unwrap_err(),
+            "aoc.toml [day15]: unknown param `rows`, expected one of row, limit",
+        );
+
+        assert_eq!(
+            Config::default().params(days::find(1).unwrap(), &overrides(&[("row", "10")])).unwrap_err(),
+            "--param for day 1: unknown param `row`, this day doesn't take any",
+        );
+
+        assert!(Config::default().params(day15, &overrides(&[("row", "ten")])).is_err());
+        assert!(Config::parse("row = 10\n").is_err());
+        assert!(Config::parse("[day26]\nrow = 10\n").is_err());
+        assert!(Config::parse("[day15]\nrow = 1.5\n").is_err());
+    }
+}
@@ -0,0 +1,89 @@
+use common::{GenerateFn, Param, ParseFn, SimulateFn, Solution, StreamFn};
+
+pub struct Day {
+    pub number: u8,
+    pub parse: ParseFn,
+    // What `parse` can be given in its `Params`
+    pub params: &'static [Param],
+    // Makes up inputs from a seed and a size
+    pub generate: GenerateFn,
+    // For days that can go through their input a line at a time
+    pub stream: Option<StreamFn>,
+    // For days that play out a step at a time, and can be animated
+    pub simulate: Option<SimulateFn>,
+}
+
+// Day 19 never got done, so there's a gap.
+pub const DAYS: &[Day] = &[
+    Day { number: 1, parse: common::parse::<aoc01::Day01>, params: aoc01::Day01::PARAMS, generate: common::generate::<aoc01::Day01>, stream: Some(common::stream::<aoc01::Day01>), simulate: None },
+    Day { number: 2, parse: common::parse::<aoc02::Day02>, params: aoc02::Day02::PARAMS, generate: common::generate::<aoc02::Day02>, stream: Some(common::stream::<aoc02::Day02>), simulate: None },
+    Day { number: 3, parse: common::parse::<aoc03::Day03>, params: aoc03::Day03::PARAMS, generate: common::generate::<aoc03::Day03>, stream: None, simulate: None },
+    Day { number: 4, parse: common::parse::<aoc04::Day04>, params: aoc04::Day04::PARAMS, generate: common::generate::<aoc04::Day04>, stream: Some(common::stream::<aoc04::Day04>), simulate: None },
+    Day { number: 5, parse: common::parse::<aoc05::Day05>, params: aoc05::Day05::PARAMS, generate: common::generate::<aoc05::Day05>, stream: None, simulate: None },
+    Day { number: 6, parse: common::parse::<aoc06::Day06>, params: aoc06::Day06::PARAMS, generate: common::generate::<aoc06::Day06>, stream: None, simulate: None },
+    Day { number: 7, parse: common::parse::<aoc07::Day07>, params: aoc07::Day07::PARAMS, generate: common::generate::<aoc07::Day07>, stream: None, simulate: None },
+    Day { number: 8, parse: common::parse::<aoc08::Day08>, params: aoc08::Day08::PARAMS, generate: common::generate::<aoc08::Day08>, stream: None, simulate: None },
+    Day { number: 9, parse: common::parse::<aoc09::Day09>, params: aoc09::Day09::PARAMS, generate: common::generate::<aoc09::Day09>, stream: None, simulate: Some(common::simulate::<aoc09::Day09>) },
+    Day { number: 10, parse: common::parse::<aoc10::Day10>, params: aoc10::Day10::PARAMS, generate: common::generate::<aoc10::Day10>, stream: None, simulate: None },
+    Day { number: 11, parse: common::parse::<aoc11::Day11>, params: aoc11::Day11::PARAMS, generate: common::generate::<aoc11::Day11>, stream: None, simulate: None },
+    Day { number: 12, parse: common::parse::<aoc12::Day12>, params: aoc12::Day12::PARAMS, generate: common::generate::<aoc12::Day12>, stream: None, simulate: None },
+    Day { number: 13, parse: common::parse::<aoc13::Day13>, params: aoc13::Day13::PARAMS, generate: common::generate::<aoc13::Day13>, stream: None, simulate: None },
+    Day { number: 14, parse: common::parse::<aoc14::Day14>, params: aoc14::Day14::PARAMS, generate: common::generate::<aoc14::Day14>, stream: None, simulate: Some(common::simulate::<aoc14::Day14>) },
+    Day { number: 15, parse: common::parse::<aoc15::Day15>, params: aoc15::Day15::PARAMS, generate: common::generate::<aoc15::Day15>, stream: None, simulate: None },
+    Day { number: 16, parse: common::parse::<aoc16::Day16>, params: aoc16::Day16::PARAMS, generate: common::generate::<aoc16::Day16>, stream: None, simulate: None },
+    Day { number: 17, parse: common::parse::<aoc17::Day17>, params: aoc17::Day17::PARAMS, generate: common::generate::<aoc17::Day17>, stream: None, simulate: Some(common::simulate::<aoc17::Day17>) },
+    Day { number: 18, parse: common::parse::<aoc18::Day18>, params: aoc18::Day18::PARAMS, generate: common::generate::<aoc18::Day18>, stream: None, simulate: None },
+    Day { number: 20, parse: common::parse::<aoc20::Day20>, params: aoc20::Day20::PARAMS, generate: common::generate::<aoc20::Day20>, stream: None, simulate: None },
+    Day { number: 21, parse: common::parse::<aoc21::Day21>, params: aoc21::Day21::PARAMS, generate: common::generate::<aoc21::Day21>, stream: None, simulate: None },
+    Day { number: 22, parse: common::parse::<aoc22::Day22>, params: aoc22::Day22::PARAMS, generate: common::generate::<aoc22::Day22>, stream: None, simulate: None },
+    Day { number: 23, parse: common::parse::<aoc23::Day23>, params: aoc23::Day23::PARAMS, generate: common::generate::<aoc23::Day23>, stream: None, simulate: Some(common::simulate::<aoc23::Day23>) },
+    Day { number: 24, parse: common::parse::<aoc24::Day24>, params: aoc24::Day24::PARAMS, generate: common::generate::<aoc24::Day24>, stream: None, simulate: Some(common::simulate::<aoc24::Day24>) },
+    Day { number: 25, parse: common::parse::<aoc25::Day25>, params: aoc25::Day25::PARAMS, generate: common::generate::<aoc25::Day25>, stream: Some(common::stream::<aoc25::Day25>), simulate: None },
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{Params, Part};
+
+    use crate::days::*;
+
+    // Day 15's generated inputs are as big as the real one whatever the size,
+    // and its part two takes seconds a go in a debug build
+    const SLOW_PART_TWO: &[u8] = &[15];
+
+    fn solve_generated(seeds: std::ops::Range<u64>, sizes: std::ops::RangeInclusive<usize>, part_two: impl Fn(&Day, usize) -> bool) {
+        for day in DAYS {
+            for seed in seeds.clone() {
+                for size in sizes.clone() {
+                    let input = (day.generate)(seed, size);
+                    let parsed = (day.parse)(&input, &Params::new()).unwrap_or_else(|e| {
+                        panic!("day {} seed {seed} size {size}: {}", day.number, e.diagnostic(&input))
+                    });
+
+                    parsed.solve(Part::One);
+
+                    if part_two(day, size) {
+                        parsed.solve(Part::Two);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        // Part two is too slow in a debug build for some days at anything bigger
+        solve_generated(0..3, 1..=3, |day, size| size == 1 && !SLOW_PART_TWO.contains(&day.number));
+    }
+
+    // Every day's part two, at every size: `cargo test --release -- --ignored`
+    #[test]
+    #[ignore]
+    fn generated_inputs_solve_part_two() {
+        solve_generated(0..3, 1..=3, |_, _| true);
+    }
+}
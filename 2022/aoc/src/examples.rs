@@ -0,0 +1,133 @@
+//! The puzzle examples from `examples/dayNN.toml`, checked against what each
+//! day's solution makes of them. `build.rs` generates a `#[test]` for every
+//! manifest, which ends up at the bottom of this file.
+//!
+//! A manifest holds one or more examples:
+//!
+//! ```toml
+//! [[example]]
+//! params = { row = 10, limit = 20 }
+//! input = '''
+//! Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+//! '''
+//! part_one = 26
+//! part_two = 56000011
+//! ```
+//!
+//! `params` is optional and only needed where the example uses different
+//! values from the real input; they have to be ones the day lists in its
+//! `PARAMS`. A part without an answer isn't checked.
+
+use std::fs;
+use std::path::PathBuf;
+
+use common::{Params, Part};
+
+use crate::config;
+use crate::days::{self, Day};
+
+pub struct Example {
+    pub input: Vec<String>,
+    pub params: Params,
+    pub answers: Vec<(Part, String)>,
+}
+
+pub fn manifest_path(day: u8) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("examples")
+        .join(format!("day{day:02}.toml"))
+}
+
+pub fn load(day: &Day) -> Result<Vec<Example>, String> {
+    let path = manifest_path(day.number);
+    let text = fs::read_to_string(&path).map_err(|e| format!("couldn't read {}: {e}", path.display()))?;
+    let manifest: toml::Table = text.parse().map_err(|e| format!("{}: {e}", path.display()))?;
+
+    let examples = manifest
+        .get("example")
+        .and_then(|e| e.as_array())
+        .ok_or(format!("{}: no [[example]] tables", path.display()))?;
+
+    examples
+        .iter()
+        .enumerate()
+        .map(|(i, example)| parse_example(day, example).map_err(|e| format!("{} example {}: {e}", path.display(), i + 1)))
+        .collect()
+}
+
+fn parse_example(day: &Day, example: &toml::Value) -> Result<Example, String> {
+    let input = example
+        .get("input")
+        .and_then(|i| i.as_str())
+        .ok_or("no input")?
+        .lines()
+        .map(String::from)
+        .collect();
+
+    let mut params = Params::new();
+
+    if let Some(table) = example.get("params") {
+        let table = table.as_table().ok_or("params should be a table")?;
+
+        for (name, value) in table {
+            let raw = config::raw_value(value).ok_or(format!("param {name} should be a number or a string"))?;
+            params.apply(day.params, name, &raw)?;
+        }
+    }
+
+    let mut answers = vec![];
+
+    for (key, part) in [("part_one", Part::One), ("part_two", Part::Two)] {
+        let answer = match example.get(key) {
+            None => continue,
+            Some(toml::Value::Integer(n)) => n.to_string(),
+            // Multi-line answers (day 10) end with a newline in the manifest
+            Some(toml::Value::String(s)) => s.trim_end().to_string(),
+            Some(_) => return Err(format!("{key} should be a number or a string")),
+        };
+
+        answers.push((part, answer));
+    }
+
+    Ok(Example { input, params, answers })
+}
+
+fn check_day(day: u8) {
+    let solution = days::find(day).unwrap_or_else(|| panic!("day {day} has an example but no solution"));
+    let examples = load(solution).unwrap();
+    let mut failures = vec![];
+
+    for (i, example) in examples.iter().enumerate() {
+        let parsed = (solution.parse)(&example.input, &example.params)
+            .unwrap_or_else(|e| panic!("example {}: {}", i + 1, e.diagnostic(&example.input)));
+
+        for (part, expected) in &example.answers {
+            let actual = parsed.solve(*part).to_string();
+
+            if actual != *expected {
+                failures.push(format!(
+                    "example {} part {}: expected {expected}, got {actual}",
+                    i + 1,
+                    part.number(),
+                ));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "day {day}:\n{}", failures.join("\n"));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::examples::*;
+
+    #[test]
+    fn every_day_has_examples() {
+        for day in days::DAYS {
+            assert!(manifest_path(day.number).exists(), "no examples for day {}", day.number);
+        }
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/example_tests.rs"));
+}
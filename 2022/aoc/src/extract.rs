@@ -0,0 +1,349 @@
+//! Examples taken out of a saved puzzle page rather than copied by hand: the
+//! `<pre><code>` blocks, and the answers, which are the last `<code><em>` in
+//! each part's description.
+//!
+//! A page often has more than one block (a small example, then a larger one,
+//! or the steps of one being worked through), so they're numbered in the
+//! order they come, and one of them is picked as the example. Each part's
+//! answer goes with the first block in its description, as the worked steps
+//! come after the example they're for, and part two mostly goes back over
+//! part one's example without a block of its own. Either part can be told
+//! otherwise, for the pages where the answer is for a later block.
+
+use std::fs;
+use std::path::Path;
+
+// Lines of each block `--list` shows
+const PREVIEW_LINES: usize = 4;
+
+#[derive(Debug, PartialEq)]
+pub struct Page {
+    // "Day 23: Unstable Diffusion"
+    pub title: Option<String>,
+    pub blocks: Vec<Block>,
+    // Part one's and part two's, if the page got that far
+    pub answers: [Option<String>; 2],
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Block {
+    pub text: String,
+    // 1 or 2, whichever part's description it's in
+    pub part: usize,
+}
+
+impl Page {
+    pub fn parse(html: &str) -> Page {
+        let title = between_all(html, "<h2", "</h2>")
+            .into_iter()
+            .map(|heading| text_of(heading.split_once('>').map_or(heading, |(_, h)| h)))
+            .map(|heading| heading.trim_matches(|c: char| c == '-' || c.is_whitespace()).to_string())
+            .find(|heading| heading.starts_with("Day "));
+
+        let mut blocks = vec![];
+        let mut answers = [None, None];
+
+        for (i, article) in between_all(html, "<article", "</article>").into_iter().take(2).enumerate() {
+            for code in between_all(article, "<pre><code>", "</code></pre>") {
+                blocks.push(Block { text: text_of(code), part: i + 1 });
+            }
+
+            answers[i] = last_emphasised(article);
+        }
+
+        Page { title, blocks, answers }
+    }
+
+    /// The day's number, going by the title.
+    pub fn day(&self) -> Option<u8> {
+        self.title.as_ref()?.strip_prefix("Day ")?.split(':').next()?.parse().ok()
+    }
+
+    /// What's on the page, to pick a block from, going by where the
+    /// answers would go without being told.
+    pub fn listing(&self) -> String {
+        let mut lines = vec![self.title.clone().unwrap_or("(no title)".to_string())];
+
+        for (i, block) in self.blocks.iter().enumerate() {
+            let block_lines: Vec<&str> = block.text.lines().collect();
+            let answered: Vec<String> = (1..=2)
+                .filter(|part| self.answers[part - 1].is_some() && self.answered_block(*part, &[None, None]) == Some(i + 1))
+                .map(|part| format!(", part {part}'s answer"))
+                .collect();
+
+            lines.push(format!("\nBlock {} (part {}, {} lines{}):", i + 1, block.part, block_lines.len(), answered.concat()));
+
+            lines.extend(block_lines.iter().take(PREVIEW_LINES).map(|line| format!("  {line}")));
+
+            if block_lines.len() > PREVIEW_LINES {
+                lines.push(format!("  ... and {} more", block_lines.len() - PREVIEW_LINES));
+            }
+        }
+
+        let answer = |part: usize| self.answers[part - 1].clone().unwrap_or("none found".to_string());
+        lines.push(format!("\nAnswers: part 1 {}, part 2 {}", answer(1), answer(2)));
+
+        lines.join("\n") + "\n"
+    }
+
+    /// A manifest with block `number` (from 1) as the example, for
+    /// `examples/dayNN.toml`, and a note for each answer on the page that
+    /// was left out of it for being some other block's. `answer_blocks`
+    /// says which block each part's answer is for, where the default's wrong.
+    pub fn manifest(&self, number: usize, answer_blocks: &[Option<usize>; 2]) -> Result<(String, Vec<String>), String> {
+        let block = self.block(number)?;
+
+        for answered in answer_blocks.iter().flatten() {
+            self.block(*answered)?;
+        }
+
+        let mut left_out = vec![];
+
+        let mut manifest = format!("# {}\n\n[[example]]\n", self.title.as_deref().unwrap_or("Puzzle example"));
+
+        // Literal, like the ones written by hand, unless the input gets in the way
+        if block.text.contains("'''") {
+            manifest += &format!("input = {}\n", toml::Value::String(block.text.clone()));
+        } else {
+            manifest += &format!("input = '''\n{}'''\n", block.text);
+        }
+
+        let answers = [("part_one", &self.answers[0]), ("part_two", &self.answers[1])];
+
+        for (part, (key, answer)) in answers.into_iter().enumerate() {
+            let Some(answer) = answer else {
+                continue;
+            };
+
+            match self.answered_block(part + 1, answer_blocks) {
+                Some(answered) if answered == number => {}
+
+                Some(answered) => {
+                    left_out.push(format!("left out part {}'s answer {answer}, it's for block {answered}", part + 1));
+                    continue;
+                }
+
+                None => continue,
+            }
+
+            match answer.parse::<i64>() {
+                Ok(n) => manifest += &format!("{key} = {n}\n"),
+                Err(_) => manifest += &format!("{key} = {}\n", toml::Value::String(answer.clone())),
+            }
+        }
+
+        Ok((manifest, left_out))
+    }
+
+    // The block (from 1) `part`'s answer is for: the one it was told, or the
+    // first in its description, or for part two without any, the same as
+    // part one's
+    fn answered_block(&self, part: usize, answer_blocks: &[Option<usize>; 2]) -> Option<usize> {
+        let first_in = |part: usize| self.blocks.iter().position(|b| b.part == part).map(|i| i + 1);
+
+        answer_blocks[part - 1].or_else(|| match part {
+            2 => first_in(2).or_else(|| self.answered_block(1, answer_blocks)),
+            _ => first_in(part),
+        })
+    }
+
+    /// Block `number` (from 1).
+    pub fn block(&self, number: usize) -> Result<&Block, String> {
+        match self.blocks.len() {
+            0 => Err("the page has no <pre><code> blocks".to_string()),
+            count => self
+                .blocks
+                .get(number.wrapping_sub(1))
+                .ok_or(format!("there's no block {number}, the page has {count}, see --list")),
+        }
+    }
+}
+
+/// Writes `manifest` to `path`, unless there's one there with an example
+/// in it already and not `force`. The empty one `aoc new` starts a day with
+/// doesn't count.
+pub fn write_manifest(path: &Path, manifest: &str, force: bool) -> Result<(), String> {
+    if !force && path.exists() {
+        let existing = fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {e}", path.display()))?;
+        let existing: toml::Table = existing.parse().map_err(|e| format!("{}: {e}", path.display()))?;
+
+        let filled_in = existing
+            .get("example")
+            .and_then(|e| e.as_array())
+            .is_some_and(|examples| examples.iter().any(|e| e.get("input").and_then(|i| i.as_str()).is_some_and(|i| !i.trim().is_empty())));
+
+        if filled_in {
+            return Err(format!("{} already has an example, --force replaces it", path.display()));
+        }
+    }
+
+    fs::write(path, manifest).map_err(|e| format!("couldn't write {}: {e}", path.display()))
+}
+
+// Everything between each `open` and the `close` after it
+fn between_all<'a>(text: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut found = vec![];
+    let mut rest = text;
+
+    while let Some((_, after)) = rest.split_once(open) {
+        let Some((inside, after)) = after.split_once(close) else {
+            break;
+        };
+
+        found.push(inside);
+        rest = after;
+    }
+
+    found
+}
+
+// The last `<code><em>` in a part's description, or `<em><code>`, as some
+// years have it
+fn last_emphasised(article: &str) -> Option<String> {
+    [("<code><em>", "</em></code>"), ("<em><code>", "</code></em>")]
+        .iter()
+        .filter_map(|(open, close)| {
+            let start = article.rfind(open)? + open.len();
+            let (answer, _) = article[start..].split_once(close)?;
+            Some((start, answer))
+        })
+        .max_by_key(|(start, _)| *start)
+        .map(|(_, answer)| text_of(answer).trim().to_string())
+}
+
+// Without tags, and with the entities the site uses turned back into text
+fn text_of(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::extract::*;
+
+    // Cut down from a saved page, with part two unlocked
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us"><head><title>Day 9 - Advent of Code 2022</title></head><body>
+<main>
+<article class="day-desc"><h2>--- Day 9: Rope Bridge ---</h2><p>For example:</p>
+<pre><code>R 4
+U 4
+</code></pre>
+<p>The head moves like this:</p>
+<pre><code>......
+....<em>H</em>.
+s&lt;..T.
+</code></pre>
+<p>So, there are <code><em>13</em></code> positions the tail visited at least once.</p>
+</article>
+<p>Your puzzle answer was <code>6236</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Here's a larger example:</p>
+<pre><code>R 5
+U 8
+</code></pre>
+<p>Now the tail (<code>9</code>) visits <code><em>36</em></code> positions.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn test_parse() {
+        let page = Page::parse(PAGE);
+
+        assert_eq!(page.title, Some("Day 9: Rope Bridge".to_string()));
+        assert_eq!(page.day(), Some(9));
+        assert_eq!(page.answers, [Some("13".to_string()), Some("36".to_string())]);
+
+        let blocks: Vec<(&str, usize)> = page.blocks.iter().map(|b| (b.text.as_str(), b.part)).collect();
+        assert_eq!(blocks, vec![("R 4\nU 4\n", 1), ("......\n....H.\ns<..T.\n", 1), ("R 5\nU 8\n", 2)]);
+
+        let listing = page.listing();
+        assert!(listing.contains("Block 1 (part 1, 2 lines, part 1's answer):"));
+        assert!(listing.contains("Block 2 (part 1, 3 lines):"));
+        assert!(listing.contains("Block 3 (part 2, 2 lines, part 2's answer):"));
+
+        let older = Page::parse("<article><p>It's <em><code>42</code></em>, then <code>7</code>.</p></article>");
+        assert_eq!(older.answers, [Some("42".to_string()), None]);
+        assert_eq!(older.title, None);
+    }
+
+    #[test]
+    fn test_manifest() {
+        let page = Page::parse(PAGE);
+
+        let default = [None, None];
+
+        // The example comes before the steps worked through on it, so the
+        // answers are for the first block in each part
+        assert_eq!(
+            page.manifest(1, &default),
+            Ok((
+                "# Day 9: Rope Bridge\n\n[[example]]\ninput = '''\nR 4\nU 4\n'''\npart_one = 13\n".to_string(),
+                vec!["left out part 2's answer 36, it's for block 3".to_string()],
+            )),
+        );
+
+        assert_eq!(
+            page.manifest(3, &default),
+            Ok((
+                "# Day 9: Rope Bridge\n\n[[example]]\ninput = '''\nR 5\nU 8\n'''\npart_two = 36\n".to_string(),
+                vec!["left out part 1's answer 13, it's for block 1".to_string()],
+            )),
+        );
+
+        // Nothing's for the worked steps
+        let (manifest, left_out) = page.manifest(2, &default).unwrap();
+        assert_eq!(manifest, "# Day 9: Rope Bridge\n\n[[example]]\ninput = '''\n......\n....H.\ns<..T.\n'''\n");
+        assert_eq!(left_out.len(), 2);
+
+        // Unless told otherwise, a part at a time
+        let (manifest, left_out) = page.manifest(2, &[Some(2), None]).unwrap();
+        assert!(manifest.ends_with("part_one = 13\n"));
+        assert_eq!(left_out, vec!["left out part 2's answer 36, it's for block 3".to_string()]);
+        assert!(page.manifest(1, &[None, Some(1)]).unwrap().1.is_empty());
+        assert_eq!(page.manifest(1, &[Some(5), None]), Err("there's no block 5, the page has 3, see --list".to_string()));
+
+        // Part two going over part one's example
+        let reused = Page::parse("<article><pre><code>1\n</code></pre><code><em>2</em></code></article><article><code><em>3</em></code></article>");
+        assert_eq!(reused.manifest(1, &default), Ok(("# Puzzle example\n\n[[example]]\ninput = '''\n1\n'''\npart_one = 2\npart_two = 3\n".to_string(), vec![])));
+
+        assert_eq!(page.manifest(4, &default), Err("there's no block 4, the page has 3, see --list".to_string()));
+        assert_eq!(page.manifest(0, &default), Err("there's no block 0, the page has 3, see --list".to_string()));
+
+        // Whatever it writes reads back the same
+        let quoted = Page::parse("<article><pre><code>'''\n</code></pre><code><em>ABC</em></code></article>");
+        let manifest: toml::Table = quoted.manifest(1, &default).unwrap().0.parse().unwrap();
+        assert_eq!(manifest["example"][0]["input"].as_str(), Some("'''\n"));
+        assert_eq!(manifest["example"][0]["part_one"].as_str(), Some("ABC"));
+    }
+
+    #[test]
+    fn test_write_manifest() {
+        let path = std::env::temp_dir().join(format!("aoc-test-extract-{}.toml", std::process::id()));
+
+        // The empty one from `aoc new` gets filled in
+        fs::write(&path, "# Day 9\n\n[[example]]\ninput = '''\n'''\n").unwrap();
+        assert_eq!(write_manifest(&path, "# Day 9: Rope Bridge\n", false), Ok(()));
+
+        fs::write(&path, "[[example]]\ninput = '''\nR 4\n'''\n").unwrap();
+        assert!(write_manifest(&path, "# Day 9: Rope Bridge\n", false).unwrap_err().contains("--force"));
+        assert_eq!(write_manifest(&path, "# Day 9: Rope Bridge\n", true), Ok(()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# Day 9: Rope Bridge\n");
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,74 @@
+//! Puzzle inputs from the site, each downloaded once and kept in a cache
+//! (`.cache/inputs/<year>/dayNN.txt`, git ignored) from then on. An input
+//! never changes once it's out, so there's no reason to ask for it twice.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::site::{Site, SiteError};
+
+pub const DEFAULT_CACHE: &str = ".cache/inputs";
+
+pub fn cached_path(cache: &Path, year: u16, day: u8) -> PathBuf {
+    cache.join(year.to_string()).join(format!("day{day:02}.txt"))
+}
+
+/// The input for `day` of `year`, and whether it had to be downloaded for it.
+pub fn input(site: &Site, cache: &Path, year: u16, day: u8) -> Result<(String, bool), String> {
+    let path = cached_path(cache, year, day);
+
+    if path.exists() {
+        let text = fs::read_to_string(&path).map_err(|e| format!("couldn't read {}: {e}", path.display()))?;
+        return Ok((text, false));
+    }
+
+    let text = site.get(&format!("/{year}/day/{day}/input")).map_err(|e| match e {
+        SiteError::NotFound => format!("day {day} of {year} isn't unlocked yet, or doesn't exist"),
+        e => format!("couldn't fetch day {day} of {year}: {e}"),
+    })?;
+
+    if text.trim().is_empty() {
+        return Err(format!("the input for day {day} of {year} came back empty"));
+    }
+
+    // Written alongside first, so that a download cut short never looks cached
+    let partial = path.with_extension("part");
+    let parent = path.parent().unwrap_or(cache);
+
+    fs::create_dir_all(parent).map_err(|e| format!("couldn't create {}: {e}", parent.display()))?;
+    fs::write(&partial, &text).map_err(|e| format!("couldn't write {}: {e}", partial.display()))?;
+    fs::rename(&partial, &path).map_err(|e| format!("couldn't write {}: {e}", path.display()))?;
+
+    Ok((text, true))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fetch::*;
+    use crate::stand_in::StandIn;
+
+    #[test]
+    fn test_input() {
+        let cache = std::env::temp_dir().join(format!("aoc-test-fetch-{}", std::process::id()));
+        let stand_in = StandIn::start(&[
+            ("GET", "/2022/day/7/input", 200, "$ cd /\n$ ls\n"),
+            ("GET", "/2022/day/8/input", 400, "Puzzle inputs differ by user.  Please log in to get your puzzle input."),
+        ]);
+
+        let site = Site::new(&stand_in.url, Some("abc123".to_string()));
+
+        assert_eq!(input(&site, &cache, 2022, 7), Ok(("$ cd /\n$ ls\n".to_string(), true)));
+        assert_eq!(fs::read_to_string(cached_path(&cache, 2022, 7)).unwrap(), "$ cd /\n$ ls\n");
+
+        // The second time comes from the cache, without a session even
+        let offline = Site::new(&stand_in.url, None);
+        assert_eq!(input(&offline, &cache, 2022, 7), Ok(("$ cd /\n$ ls\n".to_string(), false)));
+        assert_eq!(stand_in.requests().len(), 1);
+
+        assert_eq!(input(&site, &cache, 2022, 25), Err("day 25 of 2022 isn't unlocked yet, or doesn't exist".to_string()));
+        assert!(input(&site, &cache, 2022, 8).unwrap_err().contains("session token wasn't accepted"));
+        assert!(!cached_path(&cache, 2022, 8).exists());
+
+        fs::remove_dir_all(&cache).unwrap();
+    }
+}
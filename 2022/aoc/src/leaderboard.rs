@@ -0,0 +1,491 @@
+//! Reports on a private leaderboard, from the JSON the site gives out for it:
+//! who's ahead on local score, how many stars each member has, and for every
+//! day, how long each took over part one and then part two.
+//!
+//! The site asks for a leaderboard's JSON no more than once every 15 minutes,
+//! so a downloaded copy (`.cache/leaderboards/<year>/<id>.json` by default,
+//! git ignored) is used instead until it's older than that.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::cli::ReportFormat;
+use crate::report;
+use crate::site::{Site, SiteError};
+
+pub const DEFAULT_CACHE: &str = ".cache/leaderboards";
+
+// How long a downloaded copy will do, the least the site asks to wait
+const FRESH_FOR: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, PartialEq)]
+pub struct Leaderboard {
+    pub year: u16,
+    pub members: Vec<Member>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Member {
+    pub id: u64,
+    pub name: String,
+    pub local_score: u64,
+    pub stars: u64,
+    // For each day with a star, when each part's star came (seconds since the
+    // Unix epoch)
+    pub days: BTreeMap<u8, [Option<u64>; 2]>,
+}
+
+impl Member {
+    fn last_star(&self) -> u64 {
+        self.days.values().flatten().flatten().max().copied().unwrap_or(0)
+    }
+}
+
+/// When `day` of `year` unlocked: midnight US Eastern, 05:00 UTC.
+pub fn unlock_time(year: u16, day: u8) -> u64 {
+    (days_from_civil(year as i64, 12, day as i64) * 86400 + 5 * 3600) as u64
+}
+
+// Days from 1970-01-01 to the date, after Howard Hinnant's days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146097 + day_of_era - 719468
+}
+
+/// The JSON for leaderboard `id` of `year`, from the cache while it's fresh.
+pub fn download(site: &Site, cache: &Path, year: u16, id: u64) -> Result<String, String> {
+    let path = cached_path(cache, year, id);
+    let age = fs::metadata(&path).and_then(|m| m.modified()).ok().and_then(|t| SystemTime::now().duration_since(t).ok());
+
+    if let Some(age) = age.filter(|age| *age < FRESH_FOR) {
+        common::info!("using the copy of leaderboard {id} from {}s ago", age.as_secs());
+        return fs::read_to_string(&path).map_err(|e| format!("couldn't read {}: {e}", path.display()));
+    }
+
+    let text = site.get(&format!("/{year}/leaderboard/private/view/{id}.json")).map_err(|e| match e {
+        SiteError::NotFound => format!("there's no leaderboard {id} for {year}, or it isn't yours to see"),
+        e => format!("couldn't fetch leaderboard {id} of {year}: {e}"),
+    })?;
+
+    // Without access, the site sends its login page rather than an error
+    if !text.trim_start().starts_with('{') {
+        return Err(format!("leaderboard {id} of {year} didn't come back as JSON, check the session token"));
+    }
+
+    let parent = path.parent().unwrap_or(cache);
+    fs::create_dir_all(parent).map_err(|e| format!("couldn't create {}: {e}", parent.display()))?;
+    fs::write(&path, &text).map_err(|e| format!("couldn't write {}: {e}", path.display()))?;
+
+    Ok(text)
+}
+
+fn cached_path(cache: &Path, year: u16, id: u64) -> PathBuf {
+    cache.join(year.to_string()).join(format!("{id}.json"))
+}
+
+impl Leaderboard {
+    // {
+    //   "event": "2022", "owner_id": 1,
+    //   "members": {
+    //     "1": {
+    //       "id": 1, "name": "Alice", "local_score": 20, "stars": 3, ...
+    //       "completion_day_level": { "1": { "1": { "get_star_ts": 1669870812, ... }, "2": { ... } } }
+    //     }
+    //   }
+    // }
+    pub fn from_json(text: &str) -> Result<Leaderboard, String> {
+        let json = json::parse(text).map_err(|e| e.to_string())?;
+
+        let year = json["event"]
+            .as_str()
+            .and_then(|event| event.parse().ok())
+            .ok_or("no \"event\" year")?;
+
+        if !json["members"].is_object() {
+            return Err("no \"members\"".to_string());
+        }
+
+        let mut members = vec![];
+
+        for (key, member) in json["members"].entries() {
+            let id = member["id"].as_u64().ok_or(format!("member {key} has no id"))?;
+
+            // Anonymous members have no name, and the site shows them like this
+            let name = member["name"].as_str().map(String::from).unwrap_or(format!("(anonymous user #{id})"));
+
+            let mut days = BTreeMap::new();
+
+            for (day, parts) in member["completion_day_level"].entries() {
+                let day: u8 = day.parse().ok().filter(|d| (1..=25).contains(d)).ok_or(format!("{name} has a star on day `{day}`"))?;
+                let star = |part: &str| parts[part]["get_star_ts"].as_u64();
+
+                days.insert(day, [star("1"), star("2")]);
+            }
+
+            members.push(Member {
+                id,
+                name,
+                local_score: member["local_score"].as_u64().unwrap_or(0),
+                stars: member["stars"].as_u64().unwrap_or(0),
+                days,
+            });
+        }
+
+        Ok(Leaderboard { year, members })
+    }
+
+    /// The members best first: highest local score, then most stars, then
+    /// whoever got their last star first.
+    pub fn ranking(&self) -> Vec<&Member> {
+        let mut ranking: Vec<&Member> = self.members.iter().collect();
+
+        ranking.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.last_star().cmp(&b.last_star()))
+                .then(a.name.cmp(&b.name))
+        });
+
+        ranking
+    }
+
+    /// The whole report, as text, Markdown or CSV.
+    pub fn report(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Text => self.to_text(),
+            ReportFormat::Markdown => self.to_markdown(),
+            ReportFormat::Csv => self.to_csv(),
+        }
+    }
+
+    // Rank, name, score, stars and a strip of each day's stars, as the site
+    // shows them: * for both, + for only part one
+    fn ranking_rows(&self) -> Vec<Vec<String>> {
+        let last_day = self.members.iter().flat_map(|m| m.days.keys()).max().copied().unwrap_or(0);
+        let ranking = self.ranking();
+
+        let mut rows = vec![["Rank", "Member", "Score", "Stars", "Days"].map(String::from).to_vec()];
+
+        for (i, member) in ranking.iter().enumerate() {
+            // Members on the same score share a rank
+            let rank = ranking.iter().position(|m| m.local_score == member.local_score).unwrap_or(i) + 1;
+
+            let strip: String = (1..=last_day)
+                .map(|day| match member.days.get(&day) {
+                    Some([Some(_), Some(_)]) => '*',
+                    Some([Some(_), None]) => '+',
+                    _ => '.',
+                })
+                .collect();
+
+            rows.push(vec![rank.to_string(), member.name.clone(), member.local_score.to_string(), member.stars.to_string(), strip]);
+        }
+
+        rows
+    }
+
+    // For every day, each member who got a star on it: how long after the
+    // unlock part one took, part two took, and the gap between them. Quickest
+    // part two first, with part one only after.
+    fn delta_rows(&self) -> Vec<Vec<String>> {
+        let mut rows = vec![["Day", "Member", "Part 1", "Part 2", "Delta"].map(String::from).to_vec()];
+
+        for (day, member, [one, two]) in self.completions() {
+            let unlock = unlock_time(self.year, day);
+
+            rows.push(vec![
+                format!("{day:02}"),
+                member.name.clone(),
+                one.map(|one| elapsed(one.saturating_sub(unlock))).unwrap_or_default(),
+                two.map(|two| elapsed(two.saturating_sub(unlock))).unwrap_or_default(),
+                one.zip(two).map(|(one, two)| elapsed(two.saturating_sub(one))).unwrap_or_default(),
+            ]);
+        }
+
+        rows
+    }
+
+    fn completions(&self) -> Vec<(u8, &Member, [Option<u64>; 2])> {
+        let mut completions: Vec<(u8, &Member, [Option<u64>; 2])> = self
+            .members
+            .iter()
+            .flat_map(|member| member.days.iter().map(move |(day, stars)| (*day, member, *stars)))
+            .collect();
+
+        completions.sort_by_key(|(day, member, [one, two])| (*day, two.is_none(), *two, *one, member.name.clone()));
+        completions
+    }
+
+    fn to_text(&self) -> String {
+        format!(
+            "Private leaderboard, {} ({} members)\n\n{}\n\n{}\n",
+            self.year,
+            self.members.len(),
+            report::columns(&self.ranking_rows(), &[true, false, true, true, false]),
+            report::columns(&self.delta_rows(), &[false, false, true, true, true]),
+        )
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut ranking = self.ranking_rows();
+        let mut deltas = self.delta_rows();
+
+        for row in ranking.iter_mut().skip(1) {
+            // Stars and pluses would be taken for emphasis
+            row[4] = format!("`{}`", row[4]);
+            row[1] = markdown_text(&row[1]);
+        }
+
+        for row in deltas.iter_mut().skip(1) {
+            row[1] = markdown_text(&row[1]);
+        }
+
+        format!(
+            "# Private leaderboard, {}\n\n## Ranking\n\n{}\n\n## Days\n\n{}\n",
+            self.year,
+            markdown_table(&ranking, &[true, false, true, true, false]),
+            markdown_table(&deltas, &[false, false, true, true, true]),
+        )
+    }
+
+    // One row for each member and day, for spreadsheets, with the times in
+    // seconds. Members without a star yet get a row without a day.
+    fn to_csv(&self) -> String {
+        let ranking: Vec<Vec<String>> = self.ranking_rows().into_iter().skip(1).collect();
+        let completions = self.completions();
+
+        let mut lines = vec!["rank,member,score,stars,day,part_one,part_two,delta".to_string()];
+
+        for (member, row) in self.ranking().iter().zip(&ranking) {
+            let ranked = format!("{},{},{},{}", row[0], csv_field(&member.name), row[2], row[3]);
+            let days: Vec<&(u8, &Member, [Option<u64>; 2])> = completions.iter().filter(|(_, m, _)| m.id == member.id).collect();
+
+            if days.is_empty() {
+                lines.push(format!("{ranked},,,,"));
+            }
+
+            for (day, _, [one, two]) in days {
+                let unlock = unlock_time(self.year, *day);
+                let seconds = |time: Option<u64>| time.map(|t| t.saturating_sub(unlock).to_string()).unwrap_or_default();
+                let delta = one.zip(*two).map(|(one, two)| two.saturating_sub(one).to_string()).unwrap_or_default();
+
+                lines.push(format!("{ranked},{day},{},{},{delta}", seconds(*one), seconds(*two)));
+            }
+        }
+
+        lines.join("\n") + "\n"
+    }
+}
+
+// Seconds as h:mm:ss, with days in front once there are any
+fn elapsed(seconds: u64) -> String {
+    let (days, seconds) = (seconds / 86400, seconds % 86400);
+    let time = format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+fn markdown_table(rows: &[Vec<String>], right_aligned: &[bool]) -> String {
+    let line = |cells: Vec<String>| format!("| {} |", cells.join(" | "));
+    let escaped = |row: &Vec<String>| row.iter().map(|cell| cell.replace('|', "\\|")).collect();
+
+    let rule = right_aligned.iter().map(|right| if *right { "---:" } else { "---" }.to_string()).collect();
+
+    let mut lines = vec![line(escaped(&rows[0])), line(rule)];
+    lines.extend(rows[1..].iter().map(|row| line(escaped(row))));
+    lines.join("\n")
+}
+
+// A member's name as plain text, not emphasis, code, a link or HTML. Pipes are
+// left to `markdown_table`, which escapes them in every cell.
+fn markdown_text(text: &str) -> String {
+    let mut escaped = String::new();
+
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '~' | '&') {
+            escaped.push('\\');
+        }
+
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::leaderboard::*;
+    use crate::stand_in::StandIn;
+
+    // Day 1 unlocked at 1669870800, day 2 at 1669957200
+    const LEADERBOARD: &str = r#"{
+        "event": "2022",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "Alice", "local_score": 12, "stars": 4, "global_score": 0, "last_star_ts": 1669957800,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1669871100, "star_index": 0 }, "2": { "get_star_ts": 1669871400, "star_index": 1 } },
+                    "2": { "1": { "get_star_ts": 1669957500, "star_index": 4 }, "2": { "get_star_ts": 1669957800, "star_index": 5 } }
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "local_score": 7, "stars": 3, "global_score": 0, "last_star_ts": 1669961000,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1669871000, "star_index": 2 }, "2": { "get_star_ts": 1669880000, "star_index": 3 } },
+                    "2": { "1": { "get_star_ts": 1669961000, "star_index": 6 } }
+                }
+            },
+            "3": {
+                "id": 3, "name": "Carol, Esq.", "local_score": 0, "stars": 0, "global_score": 0, "last_star_ts": 0,
+                "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_unlock_time() {
+        assert_eq!(unlock_time(2022, 1), 1669870800);
+        assert_eq!(unlock_time(2022, 25), 1671944400);
+        assert_eq!(unlock_time(2015, 1), 1448946000);
+    }
+
+    #[test]
+    fn test_from_json() {
+        let leaderboard = Leaderboard::from_json(LEADERBOARD).unwrap();
+        assert_eq!(leaderboard.year, 2022);
+
+        let names: Vec<&str> = leaderboard.ranking().iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["Alice", "(anonymous user #2)", "Carol, Esq."]);
+
+        let anonymous = leaderboard.ranking()[1];
+        assert_eq!(anonymous.days[&2], [Some(1669961000), None]);
+
+        assert!(Leaderboard::from_json("{}").is_err());
+        assert!(Leaderboard::from_json(r#"{ "event": "2022", "members": { "1": { "name": "Alice" } } }"#).is_err());
+    }
+
+    #[test]
+    fn test_report() {
+        let leaderboard = Leaderboard::from_json(LEADERBOARD).unwrap();
+
+        assert_eq!(
+            leaderboard.report(ReportFormat::Text),
+            "Private leaderboard, 2022 (3 members)\n\
+             \n\
+             Rank  Member               Score  Stars  Days\n   \
+             1  Alice                   12      4  **\n   \
+             2  (anonymous user #2)      7      3  *+\n   \
+             3  Carol, Esq.              0      0  ..\n\
+             \n\
+             Day  Member                Part 1   Part 2    Delta\n\
+             01   Alice                0:05:00  0:10:00  0:05:00\n\
+             01   (anonymous user #2)  0:03:20  2:33:20  2:30:00\n\
+             02   Alice                0:05:00  0:10:00  0:05:00\n\
+             02   (anonymous user #2)  1:03:20\n",
+        );
+
+        assert_eq!(
+            leaderboard.report(ReportFormat::Markdown),
+            "# Private leaderboard, 2022\n\
+             \n\
+             ## Ranking\n\
+             \n\
+             | Rank | Member | Score | Stars | Days |\n\
+             | ---: | --- | ---: | ---: | --- |\n\
+             | 1 | Alice | 12 | 4 | `**` |\n\
+             | 2 | (anonymous user #2) | 7 | 3 | `*+` |\n\
+             | 3 | Carol, Esq. | 0 | 0 | `..` |\n\
+             \n\
+             ## Days\n\
+             \n\
+             | Day | Member | Part 1 | Part 2 | Delta |\n\
+             | --- | --- | ---: | ---: | ---: |\n\
+             | 01 | Alice | 0:05:00 | 0:10:00 | 0:05:00 |\n\
+             | 01 | (anonymous user #2) | 0:03:20 | 2:33:20 | 2:30:00 |\n\
+             | 02 | Alice | 0:05:00 | 0:10:00 | 0:05:00 |\n\
+             | 02 | (anonymous user #2) | 1:03:20 |  |  |\n",
+        );
+
+        assert_eq!(
+            leaderboard.report(ReportFormat::Csv),
+            "rank,member,score,stars,day,part_one,part_two,delta\n\
+             1,Alice,12,4,1,300,600,300\n\
+             1,Alice,12,4,2,300,600,300\n\
+             2,(anonymous user #2),7,3,1,200,9200,9000\n\
+             2,(anonymous user #2),7,3,2,3800,,\n\
+             3,\"Carol, Esq.\",0,0,,,,\n",
+        );
+    }
+
+    #[test]
+    fn test_markdown_names() {
+        let member = |id, name: &str| Member {
+            id,
+            name: name.to_string(),
+            local_score: 10 - id,
+            stars: 1,
+            days: BTreeMap::from([(1, [Some(1669871100), None])]),
+        };
+
+        let leaderboard = Leaderboard {
+            year: 2022,
+            members: vec![member(1, "*_Bob_*"), member(2, "[me](https://example.com) <b>&amp;</b>"), member(3, "a|b\\c`d`~e~")],
+        };
+
+        let markdown = leaderboard.report(ReportFormat::Markdown);
+
+        assert!(markdown.contains("| 1 | \\*\\_Bob\\_\\* | 9 | 1 | `+` |"));
+        assert!(markdown.contains("| 2 | \\[me\\](https://example.com) \\<b\\>\\&amp;\\</b\\> | 8 | 1 | `+` |"));
+        assert!(markdown.contains("| 3 | a\\|b\\\\c\\`d\\`\\~e\\~ | 7 | 1 | `+` |"));
+        assert!(markdown.contains("| 01 | \\*\\_Bob\\_\\* | 0:05:00 |  |  |"));
+    }
+
+    #[test]
+    fn test_elapsed() {
+        assert_eq!(elapsed(59), "0:00:59");
+        assert_eq!(elapsed(3 * 3600 + 125), "3:02:05");
+        assert_eq!(elapsed(2 * 86400 + 3600), "2d 1:00:00");
+    }
+
+    #[test]
+    fn test_download() {
+        let cache = std::env::temp_dir().join(format!("aoc-test-leaderboard-{}", std::process::id()));
+        let stand_in = StandIn::start(&[
+            ("GET", "/2022/leaderboard/private/view/123.json", 200, LEADERBOARD),
+            ("GET", "/2022/leaderboard/private/view/456.json", 200, "<html>Log in</html>"),
+        ]);
+
+        let site = Site::new(&stand_in.url, Some("abc123".to_string()));
+
+        assert_eq!(download(&site, &cache, 2022, 123), Ok(LEADERBOARD.to_string()));
+        assert_eq!(download(&site, &cache, 2022, 123), Ok(LEADERBOARD.to_string()));
+        assert_eq!(stand_in.requests().len(), 1);
+
+        assert!(download(&site, &cache, 2022, 456).unwrap_err().contains("didn't come back as JSON"));
+        assert!(download(&site, &cache, 2022, 789).unwrap_err().contains("there's no leaderboard 789"));
+        assert!(!cached_path(&cache, 2022, 456).exists());
+
+        fs::remove_dir_all(&cache).unwrap();
+    }
+}
@@ -0,0 +1,196 @@
+//! Answers that were accepted for an input before, so a refactor that changes
+//! one gets noticed.
+//!
+//! Inputs are told apart by a hash of their lines, so the ledger works for
+//! whatever input a day is run on without the input itself being stored.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use common::{Answer, Params, Part};
+use json::JsonValue;
+
+/// How an answer compares to what the ledger has for it.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Matches,
+    // Holds the answer from the ledger
+    Differs(String),
+    Missing,
+    // Saved to the ledger with --record, rather than checked
+    Recorded,
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct Ledger {
+    // (day, part, input hash) -> answer
+    answers: BTreeMap<(u8, usize, String), String>,
+}
+
+impl Ledger {
+    /// Reads the ledger at `path`. One that doesn't exist yet is just empty.
+    pub fn load(path: &Path) -> Result<Ledger, String> {
+        if !path.exists() {
+            return Ok(Ledger::default());
+        }
+
+        let text = fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {e}", path.display()))?;
+        Self::from_json(&text).map_err(|e| format!("bad ledger {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_json()).map_err(|e| format!("couldn't write {}: {e}", path.display()))
+    }
+
+    pub fn check(&self, day: u8, part: Part, input_hash: &str, answer: &Answer) -> Status {
+        match self.answers.get(&(day, part.number(), input_hash.to_string())) {
+            Some(known) if *known == answer.to_string() => Status::Matches,
+            Some(known) => Status::Differs(known.clone()),
+            None => Status::Missing,
+        }
+    }
+
+    pub fn record(&mut self, day: u8, part: Part, input_hash: &str, answer: &Answer) {
+        self.answers.insert((day, part.number(), input_hash.to_string()), answer.to_string());
+    }
+
+    // { "answers": [ { "day": 7, "part": 1, "input": "1c3f...", "answer": "95437" } ] }
+    fn to_json(&self) -> String {
+        let mut answers = JsonValue::new_array();
+
+        for ((day, part, input), answer) in &self.answers {
+            let mut entry = JsonValue::new_object();
+            entry["day"] = (*day).into();
+            entry["part"] = (*part).into();
+            entry["input"] = input.as_str().into();
+            entry["answer"] = answer.as_str().into();
+
+            answers.push(entry).unwrap();
+        }
+
+        let mut ledger = JsonValue::new_object();
+        ledger["answers"] = answers;
+
+        ledger.pretty(2)
+    }
+
+    fn from_json(text: &str) -> Result<Ledger, String> {
+        let ledger = json::parse(text).map_err(|e| e.to_string())?;
+        let mut answers = BTreeMap::new();
+
+        for (i, entry) in ledger["answers"].members().enumerate() {
+            let day = entry["day"].as_u8();
+            let part = entry["part"].as_usize();
+            let input = entry["input"].as_str();
+            let answer = entry["answer"].as_str();
+
+            match (day, part, input, answer) {
+                (Some(day), Some(part), Some(input), Some(answer)) => {
+                    answers.insert((day, part, input.to_string()), answer.to_string());
+                }
+
+                _ => return Err(format!("entry {} needs a day, part, input and answer", i + 1)),
+            }
+        }
+
+        Ok(Ledger { answers })
+    }
+}
+
+pub fn input_hash(input: &[String]) -> String {
+    let mut hasher = InputHasher::new();
+    input.iter().for_each(|line| hasher.add_line(line));
+    hasher.finish()
+}
+
+/// The key for an input solved with `params`: a hash of the input's hash and
+/// every param that's set.
+pub fn with_params(input_hash: String, params: &Params) -> String {
+    let mut hasher = InputHasher::new();
+    hasher.add_line(&input_hash);
+    params.iter().for_each(|(name, value)| hasher.add_line(&format!("{name}={value}")));
+    hasher.finish()
+}
+
+/// 64-bit FNV-1a of the input lines, as hex. Unlike `DefaultHasher`, this
+/// stays the same between Rust versions, so the ledger keeps working. Lines
+/// are added one by one, so a streamed input can be hashed as it goes past.
+pub struct InputHasher {
+    hash: u64,
+    first_line: bool,
+}
+
+impl Default for InputHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InputHasher {
+    pub fn new() -> Self {
+        InputHasher { hash: 0xcbf29ce484222325, first_line: true }
+    }
+
+    pub fn add_line(&mut self, line: &str) {
+        let separator: &[u8] = if self.first_line { b"" } else { b"\n" };
+
+        for byte in separator.iter().chain(line.as_bytes()) {
+            self.hash ^= *byte as u64;
+            self.hash = self.hash.wrapping_mul(0x100000001b3);
+        }
+
+        self.first_line = false;
+    }
+
+    pub fn finish(&self) -> String {
+        format!("{:016x}", self.hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ledger::*;
+
+    #[test]
+    fn test_input_hash() {
+        // Known FNV-1a values
+        assert_eq!(input_hash(&[]), "cbf29ce484222325");
+        assert_eq!(input_hash(&["a".to_string()]), "af63dc4c8601ec8c");
+
+        let one = vec!["1".to_string(), "2".to_string()];
+        let other = vec!["12".to_string()];
+        assert_ne!(input_hash(&one), input_hash(&other));
+
+        let hash = input_hash(&one);
+        assert_ne!(with_params(hash.clone(), &Params::new()), hash);
+        assert_ne!(with_params(hash.clone(), &Params::new().with("row", 10)), with_params(hash.clone(), &Params::new()));
+        assert_ne!(with_params(hash.clone(), &Params::new().with("row", 10)), with_params(hash, &Params::new().with("row", 11)));
+    }
+
+    #[test]
+    fn test_check_and_record() {
+        let mut ledger = Ledger::default();
+        let answer = Answer::Number(95437);
+
+        assert_eq!(ledger.check(7, Part::One, "abc", &answer), Status::Missing);
+
+        ledger.record(7, Part::One, "abc", &answer);
+        assert_eq!(ledger.check(7, Part::One, "abc", &answer), Status::Matches);
+        assert_eq!(ledger.check(7, Part::One, "abc", &Answer::Number(1)), Status::Differs("95437".to_string()));
+
+        // Other input, other part
+        assert_eq!(ledger.check(7, Part::One, "def", &answer), Status::Missing);
+        assert_eq!(ledger.check(7, Part::Two, "abc", &answer), Status::Missing);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut ledger = Ledger::default();
+        ledger.record(5, Part::One, "abc", &Answer::from("CMZ"));
+        ledger.record(10, Part::Two, "def", &Answer::Art("#..#\n.##.\n".to_string()));
+
+        assert_eq!(Ledger::from_json(&ledger.to_json()), Ok(ledger));
+        assert!(Ledger::from_json(r#"{ "answers": [ { "day": 1 } ] }"#).is_err());
+    }
+}
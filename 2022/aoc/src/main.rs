@@ -0,0 +1,734 @@
+mod animate;
+mod bench;
+mod cli;
+mod config;
+mod days;
+#[cfg(test)]
+mod examples;
+mod extract;
+mod fetch;
+mod leaderboard;
+mod ledger;
+mod player;
+mod pool;
+mod report;
+mod scaffold;
+mod site;
+#[cfg(test)]
+mod stand_in;
+mod submit;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::OnceLock;
+use std::path::Path;
+use std::process;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use cli::{
+    AnimateArgs, BatchArgs, BenchArgs, Command, ExtractArgs, FetchArgs, Format, GenerateArgs, InputSource, LeaderboardArgs, LeaderboardSource,
+    NewArgs, OutputFormat, ParamsArgs, PlayArgs, RunArgs, SubmitArgs, Target,
+};
+use common::{Answer, Frame, Params, Parsed, Part, Simulation, StreamError, StreamFn};
+use config::Config;
+use days::Day;
+use ledger::{Ledger, Status};
+use report::{Outcome, Solved};
+
+fn main() {
+    let (verbosity, args) = common::log::take_verbosity(env::args().skip(1).collect());
+    common::log::set_verbosity(verbosity);
+
+    let command = match cli::parse(&args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{}", cli::USAGE);
+            process::exit(2);
+        }
+    };
+
+    let result = match command {
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            Ok(())
+        }
+
+        Command::Run(run_args) => run(run_args),
+        Command::Batch(batch_args) => batch(batch_args),
+        Command::Bench(bench_args) => bench(bench_args),
+        Command::Animate(animate_args) => animate(animate_args),
+        Command::Play(play_args) => play(play_args),
+        Command::Params(params_args) => params(params_args),
+        Command::Generate(generate_args) => generate(generate_args),
+        Command::New(new_args) => new(new_args),
+        Command::Fetch(fetch_args) => fetch(fetch_args),
+        Command::Submit(submit_args) => submit(submit_args),
+        Command::Leaderboard(leaderboard_args) => leaderboard(leaderboard_args),
+        Command::Extract(extract_args) => extract(extract_args),
+    };
+
+    if let Err(e) = result {
+        eprintln!("error: {e}");
+        process::exit(1);
+    }
+}
+
+fn run(args: RunArgs) -> Result<(), String> {
+    let mut ledger = Ledger::load(&args.ledger)?;
+    let config = Config::load(args.params.config.as_deref())?;
+    let jobs = args.jobs.unwrap_or_else(pool::default_workers);
+    let table = args.format == OutputFormat::Text && matches!(args.target, Target::All(_));
+
+    let mut inputs = vec![];
+
+    for (day, source) in day_sources(args.target)? {
+        let params = config.params(day, &args.params.overrides)?;
+        inputs.push(DayInput { day, source, params, parsed: OnceLock::new() });
+    }
+
+    // Each part is a task of its own, so that both parts of a slow day can go
+    // side by side. Whichever starts first parses the input, and the other
+    // waits for it.
+    let tasks: Vec<(&DayInput, Part)> = inputs
+        .iter()
+        .flat_map(|input| selected_parts(args.part).into_iter().map(move |part| (input, part)))
+        .collect();
+
+    let mut outcomes = vec![];
+    let started = Instant::now();
+    // From when each day's first part started to when its last finished
+    let mut spans: BTreeMap<u8, (Instant, Instant)> = BTreeMap::new();
+
+    pool::run_ordered(
+        &tasks,
+        jobs,
+        |(input, part)| {
+            let start = Instant::now();
+            let parsed = input.parsed.get_or_init(|| parse_day(input.day, &input.source, &input.params));
+            (solve_part(input.day, *part, parsed), start, Instant::now())
+        },
+        |(mut outcome, start, end)| {
+            check_ledger(&mut outcome, &mut ledger, args.record);
+
+            match (args.format, outcome.to_text()) {
+                (OutputFormat::Json, _) => println!("{}", outcome.to_json()),
+                (OutputFormat::Text, Ok(text)) => println!("{text}"),
+
+                // A day that fails gets its (error) in the table and the
+                // exit status, without stopping the rest. Both parts get
+                // the same error from reading or parsing, so once will do.
+                (OutputFormat::Text, Err(e)) => {
+                    if !outcomes.iter().any(|o: &Outcome| o.day == outcome.day && o.result.as_ref().err() == Some(&e)) {
+                        eprintln!("error: {e}");
+                    }
+                }
+            }
+
+            let span = spans.entry(outcome.day).or_insert((start, end));
+            *span = (span.0.min(start), span.1.max(end));
+
+            outcomes.push(outcome);
+
+            Ok::<(), String>(())
+        },
+    )?;
+
+    if table {
+        let wall_times: Vec<(u8, Duration)> = spans.iter().map(|(day, (start, end))| (*day, *end - *start)).collect();
+        println!("\n{}", report::table(&outcomes, &wall_times));
+        println!("\nAll done in {} on {jobs} thread(s)", bench::format_duration(started.elapsed()));
+    }
+
+    if args.record {
+        ledger.save(&args.ledger)?;
+    }
+
+    let failures: BTreeSet<u8> = outcomes.iter().filter(|o| o.result.is_err()).map(|o| o.day).collect();
+
+    if !failures.is_empty() {
+        return Err(format!("{} day(s) couldn't be run", failures.len()));
+    }
+
+    let mismatches = outcomes
+        .iter()
+        .filter(|o| matches!(&o.result, Ok(Solved { ledger: Some(Status::Differs(_)), .. })))
+        .count();
+
+    if mismatches > 0 {
+        return Err(format!("{mismatches} answer(s) differ from {}", args.ledger.display()));
+    }
+
+    Ok(())
+}
+
+// A day's input for `run`, parsed by whichever of its parts gets to it first
+struct DayInput {
+    day: &'static Day,
+    source: InputSource,
+    params: Params,
+    parsed: OnceLock<ParsedDay>,
+}
+
+// The parsed input, its hash for the ledger, and how long it took to read and
+// parse, or why it couldn't be
+type ParsedDay = Result<(Box<dyn Parsed>, String, Duration), String>;
+
+// Solves each of `parts`, timing it. A day whose input can't be read or
+// parsed gets that error for each of its parts.
+fn solve_day(day: &Day, parts: &[Part], source: &InputSource, params: &Params) -> Vec<Outcome> {
+    let parsed = parse_day(day, source, params);
+    parts.iter().map(|part| solve_part(day, *part, &parsed)).collect()
+}
+
+fn parse_day(day: &Day, source: &InputSource, params: &Params) -> ParsedDay {
+    let start = Instant::now();
+
+    // None of the streaming days take any params
+    let parsed = catch_panic(|| match day.stream {
+        Some(stream) => stream_input(day, stream, source),
+
+        None => read_input(source).and_then(|input| {
+            let parsed = (day.parse)(&input, params)
+                .map_err(|e| format!("couldn't parse day {} input, {}", day.number, e.diagnostic(&input)))?;
+
+            Ok((parsed, ledger::input_hash(&input)))
+        }),
+    })
+    .unwrap_or_else(|e| Err(format!("day {} panicked reading its input: {e}", day.number)));
+
+    // Other params can give other answers for the same input
+    let (parsed, input_hash) = parsed?;
+    Ok((parsed, ledger::with_params(input_hash, params), start.elapsed()))
+}
+
+fn solve_part(day: &Day, part: Part, parsed: &ParsedDay) -> Outcome {
+    let (parsed, input_hash, parse_time) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => return Outcome { day: day.number, part, input_hash: None, parse_time: None, result: Err(e.clone()) },
+    };
+
+    let start = Instant::now();
+    let answer = catch_panic(|| parsed.solve(part));
+    let solve_time = start.elapsed();
+
+    Outcome {
+        day: day.number,
+        part,
+        input_hash: Some(input_hash.clone()),
+        parse_time: Some(*parse_time),
+        result: answer
+            .map(|answer| Solved { answer, solve_time, ledger: None })
+            .map_err(|e| format!("day {} part {} panicked: {e}", day.number, part.number())),
+    }
+}
+
+// A day that panics fails like any other, rather than taking every other
+// day's answers down with it
+fn catch_panic<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "no message".to_string())
+    })
+}
+
+// Checks a solved answer against the ledger, or records it there
+fn check_ledger(outcome: &mut Outcome, ledger: &mut Ledger, record: bool) {
+    let (Ok(solved), Some(input_hash)) = (&mut outcome.result, &outcome.input_hash) else {
+        return;
+    };
+
+    if !solved.answer.is_solved() {
+        return;
+    }
+
+    solved.ledger = Some(if record {
+        ledger.record(outcome.day, outcome.part, input_hash, &solved.answer);
+        Status::Recorded
+    } else {
+        ledger.check(outcome.day, outcome.part, input_hash, &solved.answer)
+    });
+}
+
+// Goes through the input a line at a time, hashing it for the ledger on the
+// way, so that it never has to be in memory all at once
+fn stream_input(day: &Day, stream: StreamFn, source: &InputSource) -> Result<(Box<dyn Parsed>, String), String> {
+    let reader: Box<dyn BufRead> = match source {
+        InputSource::Stdin => Box::new(io::stdin().lock()),
+        InputSource::File(path) => Box::new(BufReader::new(
+            File::open(path).map_err(|e| format!("couldn't read {}: {e}", path.display()))?,
+        )),
+    };
+
+    let mut hasher = ledger::InputHasher::new();
+
+    let mut lines = reader.lines().inspect(|line| {
+        if let Ok(line) = line {
+            hasher.add_line(line);
+        }
+    });
+
+    let parsed = stream(&mut lines).map_err(|e| match e {
+        StreamError::Io(e) => format!("couldn't read day {} input: {e}", day.number),
+        e => format!("couldn't parse day {} input, {e}", day.number),
+    })?;
+
+    Ok((parsed, hasher.finish()))
+}
+
+fn batch(args: BatchArgs) -> Result<(), String> {
+    let day = days::find(args.day).ok_or(format!("day {} has no solution", args.day))?;
+    let params = config::day_params(day, &args.params)?;
+    let parts = selected_parts(args.part);
+
+    let entries = fs::read_dir(&args.inputs).map_err(|e| format!("couldn't read {}: {e}", args.inputs.display()))?;
+    let mut files = vec![];
+
+    for entry in entries {
+        let path = entry.map_err(|e| format!("couldn't read {}: {e}", args.inputs.display()))?.path();
+
+        // Leave out dotfiles, like editor swap files and .gitignore
+        let hidden = path.file_name().and_then(|n| n.to_str()).is_none_or(|n| n.starts_with('.'));
+
+        if path.is_file() && !hidden {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+
+    if files.is_empty() {
+        return Err(format!("no input files in {}", args.inputs.display()));
+    }
+
+    let mut rows = vec![];
+    let mut failures = 0;
+    let jobs = args.jobs.unwrap_or_else(pool::default_workers);
+
+    pool::run_ordered(
+        &files,
+        jobs,
+        |path| solve_day(day, &parts, &InputSource::File(path.clone()), &params),
+        |outcomes| {
+            let path = &files[rows.len()];
+            let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+
+            // Every part has the same error, from reading or parsing
+            if let Some(Err(e)) = outcomes.first().map(|o| &o.result) {
+                eprintln!("error: {}: {e}", path.display());
+                failures += 1;
+            }
+
+            rows.push((name, outcomes));
+            Ok::<(), String>(())
+        },
+    )?;
+
+    println!("{}", report::batch_table(&rows));
+
+    if failures > 0 {
+        return Err(format!("{failures} of {} input(s) couldn't be run", files.len()));
+    }
+
+    Ok(())
+}
+
+fn bench(args: BenchArgs) -> Result<(), String> {
+    let baseline = if args.baseline.exists() {
+        let text = fs::read_to_string(&args.baseline)
+            .map_err(|e| format!("couldn't read {}: {e}", args.baseline.display()))?;
+
+        Some(bench::from_json(&text).map_err(|e| format!("bad baseline {}: {e}", args.baseline.display()))?)
+    } else {
+        None
+    };
+
+    let config = Config::load(args.params.config.as_deref())?;
+    let mut measurements = vec![];
+
+    for (day, source) in day_sources(args.target)? {
+        let params = config.params(day, &args.params.overrides)?;
+        let input = read_input(&source)?;
+
+        for m in bench::bench_day(day, &input, &params, &selected_parts(args.part), args.runs)? {
+            let mut line = format!(
+                "Day {:02} {:<7} min {:>9}  median {:>9}  max {:>9}",
+                m.day,
+                m.stage.to_string(),
+                bench::format_duration(m.timings.min),
+                bench::format_duration(m.timings.median),
+                bench::format_duration(m.timings.max),
+            );
+
+            if let Some(change) = baseline.as_ref().and_then(|b| bench::change(b, &m)) {
+                line += &format!("  {change:+7.1}%");
+            }
+
+            println!("{line}");
+
+            measurements.push(m);
+        }
+    }
+
+    let regressions = match &baseline {
+        Some(baseline) => bench::regressions(baseline, &measurements, args.threshold),
+        None => vec![],
+    };
+
+    for r in &regressions {
+        eprintln!(
+            "Regression: day {:02} {} went from {} to {}",
+            r.day,
+            r.stage,
+            bench::format_duration(r.before),
+            bench::format_duration(r.after),
+        );
+    }
+
+    if baseline.is_none() || args.save {
+        fs::write(&args.baseline, bench::to_json(&measurements, args.runs))
+            .map_err(|e| format!("couldn't write {}: {e}", args.baseline.display()))?;
+
+        println!("Saved baseline to {}", args.baseline.display());
+    } else if !regressions.is_empty() {
+        return Err(format!(
+            "{} regression(s) over {}% slower than {}",
+            regressions.len(),
+            args.threshold,
+            args.baseline.display(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn animate(args: AnimateArgs) -> Result<(), String> {
+    let day = days::find(args.day).ok_or(format!("day {} has no solution", args.day))?;
+    let simulate = day.simulate.ok_or(format!("day {} isn't a simulation", args.day))?;
+    let params = config::day_params(day, &args.params)?;
+    let input = read_input(&args.source)?;
+
+    // Images need to know how big to be before the first frame goes out, so
+    // they play the simulation through twice rather than keep every frame
+    let start = || -> Result<Box<dyn Simulation>, String> {
+        simulate(&input, &params, args.part)
+            .map_err(|e| format!("couldn't parse day {} input, {}", day.number, e.diagnostic(&input)))
+    };
+
+    let count = match (args.format, &args.out) {
+        (Format::Text, None) => {
+            animate::write_text(sampled(start()?.as_mut(), args.every), &mut io::stdout().lock())
+                .map_err(|e| format!("couldn't write frames: {e}"))?;
+
+            return Ok(());
+        }
+
+        (Format::Text, Some(path)) => {
+            let mut file = File::create(path).map_err(|e| format!("couldn't write {}: {e}", path.display()))?;
+            animate::write_text(sampled(start()?.as_mut(), args.every), &mut file)
+                .map_err(|e| format!("couldn't write {}: {e}", path.display()))?
+        }
+
+        (format, Some(path)) => {
+            let mut simulation = start()?;
+            let mut frames = sampled(simulation.as_mut(), args.every).map(|(_, frame)| frame);
+
+            // There's always at least the first frame
+            let mut canvas = animate::Canvas::around(&frames.next().unwrap());
+            frames.for_each(|frame| canvas.include(&frame));
+
+            let mut simulation = start()?;
+            let frames = sampled(simulation.as_mut(), args.every).map(|(_, frame)| frame);
+
+            if format == Format::Ppm {
+                animate::write_ppms(frames, &canvas, args.scale, path)?
+            } else {
+                animate::write_gif(frames, &canvas, args.scale, path)?
+            }
+        }
+
+        (_, None) => unreachable!("images always have --out"),
+    };
+
+    println!("Wrote {count} frame(s) of day {:02} to {}", day.number, args.out.unwrap().display());
+    Ok(())
+}
+
+fn play(args: PlayArgs) -> Result<(), String> {
+    let day = days::find(args.day).ok_or(format!("day {} has no solution", args.day))?;
+    let simulate = day.simulate.ok_or(format!("day {} isn't a simulation", args.day))?;
+    let params = config::day_params(day, &args.params)?;
+    let input = read_input(&args.source)?;
+
+    // Check the input's fine up front, rather than after the terminal's been taken over
+    simulate(&input, &params, args.part)
+        .map_err(|e| format!("couldn't parse day {} input, {}", day.number, e.diagnostic(&input)))?;
+
+    let mut player = player::Player::new(|| {
+        simulate(&input, &params, args.part).expect("input parsed fine the first time")
+    });
+
+    let title = format!("Day {:02} part {}", day.number, args.part.number());
+    player::play(&mut player, &title, args.speed).map_err(|e| format!("terminal trouble: {e}"))
+}
+
+// Lists the params a day takes, with what they're set to now if that's not
+// the default
+fn params(args: ParamsArgs) -> Result<(), String> {
+    let day = days::find(args.day).ok_or(format!("day {} has no solution", args.day))?;
+    let params = config::day_params(day, &args.params)?;
+
+    if day.params.is_empty() {
+        println!("Day {:02} doesn't take any params", day.number);
+        return Ok(());
+    }
+
+    let set: BTreeMap<&str, String> = params.iter().map(|(name, value)| (name, value.to_string())).collect();
+
+    let rows: Vec<(String, String)> = day
+        .params
+        .iter()
+        .map(|param| {
+            let value = match set.get(param.name()) {
+                Some(value) => format!("{}={value} (default {})", param.name(), param.default()),
+                None => format!("{}={}", param.name(), param.default()),
+            };
+
+            match param.min() {
+                Some(min) => (value, format!("{} (at least {min})", param.about())),
+                None => (value, param.about().to_string()),
+            }
+        })
+        .collect();
+
+    let width = rows.iter().map(|(value, _)| value.chars().count()).max().unwrap_or(0);
+
+    for (value, about) in rows {
+        println!("{value:<width$}  {about}");
+    }
+
+    Ok(())
+}
+
+fn generate(args: GenerateArgs) -> Result<(), String> {
+    let day = days::find(args.day).ok_or(format!("day {} has no solution", args.day))?;
+    let mut text = (day.generate)(args.seed, args.size).join("\n");
+    text.push('\n');
+
+    match &args.out {
+        None => print!("{text}"),
+        Some(path) => fs::write(path, text).map_err(|e| format!("couldn't write {}: {e}", path.display()))?,
+    }
+
+    Ok(())
+}
+
+fn new(args: NewArgs) -> Result<(), String> {
+    let here = env::current_dir().map_err(|e| format!("couldn't tell the current directory: {e}"))?;
+    let workspace = scaffold::year_workspace(&here, args.year)?;
+
+    for path in scaffold::create(&workspace, args.day)? {
+        println!("wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+fn fetch(args: FetchArgs) -> Result<(), String> {
+    let site = site::Site::new(&args.server, site::session(&args.session_file));
+    let (text, downloaded) = fetch::input(&site, &args.cache, args.year, args.day)?;
+
+    let cached = fetch::cached_path(&args.cache, args.year, args.day);
+
+    if downloaded {
+        common::info!("fetched day {} of {} into {}", args.day, args.year, cached.display());
+    } else {
+        common::info!("day {} of {} was already in {}", args.day, args.year, cached.display());
+    }
+
+    match &args.out {
+        None => print!("{text}"),
+        Some(path) => fs::write(path, text).map_err(|e| format!("couldn't write {}: {e}", path.display()))?,
+    }
+
+    Ok(())
+}
+
+fn submit(args: SubmitArgs) -> Result<(), String> {
+    let answer = match args.answer {
+        Some(answer) => answer,
+        None => solved_answer(&args)?,
+    };
+
+    let mut attempts = submit::Attempts::load(&args.attempts)?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|t| t.as_secs()).unwrap_or(0);
+    let title = format!("Day {:02} part {}", args.day, args.part.number());
+
+    if let Some(reason) = attempts.refusal(args.year, args.day, args.part, &answer, now) {
+        return Err(format!("not sending {answer} for day {} part {}: {reason}", args.day, args.part.number()));
+    }
+
+    let site = site::Site::new(&args.server, site::session(&args.session_file));
+    common::info!("sending {answer} for day {} part {} of {}", args.day, args.part.number(), args.year);
+
+    let submit::Reply { verdict, wait } = submit::send(&site, args.year, args.day, args.part, &answer)?;
+
+    attempts.record(submit::Attempt {
+        year: args.year,
+        day: args.day,
+        part: args.part.number(),
+        answer: answer.clone(),
+        verdict: verdict.clone(),
+        at: now,
+        wait,
+    });
+    attempts.save(&args.attempts)?;
+
+    let waiting = if wait > 0 { format!(", and the site wants {wait}s before the next answer") } else { String::new() };
+
+    match verdict {
+        submit::Verdict::Correct => {
+            println!("{title}: {answer} is the right answer");
+            Ok(())
+        }
+
+        submit::Verdict::TooHigh => Err(format!("{title}: {answer} isn't the right answer, it's too high{waiting}")),
+        submit::Verdict::TooLow => Err(format!("{title}: {answer} isn't the right answer, it's too low{waiting}")),
+        submit::Verdict::Wrong => Err(format!("{title}: {answer} isn't the right answer{waiting}")),
+        submit::Verdict::Wait(secs) => Err(format!("{title}: too soon after the last answer, wait another {secs}s")),
+        submit::Verdict::WrongLevel => Err(format!(
+            "{title}: the site isn't taking answers for this part, it's either solved already or not unlocked yet (part one first)"
+        )),
+    }
+}
+
+fn leaderboard(args: LeaderboardArgs) -> Result<(), String> {
+    let text = match &args.source {
+        LeaderboardSource::File(path) => {
+            fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {e}", path.display()))?
+        }
+
+        LeaderboardSource::Site { id, year, cache, server, session_file } => {
+            let site = site::Site::new(server, site::session(session_file));
+            leaderboard::download(&site, cache, *year, *id)?
+        }
+    };
+
+    let leaderboard = leaderboard::Leaderboard::from_json(&text).map_err(|e| format!("bad leaderboard JSON: {e}"))?;
+    print!("{}", leaderboard.report(args.format));
+
+    Ok(())
+}
+
+fn extract(args: ExtractArgs) -> Result<(), String> {
+    let html = fs::read_to_string(&args.page).map_err(|e| format!("couldn't read {}: {e}", args.page.display()))?;
+    let page = extract::Page::parse(&html);
+
+    // Easily done, with a browser's downloads all in one place
+    if let Some(day) = page.day().filter(|day| *day != args.day) {
+        return Err(format!("{} is the page for day {day}, not day {}", args.page.display(), args.day));
+    }
+
+    if args.list {
+        print!("{}", page.listing());
+        return Ok(());
+    }
+
+    if let Some(fixture) = &args.fixture {
+        if fixture.exists() && !args.force {
+            return Err(format!("{} already exists, --force replaces it", fixture.display()));
+        }
+
+        let block = page.block(args.block)?;
+        fs::write(fixture, &block.text).map_err(|e| format!("couldn't write {}: {e}", fixture.display()))?;
+        println!("wrote {}", fixture.display());
+
+        return Ok(());
+    }
+
+    let here = env::current_dir().map_err(|e| format!("couldn't tell the current directory: {e}"))?;
+    let manifest = scaffold::find_workspace(&here)?.join("examples").join(format!("day{:02}.toml", args.day));
+    let (text, left_out) = page.manifest(args.block, &args.answer_blocks)?;
+
+    for note in left_out {
+        common::warn!("{note}");
+    }
+
+    extract::write_manifest(&manifest, &text, args.force)?;
+    println!("wrote {}", manifest.display());
+
+    Ok(())
+}
+
+// The answer to submit when there wasn't an --answer, solved from the input
+fn solved_answer(args: &SubmitArgs) -> Result<String, String> {
+    let day = days::find(args.day).ok_or(format!("day {} has no solution", args.day))?;
+    let params = config::day_params(day, &args.params)?;
+
+    let outcome = solve_day(day, &[args.part], &args.source, &params).remove(0);
+
+    match outcome.result?.answer {
+        Answer::Unsolved => Err(format!("day {} part {} isn't solved, give the --answer", day.number, args.part.number())),
+        Answer::Art(art) => Err(format!("read the letters off this and give them as the --answer:\n{}", art.trim_end())),
+        answer => Ok(answer.to_string()),
+    }
+}
+
+// Every `every`th frame of `simulation`, numbered by the step it's from
+fn sampled(simulation: &mut dyn Simulation, every: usize) -> impl Iterator<Item = (usize, Frame)> + '_ {
+    common::frames(simulation).enumerate().step_by(every)
+}
+
+// Each day `target` covers, and where its input comes from. Days without an
+// input file are skipped when going through all of them.
+fn day_sources(target: Target) -> Result<Vec<(&'static Day, InputSource)>, String> {
+    match target {
+        Target::Day(number, source) => {
+            let day = days::find(number).ok_or(format!("day {number} has no solution"))?;
+            Ok(vec![(day, source)])
+        }
+
+        Target::All(input_dir) => {
+            let mut sources = vec![];
+
+            for day in days::DAYS {
+                let path = input_path(&input_dir, day.number);
+
+                if !path.exists() {
+                    eprintln!("Skipping day {}: no input at {}", day.number, path.display());
+                    continue;
+                }
+
+                sources.push((day, InputSource::File(path)));
+            }
+
+            Ok(sources)
+        }
+    }
+}
+
+// Reads the whole input in, for days that need all of it at once
+fn read_input(source: &InputSource) -> Result<Vec<String>, String> {
+    match source {
+        InputSource::Stdin => common::read_stdin().map_err(|e| format!("couldn't read stdin: {e}")),
+        InputSource::File(path) => common::read_file(path)
+            .map_err(|e| format!("couldn't read {}: {e}", path.display())),
+    }
+}
+
+fn selected_parts(part: Option<Part>) -> Vec<Part> {
+    match part {
+        Some(part) => vec![part],
+        None => vec![Part::One, Part::Two],
+    }
+}
+
+// Each day keeps its puzzle input in its own folder as `input.txt`
+fn input_path(input_dir: &Path, day: u8) -> std::path::PathBuf {
+    input_dir.join(format!("aoc{day:02}")).join("input.txt")
+}
@@ -0,0 +1,338 @@
+//! Watching a simulation play out in the terminal, redrawing each frame in
+//! place, with keys to pause it, step through it either way, change its speed
+//! and jump to a given step.
+
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use common::{Frame, Simulation};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, terminal, ExecutableCommand};
+
+use crate::animate;
+
+// Frames kept around for stepping back through. Going back further than this
+// plays the simulation through again from the start.
+const HISTORY: usize = 1000;
+
+// Steps per second to pick from
+const SPEEDS: &[usize] = &[1, 2, 5, 10, 20, 50, 100, 200, 500, 1000];
+
+// Redrawing any more often than this is lost on the terminal, so faster
+// speeds take more than one step per redraw
+const MIN_TICK: Duration = Duration::from_millis(20);
+
+const KEYS: &str = "space: play/pause  ←/→: step  +/-: speed  g: go to step  0: restart  q: quit";
+
+/// A simulation that can be moved through in either direction.
+pub struct Player<'a> {
+    start: Box<dyn Fn() -> Box<dyn Simulation> + 'a>,
+    simulation: Box<dyn Simulation>,
+    // Step the simulation itself has got to
+    latest: usize,
+    // Frames for the last few steps, ending with `latest`
+    recent: VecDeque<Frame>,
+    history: usize,
+    // Step being shown, which is `latest` unless it's been stepped back from
+    shown: usize,
+    // Last step there is, once the simulation's got there
+    last: Option<usize>,
+}
+
+impl<'a> Player<'a> {
+    /// `start` sets up the simulation from scratch, which is needed again
+    /// whenever going back further than the frames kept.
+    pub fn new(start: impl Fn() -> Box<dyn Simulation> + 'a) -> Self {
+        let simulation = start();
+        let recent = VecDeque::from([simulation.render_frame()]);
+
+        Player {
+            start: Box::new(start),
+            simulation,
+            latest: 0,
+            recent,
+            history: HISTORY,
+            shown: 0,
+            last: None,
+        }
+    }
+
+    pub fn shown(&self) -> usize {
+        self.shown
+    }
+
+    pub fn last(&self) -> Option<usize> {
+        self.last
+    }
+
+    pub fn frame(&self) -> &Frame {
+        &self.recent[self.recent.len() - 1 - (self.latest - self.shown)]
+    }
+
+    /// Moves on a step. Returns false if there are no more.
+    pub fn forward(&mut self) -> bool {
+        if self.shown < self.latest {
+            self.shown += 1;
+            return true;
+        }
+
+        if !self.advance() {
+            return false;
+        }
+
+        self.shown = self.latest;
+        true
+    }
+
+    /// Goes back a step. Returns false if already at the start.
+    pub fn back(&mut self) -> bool {
+        if self.shown == 0 {
+            return false;
+        }
+
+        self.seek(self.shown - 1);
+        true
+    }
+
+    /// Jumps to `step`, or the last one if there aren't that many.
+    pub fn seek(&mut self, step: usize) {
+        let earliest = self.latest + 1 - self.recent.len();
+
+        if step < earliest {
+            self.restart();
+        }
+
+        while self.latest < step && self.advance() {}
+
+        self.shown = std::cmp::min(step, self.latest);
+    }
+
+    // Runs the simulation itself a step further
+    fn advance(&mut self) -> bool {
+        if self.last == Some(self.latest) || !self.simulation.step() {
+            self.last = Some(self.latest);
+            return false;
+        }
+
+        self.latest += 1;
+        self.recent.push_back(self.simulation.render_frame());
+
+        if self.recent.len() > self.history {
+            self.recent.pop_front();
+        }
+
+        true
+    }
+
+    fn restart(&mut self) {
+        self.simulation = (self.start)();
+        self.recent = VecDeque::from([self.simulation.render_frame()]);
+        self.latest = 0;
+        self.shown = 0;
+    }
+}
+
+/// Plays `player` in the terminal until told to quit, starting at `speed`
+/// steps per second.
+pub fn play(player: &mut Player, title: &str, speed: usize) -> io::Result<()> {
+    let mut stdout = io::stdout();
+
+    terminal::enable_raw_mode()?;
+    stdout.execute(terminal::EnterAlternateScreen)?.execute(cursor::Hide)?;
+
+    let result = run(player, title, speed, &mut stdout);
+
+    // Put the terminal back as it was, whatever happened
+    stdout.execute(cursor::Show)?.execute(terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+
+    result
+}
+
+fn run(player: &mut Player, title: &str, speed: usize, out: &mut impl Write) -> io::Result<()> {
+    // Closest speed there is to the one asked for
+    let mut speed = SPEEDS.iter().position(|s| *s >= speed).unwrap_or(SPEEDS.len() - 1);
+    let mut playing = true;
+    // Step number being typed in, after pressing g
+    let mut going_to: Option<String> = None;
+
+    loop {
+        let status = match &going_to {
+            Some(typed) => format!("Go to step: {typed}_  (enter: go, esc: cancel)"),
+            None => {
+                let state = if playing { "playing" } else { "paused" };
+                let end = match player.last() {
+                    Some(last) => format!(" of {last}"),
+                    None => String::new(),
+                };
+
+                format!("{title}  step {}{end}  {state} at {}/s", player.shown(), SPEEDS[speed])
+            }
+        };
+
+        draw(out, player.frame(), &status)?;
+
+        let tick = std::cmp::max(Duration::from_secs(1) / SPEEDS[speed] as u32, MIN_TICK);
+        let steps_per_tick = std::cmp::max(1, SPEEDS[speed] * MIN_TICK.as_millis() as usize / 1000);
+
+        if !event::poll(if playing { tick } else { Duration::from_secs(60) })? {
+            if playing {
+                for _ in 0..steps_per_tick {
+                    if !player.forward() {
+                        playing = false;
+                        break;
+                    }
+                }
+            }
+
+            continue;
+        }
+
+        let key = match event::read()? {
+            Event::Key(key) if key.kind != KeyEventKind::Release => key,
+            // Resizes and the like just get a redraw
+            _ => continue,
+        };
+
+        if let Some(typed) = going_to.as_mut() {
+            match key.code {
+                KeyCode::Char(c) if c.is_ascii_digit() => typed.push(c),
+                KeyCode::Backspace => { typed.pop(); }
+
+                KeyCode::Enter => {
+                    if let Ok(step) = typed.parse() {
+                        player.seek(step);
+                    }
+
+                    going_to = None;
+                }
+
+                KeyCode::Esc => { going_to = None; }
+                _ => {}
+            }
+
+            continue;
+        }
+
+        match key {
+            KeyEvent { code: KeyCode::Char('c'), modifiers: KeyModifiers::CONTROL, .. } => return Ok(()),
+
+            KeyEvent { code, .. } => match code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char(' ') => { playing = !playing; }
+
+                KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('.') => {
+                    playing = false;
+                    player.forward();
+                }
+
+                KeyCode::Left | KeyCode::Char('h') | KeyCode::Char(',') => {
+                    playing = false;
+                    player.back();
+                }
+
+                KeyCode::Char('+') | KeyCode::Char('=') => { speed = std::cmp::min(speed + 1, SPEEDS.len() - 1); }
+                KeyCode::Char('-') => { speed = speed.saturating_sub(1); }
+                KeyCode::Char('0') | KeyCode::Home => { player.seek(0); }
+
+                KeyCode::Char('g') => {
+                    playing = false;
+                    going_to = Some(String::new());
+                }
+
+                _ => {}
+            },
+        }
+    }
+}
+
+// Redraws the whole screen from the top left. Whatever doesn't fit in the
+// terminal gets cut off.
+fn draw(out: &mut impl Write, frame: &Frame, status: &str) -> io::Result<()> {
+    let (columns, rows) = terminal::size()?;
+    let (columns, rows) = (columns as usize, rows as usize);
+
+    let mut screen = String::from("\x1b[H");
+
+    for row in frame.cells.rows().take(rows.saturating_sub(2)) {
+        let mut colour = None;
+
+        for cell in row.iter().take(columns) {
+            let rgb = animate::colour(*cell);
+
+            if colour != Some(rgb) {
+                screen += &format!("\x1b[38;2;{};{};{}m", rgb[0], rgb[1], rgb[2]);
+                colour = Some(rgb);
+            }
+
+            screen.push(*cell);
+        }
+
+        // Raw mode needs the \r as well
+        screen += "\x1b[0m\x1b[K\r\n";
+    }
+
+    for line in [status, KEYS] {
+        screen += &format!("\x1b[J\r\n{}", line.chars().take(columns).collect::<String>());
+    }
+
+    write!(out, "{screen}\x1b[J")?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{Params, Part};
+
+    use crate::days;
+    use crate::player::*;
+
+    fn sand(history: usize) -> Player<'static> {
+        let input = vec![
+            "498,4 -> 498,6 -> 496,6".to_string(),
+            "503,4 -> 502,4 -> 502,9 -> 494,9".to_string(),
+        ];
+
+        let simulate = days::find(14).unwrap().simulate.unwrap();
+        let mut player = Player::new(move || simulate(&input, &Params::new(), Part::One).unwrap());
+        player.history = history;
+        player
+    }
+
+    #[test]
+    fn test_forward_and_back() {
+        let mut player = sand(3);
+        let first = player.frame().clone();
+
+        assert!(!player.back());
+        assert!(player.forward());
+        assert!(player.forward());
+        let second = player.frame().clone();
+
+        // From the frames kept...
+        assert!(player.back());
+        assert!(player.forward());
+        assert_eq!(player.frame(), &second);
+
+        // ...and further back than that
+        (0..5).for_each(|_| { player.forward(); });
+        player.seek(0);
+        assert_eq!((player.shown(), player.frame()), (0, &first));
+
+        player.seek(2);
+        assert_eq!(player.frame(), &second);
+    }
+
+    #[test]
+    fn test_seek_past_the_end() {
+        let mut player = sand(HISTORY);
+
+        player.seek(1000);
+        assert_eq!((player.shown(), player.last()), (24, Some(24)));
+        assert!(!player.forward());
+
+        assert!(player.back());
+        assert_eq!(player.shown(), 23);
+    }
+}
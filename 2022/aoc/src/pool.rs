@@ -0,0 +1,133 @@
+//! Just enough of a thread pool to solve days side by side, while still
+//! reporting on them in order.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Runs `work` on each of `tasks` over `workers` threads, and hands the
+/// results to `done` in the order of the tasks, each one as soon as it and
+/// all the ones before it have finished. If `done` returns an error, no more
+/// tasks get started and that error is returned.
+pub fn run_ordered<T, R, E>(
+    tasks: &[T],
+    workers: usize,
+    work: impl Fn(&T) -> R + Sync,
+    mut done: impl FnMut(R) -> Result<(), E>,
+) -> Result<(), E>
+where
+    T: Sync,
+    R: Send,
+{
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, tasks.len().max(1)) {
+            let sender = sender.clone();
+            let (next, stop, work) = (&next, &stop, &work);
+
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+
+                if i >= tasks.len() || stop.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                // Nobody's listening any more if `done` failed
+                if sender.send((i, work(&tasks[i]))).is_err() {
+                    break;
+                }
+            });
+        }
+
+        // Only the workers' senders keep the channel open
+        drop(sender);
+
+        let mut waiting = BTreeMap::new();
+        let mut due = 0;
+
+        for (i, result) in receiver {
+            waiting.insert(i, result);
+
+            while let Some(result) = waiting.remove(&due) {
+                due += 1;
+
+                if let Err(e) = done(result) {
+                    stop.store(true, Ordering::SeqCst);
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(())
+    })
+}
+
+/// How many workers to use when not told otherwise: one per core.
+pub fn default_workers() -> usize {
+    thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::pool::*;
+
+    #[test]
+    fn test_run_ordered() {
+        // The early tasks take longest, so finish last
+        let tasks: Vec<u64> = (0..8).collect();
+        let work = |t: &u64| {
+            thread::sleep(Duration::from_millis(40 - t * 5));
+            t * 10
+        };
+
+        for workers in [1, 3, 8, 20] {
+            let mut results = vec![];
+            let outcome: Result<(), ()> = run_ordered(&tasks, workers, work, |r| {
+                results.push(r);
+                Ok(())
+            });
+
+            assert_eq!(outcome, Ok(()));
+            assert_eq!(results, vec![0, 10, 20, 30, 40, 50, 60, 70]);
+        }
+    }
+
+    #[test]
+    fn test_run_ordered_stops() {
+        let tasks: Vec<u64> = (0..100).collect();
+        let started = AtomicUsize::new(0);
+        let mut results = vec![];
+
+        let outcome = run_ordered(
+            &tasks,
+            2,
+            |t| {
+                started.fetch_add(1, Ordering::SeqCst);
+                thread::sleep(Duration::from_millis(1));
+                *t
+            },
+            |r| {
+                results.push(r);
+                if r == 3 { Err(format!("failed at {r}")) } else { Ok(()) }
+            },
+        );
+
+        assert_eq!(outcome, Err("failed at 3".to_string()));
+        assert_eq!(results, vec![0, 1, 2, 3]);
+        assert!(started.load(Ordering::SeqCst) < 100);
+    }
+
+    #[test]
+    fn test_run_ordered_nothing() {
+        let tasks: Vec<u64> = vec![];
+        let outcome: Result<(), ()> = run_ordered(&tasks, 4, |t| *t, |_| panic!("nothing to do"));
+
+        assert_eq!(outcome, Ok(()));
+    }
+}
@@ -0,0 +1,291 @@
+//! What `aoc run` made of each part of each day, written out either for
+//! reading or, with `--format json`, for scripts: one JSON object per line.
+
+use std::time::Duration;
+
+use common::{Answer, Part};
+use json::JsonValue;
+
+use crate::bench::format_duration;
+use crate::ledger::Status;
+
+#[derive(Debug, PartialEq)]
+pub struct Outcome {
+    pub day: u8,
+    pub part: Part,
+    // None if the input couldn't be read
+    pub input_hash: Option<String>,
+    // Includes reading the input, for the days that stream it
+    pub parse_time: Option<Duration>,
+    pub result: Result<Solved, String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Solved {
+    pub answer: Answer,
+    pub solve_time: Duration,
+    // None for answers that aren't solved, so can't be in the ledger
+    pub ledger: Option<Status>,
+}
+
+impl Outcome {
+    /// The answer with how it compares to the ledger. Multi-line answers
+    /// (looking at you, day 10) start on their own line, with the note
+    /// staying next to the label.
+    pub fn to_text(&self) -> Result<String, String> {
+        let solved = self.result.as_ref().map_err(|e| e.clone())?;
+
+        let label = format!("Day {:02} part {}:", self.day, self.part.number());
+        let answer = solved.answer.to_string();
+
+        let note = match &solved.ledger {
+            None => String::new(),
+            Some(Status::Recorded) => "(recorded)".to_string(),
+            Some(Status::Matches) => "(matches ledger)".to_string(),
+            Some(Status::Missing) => "(not in ledger)".to_string(),
+            Some(Status::Differs(known)) if known.contains('\n') => {
+                format!("(DIFFERS from ledger, which has:\n{known}\n)")
+            }
+            Some(Status::Differs(known)) => format!("(DIFFERS from ledger, which has {known})"),
+        };
+
+        Ok(if answer.contains('\n') {
+            if note.is_empty() {
+                format!("{label}\n{answer}")
+            } else {
+                format!("{label} {note}\n{answer}")
+            }
+        } else if note.is_empty() {
+            format!("{label} {answer}")
+        } else {
+            format!("{label} {answer} {note}")
+        })
+    }
+
+    // { "day": 7, "part": 1, "answer": 95437, "ledger": "matches", "parse_ms": 0.41,
+    //   "solve_ms": 0.02, "input": "1c3f...", "error": null }
+    pub fn to_json(&self) -> String {
+        let mut object = JsonValue::new_object();
+        object["day"] = self.day.into();
+        object["part"] = self.part.number().into();
+        object["answer"] = JsonValue::Null;
+        object["ledger"] = JsonValue::Null;
+        object["parse_ms"] = self.parse_time.map(milliseconds).into();
+        object["solve_ms"] = JsonValue::Null;
+        object["input"] = self.input_hash.clone().into();
+        object["error"] = JsonValue::Null;
+
+        match &self.result {
+            Ok(solved) => {
+                object["answer"] = match &solved.answer {
+                    // Any answer a day can give fits one or the other
+                    Answer::Number(n) => match (i64::try_from(*n), u64::try_from(*n)) {
+                        (Ok(n), _) => n.into(),
+                        (_, Ok(n)) => n.into(),
+                        _ => n.to_string().into(),
+                    },
+                    Answer::Text(s) | Answer::Art(s) => s.trim_end().into(),
+                    Answer::Unsolved => JsonValue::Null,
+                };
+
+                object["ledger"] = match &solved.ledger {
+                    None => JsonValue::Null,
+                    Some(Status::Recorded) => "recorded".into(),
+                    Some(Status::Matches) => "matches".into(),
+                    Some(Status::Missing) => "missing".into(),
+                    Some(Status::Differs(_)) => "differs".into(),
+                };
+
+                if let Some(Status::Differs(known)) = &solved.ledger {
+                    object["expected"] = known.as_str().into();
+                }
+
+                object["solve_ms"] = milliseconds(solved.solve_time).into();
+            }
+
+            Err(e) => {
+                object["error"] = e.as_str().into();
+            }
+        }
+
+        object.dump()
+    }
+}
+
+/// What `run --all` finishes with: a line for each day with its answers, and
+/// how long it took from starting on it to having all its parts, wall-clock.
+pub fn table(outcomes: &[Outcome], wall_times: &[(u8, Duration)]) -> String {
+    let mut rows = vec![vec!["Day".to_string(), "Part 1".to_string(), "Part 2".to_string(), "Time".to_string()]];
+
+    for (day, time) in wall_times {
+        let day_outcomes: Vec<&Outcome> = outcomes.iter().filter(|o| o.day == *day).collect();
+        rows.push(vec![format!("{day:02}"), cell(&day_outcomes, Part::One), cell(&day_outcomes, Part::Two), format_duration(*time)]);
+    }
+
+    columns(&rows, &[false, false, false, true])
+}
+
+/// What `batch` prints: a line for each input file with its answers, how long
+/// parsing took and how long each part took to solve.
+pub fn batch_table(files: &[(String, Vec<Outcome>)]) -> String {
+    let mut rows = vec![["File", "Part 1", "Part 2", "Parse", "Solve 1", "Solve 2"].map(String::from).to_vec()];
+
+    for (file, outcomes) in files {
+        let outcomes: Vec<&Outcome> = outcomes.iter().collect();
+        let parse_time = outcomes.iter().find_map(|o| o.parse_time);
+
+        let solve_time = |part: Part| {
+            outcomes
+                .iter()
+                .find(|o| o.part == part)
+                .and_then(|o| o.result.as_ref().ok())
+                .map(|solved| format_duration(solved.solve_time))
+                .unwrap_or_default()
+        };
+
+        rows.push(vec![
+            file.clone(),
+            cell(&outcomes, Part::One),
+            cell(&outcomes, Part::Two),
+            parse_time.map(format_duration).unwrap_or_default(),
+            solve_time(Part::One),
+            solve_time(Part::Two),
+        ]);
+    }
+
+    columns(&rows, &[false, false, false, true, true, true])
+}
+
+// An answer short enough for a table, or blank for a part that wasn't run
+fn cell(outcomes: &[&Outcome], part: Part) -> String {
+    match outcomes.iter().find(|o| o.part == part).map(|o| &o.result) {
+        None => String::new(),
+        Some(Err(_)) => "(error)".to_string(),
+        Some(Ok(solved)) if matches!(solved.answer, Answer::Art(_)) => "(drawing)".to_string(),
+        Some(Ok(solved)) => solved.answer.to_string(),
+    }
+}
+
+/// Lines the rows up in columns, two spaces apart.
+pub fn columns(rows: &[Vec<String>], right_aligned: &[bool]) -> String {
+    let widths: Vec<usize> = (0..right_aligned.len())
+        .map(|column| rows.iter().map(|row| row[column].chars().count()).max().unwrap_or(0))
+        .collect();
+
+    rows.iter()
+        .map(|row| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .zip(right_aligned)
+                .map(|((cell, width), right)| if *right { format!("{cell:>width$}") } else { format!("{cell:<width$}") })
+                .collect();
+
+            cells.join("  ").trim_end().to_string()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn milliseconds(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::*;
+
+    fn outcome(answer: Answer, ledger: Option<Status>) -> Outcome {
+        Outcome {
+            day: 7,
+            part: Part::One,
+            input_hash: Some("abc".to_string()),
+            parse_time: Some(Duration::from_micros(1500)),
+            result: Ok(Solved { answer, solve_time: Duration::from_millis(2), ledger }),
+        }
+    }
+
+    #[test]
+    fn test_to_text() {
+        assert_eq!(outcome(Answer::Number(95437), None).to_text(), Ok("Day 07 part 1: 95437".to_string()));
+
+        assert_eq!(
+            outcome(Answer::Number(95437), Some(Status::Differs("1".to_string()))).to_text(),
+            Ok("Day 07 part 1: 95437 (DIFFERS from ledger, which has 1)".to_string()),
+        );
+
+        assert_eq!(
+            outcome(Answer::Art("#.\n.#\n".to_string()), Some(Status::Matches)).to_text(),
+            Ok("Day 07 part 1: (matches ledger)\n#.\n.#".to_string()),
+        );
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json = json::parse(&outcome(Answer::Number(95437), Some(Status::Missing)).to_json()).unwrap();
+
+        assert_eq!(json["day"], 7);
+        assert_eq!(json["part"], 1);
+        assert_eq!(json["answer"], 95437);
+        assert_eq!(json["ledger"], "missing");
+        assert_eq!(json["parse_ms"], 1.5);
+        assert_eq!(json["solve_ms"], 2.0);
+        assert_eq!(json["input"], "abc");
+        assert!(json["error"].is_null());
+
+        let json = json::parse(&outcome(Answer::from(u64::MAX), None).to_json()).unwrap();
+        assert_eq!(json["answer"], u64::MAX);
+
+        let json = json::parse(&outcome(Answer::Unsolved, None).to_json()).unwrap();
+        assert!(json["answer"].is_null());
+        assert!(json["ledger"].is_null());
+
+        let failed = Outcome {
+            day: 3,
+            part: Part::Two,
+            input_hash: None,
+            parse_time: None,
+            result: Err("couldn't read aoc03/input.txt".to_string()),
+        };
+
+        let json = json::parse(&failed.to_json()).unwrap();
+        assert_eq!(json["error"], "couldn't read aoc03/input.txt");
+        assert!(json["answer"].is_null());
+        assert!(json["input"].is_null());
+        assert_eq!(failed.to_text(), Err("couldn't read aoc03/input.txt".to_string()));
+    }
+
+    #[test]
+    fn test_table() {
+        let mut outcomes = vec![
+            outcome(Answer::Number(95437), None),
+            outcome(Answer::Art("#.\n.#\n".to_string()), None),
+        ];
+        outcomes[1].part = Part::Two;
+        outcomes.push(Outcome { day: 12, part: Part::One, input_hash: None, parse_time: None, result: Err("bad".to_string()) });
+
+        let wall_times = vec![(7, Duration::from_micros(2500)), (12, Duration::from_micros(40))];
+
+        assert_eq!(
+            table(&outcomes, &wall_times),
+            "Day  Part 1   Part 2      Time\n\
+             07   95437    (drawing)  2.5ms\n\
+             12   (error)              40µs",
+        );
+    }
+
+    #[test]
+    fn test_batch_table() {
+        let mut alice = vec![outcome(Answer::Number(95437), None), outcome(Answer::Number(24933642), None)];
+        alice[1].part = Part::Two;
+
+        let bob = vec![Outcome { day: 7, part: Part::One, input_hash: None, parse_time: None, result: Err("bad".to_string()) }];
+
+        assert_eq!(
+            batch_table(&[("alice.txt".to_string(), alice), ("bob.txt".to_string(), bob)]),
+            "File       Part 1   Part 2    Parse  Solve 1  Solve 2\n\
+             alice.txt  95437    24933642  1.5ms    2.0ms    2.0ms\n\
+             bob.txt    (error)",
+        );
+    }
+}
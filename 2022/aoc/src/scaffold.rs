@@ -0,0 +1,284 @@
+//! Sets up a new day's crate from `template/`, and hooks it up to the
+//! workspace, the runner and the examples, so none of it gets forgotten.
+//!
+//! The workspace is found from the current directory rather than from where
+//! the runner was built, so a copied or installed `aoc` works on whichever
+//! checkout it's run in.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The nearest directory from `from` up with a `Cargo.toml` that has a
+/// `[workspace]` in it.
+pub fn find_workspace(from: &Path) -> Result<PathBuf, String> {
+    from.ancestors()
+        .find(|dir| {
+            fs::read_to_string(dir.join("Cargo.toml"))
+                .ok()
+                .and_then(|text| text.parse::<toml::Table>().ok())
+                .is_some_and(|manifest| manifest.contains_key("workspace"))
+        })
+        .map(|dir| dir.to_path_buf())
+        .ok_or(format!("{} isn't in a cargo workspace", from.display()))
+}
+
+/// The workspace for `year`, next to the one `from` is in (the years all
+/// sit side by side in the repository), or in `from` itself when it's the
+/// top of the repository.
+pub fn year_workspace(from: &Path, year: u16) -> Result<PathBuf, String> {
+    let top = match find_workspace(from) {
+        Ok(workspace) => workspace.parent().map(|p| p.to_path_buf()).unwrap_or(workspace),
+        Err(_) => from.to_path_buf(),
+    };
+
+    let workspace = top.join(year.to_string());
+
+    match find_workspace(&workspace) {
+        Ok(found) if found == workspace => Ok(workspace),
+        _ => Err(format!("no workspace for {year} at {}", workspace.display())),
+    }
+}
+
+/// Creates `aocNN` in `workspace` for `day`, and returns every file it
+/// created or changed. Everything's worked out before anything is written,
+/// and should a write still fail, the ones before it are undone.
+pub fn create(workspace: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    let name = format!("aoc{day:02}");
+    let crate_dir = workspace.join(&name);
+    let manifest = workspace.join("examples").join(format!("day{day:02}.toml"));
+    let template = workspace.join("template");
+
+    if crate_dir.exists() {
+        return Err(format!("{} already exists", crate_dir.display()));
+    }
+
+    if manifest.exists() {
+        return Err(format!("{} already exists", manifest.display()));
+    }
+
+    if !template.is_dir() {
+        return Err(format!("no template at {}", template.display()));
+    }
+
+    let mut writes: Vec<(PathBuf, String)> = vec![];
+    // What the files being changed had in them before, to put back
+    let mut originals: Vec<(PathBuf, String)> = vec![];
+
+    // The template's files, renamed for the day
+    for file in template_files(&template)? {
+        let text = read(&template.join(&file))?
+            .replace("name = \"template\"", &format!("name = \"{name}\""))
+            .replace("template::", &format!("{name}::"))
+            .replace("DayNN", &format!("Day{day:02}"));
+
+        writes.push((crate_dir.join(file), text));
+    }
+
+    let members = workspace.join("Cargo.toml");
+    let original = read(&members)?;
+    let text = insert_entry(&original, day, &format!("    \"{name}\","), |line| {
+        line.trim().strip_prefix("\"aoc")?.strip_suffix("\",")?.parse().ok()
+    })
+    .map_err(|e| format!("{}: {e}", members.display()))?;
+    originals.push((members.clone(), original));
+    writes.push((members, text));
+
+    let runner = workspace.join("aoc").join("Cargo.toml");
+    let original = read(&runner)?;
+    let text = insert_entry(&original, day, &format!("{name} = {{ path = \"../{name}\" }}"), |line| {
+        line.strip_prefix("aoc")?.split_once(" = { path")?.0.parse().ok()
+    })
+    .map_err(|e| format!("{}: {e}", runner.display()))?;
+    originals.push((runner.clone(), original));
+    writes.push((runner, text));
+
+    let days = workspace.join("aoc").join("src").join("days.rs");
+    let entry = format!(
+        "    Day {{ number: {day}, parse: common::parse::<{name}::Day{day:02}>, params: {name}::Day{day:02}::PARAMS, \
+         generate: common::generate::<{name}::Day{day:02}>, stream: None, simulate: None }},"
+    );
+    let original = read(&days)?;
+    let text = insert_entry(&original, day, &entry, |line| {
+        line.trim().strip_prefix("Day { number: ")?.split_once(',')?.0.parse().ok()
+    })
+    .map_err(|e| format!("{}: {e}", days.display()))?;
+    originals.push((days.clone(), original));
+    writes.push((days, text));
+
+    // An example with no answers checks nothing until they're filled in
+    writes.push((manifest, format!("# Day {day}\n\n[[example]]\ninput = '''\n'''\n")));
+
+    let examples_existed = workspace.join("examples").is_dir();
+    let mut written = 0;
+
+    let result = [crate_dir.join("src"), workspace.join("examples")]
+        .iter()
+        .try_for_each(|dir| fs::create_dir_all(dir).map_err(|e| format!("couldn't create {}: {e}", dir.display())))
+        .and_then(|_| {
+            writes.iter().try_for_each(|(path, text)| {
+                fs::write(path, text).map_err(|e| format!("couldn't write {}: {e}", path.display()))?;
+                written += 1;
+                Ok(())
+            })
+        });
+
+    if let Err(e) = result {
+        // As much as can be put back, since there's already an error to report
+        for (path, _) in &writes[..written] {
+            match originals.iter().find(|(original, _)| original == path) {
+                Some((_, text)) => { let _ = fs::write(path, text); }
+                None => { let _ = fs::remove_file(path); }
+            }
+        }
+
+        let _ = fs::remove_dir_all(&crate_dir);
+
+        if !examples_existed {
+            let _ = fs::remove_dir(workspace.join("examples"));
+        }
+
+        return Err(e);
+    }
+
+    Ok(writes.into_iter().map(|(path, _)| path).collect())
+}
+
+// `Cargo.toml` and whatever is in `src/`, relative to the template. Anything
+// else in there (`target/`, an `input.txt`) isn't part of it.
+fn template_files(template: &Path) -> Result<Vec<PathBuf>, String> {
+    let src = template.join("src");
+    let entries = fs::read_dir(&src).map_err(|e| format!("couldn't read {}: {e}", src.display()))?;
+
+    let mut files = vec![PathBuf::from("Cargo.toml")];
+
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("couldn't read {}: {e}", src.display()))?;
+
+        if entry.path().is_file() {
+            files.push(PathBuf::from("src").join(entry.file_name()));
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {e}", path.display()))
+}
+
+// Adds `line` among the lines `entry_day` finds a day in, keeping them in
+// order of day
+fn insert_entry(text: &str, day: u8, line: &str, entry_day: impl Fn(&str) -> Option<u8>) -> Result<String, String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let entries: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| entry_day(l).map(|d| (i, d)))
+        .collect();
+
+    if entries.iter().any(|(_, d)| *d == day) {
+        return Err(format!("day {day} is already in there"));
+    }
+
+    let at = match entries.iter().rev().find(|(_, d)| *d < day) {
+        Some((i, _)) => i + 1,
+        None => entries.first().ok_or("couldn't find where the days are listed")?.0,
+    };
+
+    lines.insert(at, line);
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scaffold::*;
+
+    #[test]
+    fn test_insert_entry() {
+        let day = |l: &str| l.trim().strip_prefix("\"aoc")?.strip_suffix("\",")?.parse().ok();
+        let members = "members = [\n    \"aoc\",\n    \"aoc18\",\n    \"aoc20\",\n]\n";
+
+        assert_eq!(
+            insert_entry(members, 19, "    \"aoc19\",", day),
+            Ok("members = [\n    \"aoc\",\n    \"aoc18\",\n    \"aoc19\",\n    \"aoc20\",\n]\n".to_string()),
+        );
+
+        assert_eq!(
+            insert_entry(members, 1, "    \"aoc01\",", day),
+            Ok("members = [\n    \"aoc\",\n    \"aoc01\",\n    \"aoc18\",\n    \"aoc20\",\n]\n".to_string()),
+        );
+
+        assert!(insert_entry(members, 20, "    \"aoc20\",", day).is_err());
+        assert!(insert_entry("members = []\n", 1, "    \"aoc01\",", day).is_err());
+    }
+
+    #[test]
+    fn test_create() {
+        let workspace = std::env::temp_dir().join(format!("aoc-test-scaffold-{}", std::process::id()));
+        let here = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+
+        fs::create_dir_all(workspace.join("aoc").join("src")).unwrap();
+        fs::create_dir_all(workspace.join("template").join("src")).unwrap();
+        fs::create_dir_all(workspace.join("examples")).unwrap();
+
+        for file in ["template/Cargo.toml", "template/src/lib.rs", "template/src/main.rs"] {
+            fs::copy(here.join(file), workspace.join(file)).unwrap();
+        }
+
+        let write = |file: &str, text: &str| fs::write(workspace.join(file), text).unwrap();
+        write("Cargo.toml", "[workspace]\nmembers = [\n    \"aoc\",\n    \"aoc18\",\n    \"aoc20\",\n]\n");
+        write("aoc/Cargo.toml", "[dependencies]\naoc18 = { path = \"../aoc18\" }\naoc20 = { path = \"../aoc20\" }\n");
+        write(
+            "aoc/src/days.rs",
+            "pub const DAYS: &[Day] = &[\n    Day { number: 18, parse: p18 },\n    Day { number: 20, parse: p20 },\n];\n",
+        );
+
+        let created = create(&workspace, 19);
+        let read = |file: &str| fs::read_to_string(workspace.join(file)).unwrap();
+
+        assert_eq!(created.map(|files| files.len()), Ok(7));
+        assert!(read("Cargo.toml").contains("    \"aoc18\",\n    \"aoc19\",\n    \"aoc20\",\n"));
+        assert!(read("aoc/Cargo.toml").contains("aoc19 = { path = \"../aoc19\" }\naoc20"));
+        assert!(read("aoc/src/days.rs").contains("Day { number: 19, parse: common::parse::<aoc19::Day19>,"));
+        assert!(read("aoc19/Cargo.toml").contains("name = \"aoc19\""));
+        assert!(read("aoc19/src/main.rs").contains("common::run::<aoc19::Day19>()"));
+        assert!(read("aoc19/src/lib.rs").contains("pub struct Day19;"));
+        assert!(read("examples/day19.toml").contains("[[example]]"));
+
+        // Never over the top of a day that's already there
+        let members = read("Cargo.toml");
+        assert!(create(&workspace, 19).is_err());
+        assert!(create(&workspace, 20).is_err());
+        assert_eq!(read("Cargo.toml"), members);
+
+        // A write that fails at the last moment leaves everything as it was
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(workspace.join("nowhere").join("day21.toml"), workspace.join("examples/day21.toml")).unwrap();
+            let days = read("aoc/src/days.rs");
+
+            assert!(create(&workspace, 21).unwrap_err().contains("day21.toml"));
+            assert_eq!(read("Cargo.toml"), members);
+            assert_eq!(read("aoc/src/days.rs"), days);
+            assert!(!workspace.join("aoc21").exists());
+        }
+
+        fs::remove_dir_all(&workspace).unwrap();
+    }
+
+    #[test]
+    fn test_find_workspace() {
+        let here = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let workspace = here.parent().unwrap();
+
+        assert_eq!(find_workspace(&here.join("src")), Ok(workspace.to_path_buf()));
+        assert_eq!(find_workspace(workspace), Ok(workspace.to_path_buf()));
+        assert_eq!(year_workspace(&here.join("src"), 2022), Ok(workspace.to_path_buf()));
+        assert_eq!(year_workspace(workspace.parent().unwrap(), 2022), Ok(workspace.to_path_buf()));
+        assert!(year_workspace(here, 2015).unwrap_err().starts_with("no workspace for 2015"));
+    }
+}
@@ -0,0 +1,176 @@
+//! Talking to the Advent of Code site, as the logged in user whose session
+//! token it's given.
+//!
+//! The token is the `session` cookie from a logged in browser. It's read from
+//! `AOC_SESSION` if that's set, or else from a file (`.aoc-session` unless
+//! told otherwise), which git ignores.
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+pub const DEFAULT_SERVER: &str = "https://adventofcode.com";
+pub const DEFAULT_SESSION_FILE: &str = ".aoc-session";
+
+pub struct Site {
+    agent: ureq::Agent,
+    // No trailing slash
+    server: String,
+    session: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SiteError {
+    // There was no session token to send
+    NoSession,
+    // 404: the puzzle isn't out yet, or there's no such day
+    NotFound,
+    // 400: the session token wasn't accepted, most likely having expired
+    BadSession,
+    // Any other status, with what came with it
+    Status(u16, String),
+    // Couldn't get an answer at all
+    Transport(String),
+}
+
+impl fmt::Display for SiteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SiteError::NoSession => write!(
+                f,
+                "no session token, set AOC_SESSION or put it in {DEFAULT_SESSION_FILE} (or wherever --session-file says)",
+            ),
+            SiteError::NotFound => write!(f, "not found"),
+            SiteError::BadSession => write!(
+                f,
+                "the session token wasn't accepted; it has probably expired, so log in again and copy the new one",
+            ),
+            SiteError::Status(status, body) => write!(f, "the server said {status}: {}", body.trim()),
+            SiteError::Transport(e) => write!(f, "couldn't reach the server: {e}"),
+        }
+    }
+}
+
+/// What to tell the site this is, so whoever runs it knows what's sending
+/// the requests. `AOC_CONTACT` adds a way to get hold of whoever's running
+/// it, which the site asks automated tools to give.
+pub fn user_agent() -> String {
+    let contact = env::var("AOC_CONTACT").map(|c| format!("; {}", c.trim())).unwrap_or_default();
+    format!("aoc-runner/{} (personal Advent of Code solutions runner{contact})", env!("CARGO_PKG_VERSION"))
+}
+
+/// The session token from `AOC_SESSION`, or else `file`. None if neither has
+/// one, which is only a problem once there's something to ask the site.
+pub fn session(file: &Path) -> Option<String> {
+    let token = env::var("AOC_SESSION").ok().or_else(|| fs::read_to_string(file).ok())?;
+
+    // Copied out of the browser, it might still have the cookie name on it
+    let token = token.trim();
+    let token = token.strip_prefix("session=").unwrap_or(token);
+
+    (!token.is_empty()).then(|| token.to_string())
+}
+
+impl Site {
+    pub fn new(server: &str, session: Option<String>) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(&user_agent())
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Site { agent, server: server.trim_end_matches('/').to_string(), session }
+    }
+
+    /// The body of the page at `path`, which starts with a `/`.
+    pub fn get(&self, path: &str) -> Result<String, SiteError> {
+        let session = self.session.as_ref().ok_or(SiteError::NoSession)?;
+
+        let response = self
+            .agent
+            .get(&format!("{}{path}", self.server))
+            .set("Cookie", &format!("session={session}"))
+            .call();
+
+        Self::body(response)
+    }
+
+    /// Posts `form` to `path`, giving back the body of the page that comes
+    /// back.
+    pub fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String, SiteError> {
+        let session = self.session.as_ref().ok_or(SiteError::NoSession)?;
+
+        let response = self
+            .agent
+            .post(&format!("{}{path}", self.server))
+            .set("Cookie", &format!("session={session}"))
+            .send_form(form);
+
+        Self::body(response)
+    }
+
+    fn body(response: Result<ureq::Response, ureq::Error>) -> Result<String, SiteError> {
+        match response {
+            Ok(response) => response.into_string().map_err(|e| SiteError::Transport(e.to_string())),
+            Err(ureq::Error::Status(404, _)) => Err(SiteError::NotFound),
+            Err(ureq::Error::Status(400, _)) => Err(SiteError::BadSession),
+            Err(ureq::Error::Status(status, response)) => {
+                Err(SiteError::Status(status, response.into_string().unwrap_or_default()))
+            }
+            Err(ureq::Error::Transport(e)) => Err(SiteError::Transport(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::site::*;
+    use crate::stand_in::StandIn;
+
+    #[test]
+    fn test_get() {
+        let stand_in = StandIn::start(&[
+            ("GET", "/2022/day/7/input", 200, "$ cd /\n"),
+            ("GET", "/2022/day/8/input", 400, "Puzzle inputs differ by user.  Please log in to get your puzzle input."),
+            ("GET", "/2022/day/9/input", 500, "Internal Server Error"),
+        ]);
+
+        let site = Site::new(&format!("{}/", stand_in.url), Some("abc123".to_string()));
+
+        assert_eq!(site.get("/2022/day/7/input"), Ok("$ cd /\n".to_string()));
+        assert_eq!(site.get("/2022/day/8/input"), Err(SiteError::BadSession));
+        assert_eq!(site.get("/2022/day/9/input"), Err(SiteError::Status(500, "Internal Server Error".to_string())));
+        assert_eq!(site.get("/2022/day/26/input"), Err(SiteError::NotFound));
+
+        let request = &stand_in.requests()[0];
+        assert_eq!(request.header("Cookie"), Some("session=abc123"));
+        assert!(request.header("User-Agent").unwrap().starts_with("aoc-runner/"));
+    }
+
+    #[test]
+    fn test_no_session() {
+        let stand_in = StandIn::start(&[("GET", "/2022/day/7/input", 200, "$ cd /\n")]);
+        let site = Site::new(&stand_in.url, None);
+
+        assert_eq!(site.get("/2022/day/7/input"), Err(SiteError::NoSession));
+        assert!(stand_in.requests().is_empty());
+    }
+
+    #[test]
+    fn test_session_file() {
+        let file = std::env::temp_dir().join(format!("aoc-test-session-{}", std::process::id()));
+
+        // Only if the environment doesn't have one, which would win
+        if env::var("AOC_SESSION").is_err() {
+            fs::write(&file, "session=abc123\n").unwrap();
+            assert_eq!(session(&file), Some("abc123".to_string()));
+
+            fs::write(&file, "\n").unwrap();
+            assert_eq!(session(&file), None);
+
+            fs::remove_file(&file).unwrap();
+            assert_eq!(session(&file), None);
+        }
+    }
+}
@@ -0,0 +1,436 @@
+//! Sending answers to the site, and keeping every one sent in a local record
+//! (`attempts.json`, git ignored), so that an answer that's already been
+//! turned down never goes again. Nor does one the site's hints already rule
+//! out: anything at or over an answer that was too high, or at or under one
+//! that was too low. Nor does anything at all while the site's still asking
+//! for a wait after the last answer, which goes for the whole account, not
+//! just the part it was for.
+
+use std::fs;
+use std::path::Path;
+
+use common::Part;
+use json::JsonValue;
+
+use crate::site::{Site, SiteError};
+
+pub const DEFAULT_ATTEMPTS: &str = "attempts.json";
+
+/// What the site made of an answer.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    // Wrong, without saying which way
+    Wrong,
+    // Sent too soon after the last one, with how many seconds there were to go
+    Wait(u64),
+    // Not the part the site's expecting: either it's solved already, or it
+    // isn't unlocked yet (part two, before part one's done)
+    WrongLevel,
+}
+
+/// The site's verdict, and how many seconds it asked to wait before the
+/// next answer (0 if it didn't say).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Reply {
+    pub verdict: Verdict,
+    pub wait: u64,
+}
+
+impl Verdict {
+    // As it's kept in the record
+    fn name(&self) -> String {
+        match self {
+            Verdict::Correct => "correct".to_string(),
+            Verdict::TooHigh => "too high".to_string(),
+            Verdict::TooLow => "too low".to_string(),
+            Verdict::Wrong => "wrong".to_string(),
+            Verdict::Wait(secs) => format!("wait {secs}"),
+            Verdict::WrongLevel => "wrong level".to_string(),
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Verdict> {
+        match name {
+            "correct" => Some(Verdict::Correct),
+            "too high" => Some(Verdict::TooHigh),
+            "too low" => Some(Verdict::TooLow),
+            "wrong" => Some(Verdict::Wrong),
+            "wrong level" => Some(Verdict::WrongLevel),
+            _ => name.strip_prefix("wait ")?.parse().ok().map(Verdict::Wait),
+        }
+    }
+
+    fn rejected(&self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+/// Reads the verdict out of the page the site sends back for an answer.
+/// Anything it doesn't recognise comes back as the page's text.
+pub fn parse_response(html: &str) -> Result<Reply, String> {
+    let verdict = parse_verdict(html)?;
+
+    let wait = match verdict {
+        Verdict::Wait(secs) => secs,
+        _ => parse_wait(html),
+    };
+
+    Ok(Reply { verdict, wait })
+}
+
+fn parse_verdict(html: &str) -> Result<Verdict, String> {
+    if html.contains("That's the right answer") {
+        Ok(Verdict::Correct)
+    } else if html.contains("That's not the right answer") {
+        Ok(if html.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if html.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        })
+    } else if html.contains("You gave an answer too recently") {
+        // "You have 1m 23s left to wait." or "You have 45s left to wait."
+        let left = html.split("You have ").nth(1).and_then(|rest| rest.split(" left to wait").next()).unwrap_or("");
+        let secs = left.split_whitespace().try_fold(0, |total, part| {
+            let (number, unit) = part.split_at(part.len().saturating_sub(1));
+            let number: u64 = number.parse().ok()?;
+
+            match unit {
+                "h" => Some(total + number * 3600),
+                "m" => Some(total + number * 60),
+                "s" => Some(total + number),
+                _ => None,
+            }
+        });
+
+        Ok(Verdict::Wait(secs.unwrap_or(60)))
+    } else if html.contains("You don't seem to be solving the right level") {
+        Ok(Verdict::WrongLevel)
+    } else {
+        Err(text_of(html))
+    }
+}
+
+// "Please wait one minute before trying again." or "Please wait 5 minutes
+// before trying again.", after a wrong answer
+fn parse_wait(html: &str) -> u64 {
+    let Some(rest) = html.split("Please wait ").nth(1) else {
+        return 0;
+    };
+
+    let mut words = rest.split_whitespace();
+    let minutes = match words.next() {
+        Some("one") => Some(1),
+        Some("two") => Some(2),
+        Some("three") => Some(3),
+        Some("four") => Some(4),
+        Some("five") => Some(5),
+        Some("ten") => Some(10),
+        Some(number) => number.parse().ok(),
+        None => None,
+    };
+
+    match (minutes, words.next()) {
+        (Some(minutes), Some(unit)) if unit.starts_with("minute") => minutes * 60,
+        // Worded some other way, so going by the shortest the site asks for
+        _ => 60,
+    }
+}
+
+// The text of the page's <article>, or the whole thing, without the tags
+fn text_of(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map(|(article, _)| article)
+        .unwrap_or(html);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Posts `answer` for `part` of `day`.
+pub fn send(site: &Site, year: u16, day: u8, part: Part, answer: &str) -> Result<Reply, String> {
+    let level = part.number().to_string();
+
+    let html = site
+        .post(&format!("/{year}/day/{day}/answer"), &[("level", &level), ("answer", answer)])
+        .map_err(|e| match e {
+            SiteError::NotFound => format!("day {day} of {year} isn't unlocked yet, or doesn't exist"),
+            e => format!("couldn't submit day {day} of {year}: {e}"),
+        })?;
+
+    parse_response(&html).map_err(|text| format!("didn't understand the site's answer: {text}"))
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Attempt {
+    pub year: u16,
+    pub day: u8,
+    pub part: usize,
+    pub answer: String,
+    pub verdict: Verdict,
+    // Seconds since the Unix epoch
+    pub at: u64,
+    // Seconds the site asked to wait after it before the next answer
+    pub wait: u64,
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct Attempts {
+    attempts: Vec<Attempt>,
+}
+
+impl Attempts {
+    /// Reads the record at `path`. One that doesn't exist yet is just empty.
+    pub fn load(path: &Path) -> Result<Attempts, String> {
+        if !path.exists() {
+            return Ok(Attempts::default());
+        }
+
+        let text = fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {e}", path.display()))?;
+        Self::from_json(&text).map_err(|e| format!("bad attempts file {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_json()).map_err(|e| format!("couldn't write {}: {e}", path.display()))
+    }
+
+    pub fn record(&mut self, attempt: Attempt) {
+        self.attempts.push(attempt);
+    }
+
+    /// Why `answer` shouldn't be sent at `now`, going by the attempts before,
+    /// or None if it's worth a go.
+    pub fn refusal(&self, year: u16, day: u8, part: Part, answer: &str, now: u64) -> Option<String> {
+        let before: Vec<&Attempt> = self
+            .attempts
+            .iter()
+            .filter(|a| a.year == year && a.day == day && a.part == part.number())
+            .collect();
+
+        if let Some(solved) = before.iter().find(|a| a.verdict == Verdict::Correct) {
+            return Some(format!("this part is already solved, with {}", solved.answer));
+        }
+
+        // The site counts answers to any day against the same account
+        if let Some(last) = self.attempts.iter().max_by_key(|a| a.at) {
+            if now < last.at + last.wait {
+                return Some(format!("the site asked to wait another {}s before the next answer", last.at + last.wait - now));
+            }
+        }
+
+        if let Some(same) = before.iter().find(|a| a.answer == answer && a.verdict.rejected()) {
+            return Some(format!("{answer} was already turned down ({})", same.verdict.name()));
+        }
+
+        let number: i64 = answer.parse().ok()?;
+        let numbers = |verdict: Verdict| before.iter().filter(move |a| a.verdict == verdict).filter_map(|a| a.answer.parse::<i64>().ok());
+
+        if let Some(high) = numbers(Verdict::TooHigh).min().filter(|high| number >= *high) {
+            return Some(format!("{answer} is too high, as {high} already was"));
+        }
+
+        if let Some(low) = numbers(Verdict::TooLow).max().filter(|low| number <= *low) {
+            return Some(format!("{answer} is too low, as {low} already was"));
+        }
+
+        None
+    }
+
+    // { "attempts": [ { "year": 2022, "day": 7, "part": 1, "answer": "95437", "verdict": "too low", "at": 1670400000, "wait": 60 } ] }
+    fn to_json(&self) -> String {
+        let mut attempts = JsonValue::new_array();
+
+        for attempt in &self.attempts {
+            let mut entry = JsonValue::new_object();
+            entry["year"] = attempt.year.into();
+            entry["day"] = attempt.day.into();
+            entry["part"] = attempt.part.into();
+            entry["answer"] = attempt.answer.as_str().into();
+            entry["verdict"] = attempt.verdict.name().into();
+            entry["at"] = attempt.at.into();
+            entry["wait"] = attempt.wait.into();
+
+            attempts.push(entry).unwrap();
+        }
+
+        let mut record = JsonValue::new_object();
+        record["attempts"] = attempts;
+
+        record.pretty(2)
+    }
+
+    fn from_json(text: &str) -> Result<Attempts, String> {
+        let record = json::parse(text).map_err(|e| e.to_string())?;
+        let mut attempts = vec![];
+
+        for (i, entry) in record["attempts"].members().enumerate() {
+            let year = entry["year"].as_u16();
+            let day = entry["day"].as_u8();
+            let part = entry["part"].as_usize();
+            let answer = entry["answer"].as_str();
+            let verdict = entry["verdict"].as_str().and_then(Verdict::from_name);
+            let at = entry["at"].as_u64();
+            let wait = entry["wait"].as_u64();
+
+            match (year, day, part, answer, verdict, at, wait) {
+                (Some(year), Some(day), Some(part), Some(answer), Some(verdict), Some(at), Some(wait)) => {
+                    attempts.push(Attempt { year, day, part, answer: answer.to_string(), verdict, at, wait });
+                }
+
+                _ => return Err(format!("entry {} needs a year, day, part, answer, verdict, time and wait", i + 1)),
+            }
+        }
+
+        Ok(Attempts { attempts })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stand_in::StandIn;
+    use crate::submit::*;
+
+    fn page(text: &str) -> String {
+        format!("<html><body><main>\n<article><p>{text}</p></article>\n</main></body></html>")
+    }
+
+    fn attempt(part: usize, answer: &str, verdict: Verdict, at: u64) -> Attempt {
+        let wait = if let Verdict::Wait(secs) = verdict { secs } else { 0 };
+        Attempt { year: 2022, day: 15, part, answer: answer.to_string(), verdict, at, wait }
+    }
+
+    fn reply(verdict: Verdict, wait: u64) -> Result<Reply, String> {
+        Ok(Reply { verdict, wait })
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let right = page("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.");
+        assert_eq!(parse_response(&right), reply(Verdict::Correct, 0));
+
+        let high = page("That's not the right answer; your answer is too high.  If you're stuck, ... Please wait one minute before trying again.");
+        assert_eq!(parse_response(&high), reply(Verdict::TooHigh, 60));
+        let low = page("That's not the right answer; your answer is too low.  Please wait 5 minutes before trying again.");
+        assert_eq!(parse_response(&low), reply(Verdict::TooLow, 300));
+        assert_eq!(parse_response(&page("That's not the right answer.  If you're stuck, ...")), reply(Verdict::Wrong, 0));
+
+        let wait = page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait.");
+        assert_eq!(parse_response(&wait), reply(Verdict::Wait(83), 83));
+        assert_eq!(parse_response(&page("You gave an answer too recently. You have 45s left to wait.")), reply(Verdict::Wait(45), 45));
+
+        let solved = page("You don't seem to be solving the right level.  Did you already complete it?");
+        assert_eq!(parse_response(&solved), reply(Verdict::WrongLevel, 0));
+
+        assert_eq!(parse_response(&page("Something <em>else</em> entirely.")), Err("Something else entirely.".to_string()));
+    }
+
+    #[test]
+    fn test_refusal() {
+        let mut attempts = Attempts::default();
+        assert_eq!(attempts.refusal(2022, 15, Part::Two, "56000011", 1000), None);
+
+        attempts.record(attempt(2, "Tuning freq", Verdict::Wrong, 1000));
+        attempts.record(attempt(2, "60000000", Verdict::TooHigh, 1100));
+        attempts.record(attempt(2, "50000000", Verdict::TooLow, 1200));
+
+        assert_eq!(
+            attempts.refusal(2022, 15, Part::Two, "Tuning freq", 2000),
+            Some("Tuning freq was already turned down (wrong)".to_string()),
+        );
+        assert_eq!(
+            attempts.refusal(2022, 15, Part::Two, "60000001", 2000),
+            Some("60000001 is too high, as 60000000 already was".to_string()),
+        );
+        assert_eq!(
+            attempts.refusal(2022, 15, Part::Two, "49999999", 2000),
+            Some("49999999 is too low, as 50000000 already was".to_string()),
+        );
+        assert_eq!(attempts.refusal(2022, 15, Part::Two, "56000011", 2000), None);
+
+        // Other parts, days and years have nothing to do with it
+        assert_eq!(attempts.refusal(2022, 15, Part::One, "60000001", 2000), None);
+        assert_eq!(attempts.refusal(2022, 16, Part::Two, "60000001", 2000), None);
+        assert_eq!(attempts.refusal(2021, 15, Part::Two, "60000001", 2000), None);
+
+        attempts.record(attempt(2, "56000011", Verdict::Wait(60), 2000));
+        assert_eq!(
+            attempts.refusal(2022, 15, Part::Two, "56000011", 2020),
+            Some("the site asked to wait another 40s before the next answer".to_string()),
+        );
+        assert_eq!(attempts.refusal(2022, 15, Part::Two, "56000011", 2060), None);
+
+        // A wait after a wrong answer holds up every day, not just that one
+        attempts.record(Attempt { wait: 300, ..attempt(1, "27", Verdict::TooLow, 2030) });
+        assert_eq!(
+            attempts.refusal(2022, 16, Part::One, "1651", 2100),
+            Some("the site asked to wait another 230s before the next answer".to_string()),
+        );
+        assert_eq!(attempts.refusal(2022, 16, Part::One, "1651", 2330), None);
+
+        // Sent too early for part two, which can be sent again once it's open
+        attempts.record(attempt(2, "56000011", Verdict::WrongLevel, 2330));
+        assert_eq!(attempts.refusal(2022, 15, Part::Two, "56000011", 2400), None);
+
+        attempts.record(attempt(2, "56000011", Verdict::Correct, 2060));
+        assert_eq!(
+            attempts.refusal(2022, 15, Part::Two, "56000012", 3000),
+            Some("this part is already solved, with 56000011".to_string()),
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut attempts = Attempts::default();
+        attempts.record(attempt(1, "26", Verdict::TooLow, 1000));
+        attempts.record(attempt(2, "56000011", Verdict::Wait(83), 1100));
+        attempts.record(Attempt { wait: 60, ..attempt(2, "56000012", Verdict::TooHigh, 1150) });
+        attempts.record(attempt(2, "56000011", Verdict::WrongLevel, 1160));
+        attempts.record(attempt(2, "56000011", Verdict::Correct, 1200));
+
+        assert_eq!(Attempts::from_json(&attempts.to_json()), Ok(attempts));
+        assert!(Attempts::from_json(r#"{ "attempts": [ { "year": 2022, "verdict": "meh" } ] }"#).is_err());
+
+        let no_wait = r#"{ "attempts": [ { "year": 2022, "day": 7, "part": 1, "answer": "95437", "verdict": "wrong level", "at": 1000 } ] }"#;
+        assert_eq!(
+            Attempts::from_json(no_wait),
+            Err("entry 1 needs a year, day, part, answer, verdict, time and wait".to_string()),
+        );
+    }
+
+    #[test]
+    fn test_send() {
+        let stand_in = StandIn::start(&[
+            ("POST", "/2022/day/15/answer", 200, &page("That's not the right answer; your answer is too low.")),
+            ("POST", "/2022/day/16/answer", 200, "<html>Something went wrong</html>"),
+        ]);
+
+        let site = Site::new(&stand_in.url, Some("abc123".to_string()));
+
+        assert_eq!(send(&site, 2022, 15, Part::Two, "Tuning freq"), reply(Verdict::TooLow, 0));
+        assert!(send(&site, 2022, 16, Part::One, "1651").unwrap_err().contains("Something went wrong"));
+        assert!(send(&site, 2022, 26, Part::One, "1").unwrap_err().contains("isn't unlocked yet"));
+
+        let request = &stand_in.requests()[0];
+        assert_eq!(request.header("Cookie"), Some("session=abc123"));
+        assert_eq!(request.header("Content-Type"), Some("application/x-www-form-urlencoded"));
+        assert_eq!(request.body, "level=2&answer=Tuning+freq");
+    }
+}
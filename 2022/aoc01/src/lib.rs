@@ -0,0 +1,95 @@
+use common::{parse_number, parse_streamed, Answer, Generated, ParseError, Rng, Solution, Streaming};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = ElfTotals;
+
+    fn parse(input: &[String]) -> Result<ElfTotals, ParseError> {
+        parse_streamed::<Self>(input)
+    }
+
+    // Get the total calories carried by the elf with the most calories
+    fn part_one(totals: &ElfTotals) -> Answer {
+        totals.top_three().first().copied().unwrap_or(0).into()
+    }
+
+    // Get the total calories carried by the 3 elves with the most calories
+    fn part_two(totals: &ElfTotals) -> Answer {
+        totals.top_three().iter().sum::<i64>().into()
+    }
+}
+
+// Input comes as calories per food item, in blocks of food items carried per
+// elf. A blank line indicates the end of the list for one elf, thus separating
+// the elves food lists.
+impl Streaming for Day01 {
+    fn add_line(totals: &mut ElfTotals, line: &str) -> Result<(), ParseError> {
+        if line.trim().is_empty() {
+            // Blank line = elf finished
+            totals.finish_elf();
+        } else {
+            // Otherwise it's another food item for the current elf
+            let calories: i64 = parse_number(line, line)?;
+            *totals.current.get_or_insert(0) += calories;
+        }
+
+        Ok(())
+    }
+}
+
+// An elf for every few of `size`, carrying a handful of snacks each
+impl Generated for Day01 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        let mut lines = vec![];
+
+        for elf in 0..std::cmp::max(1, size * 5 / 2) {
+            if elf > 0 {
+                lines.push(String::new());
+            }
+
+            for _ in 0..rng.range(1, 15) {
+                lines.push(rng.range(1000, 60000).to_string());
+            }
+        }
+
+        lines
+    }
+}
+
+// Only the biggest few totals matter, so there's no need to remember every
+// elf, never mind every food item.
+#[derive(Debug, Default, PartialEq)]
+pub struct ElfTotals {
+    // Calories carried by the elf whose items are being counted up
+    current: Option<i64>,
+    // Highest totals among the elves done so far, biggest first
+    top: Vec<i64>,
+}
+
+impl ElfTotals {
+    fn finish_elf(&mut self) {
+        if let Some(calories) = self.current.take() {
+            Self::add_to_top(&mut self.top, calories);
+        }
+    }
+
+    fn add_to_top(top: &mut Vec<i64>, calories: i64) {
+        top.push(calories);
+        //b.cmp(a) instead of a.cmp(b) sorts in reverse; biggest first.
+        top.sort_by(|a, b| b.cmp(a));
+        top.truncate(3);
+    }
+
+    /// The three highest totals, biggest first. The last elf doesn't get a
+    /// blank line after it, so counts here too.
+    pub fn top_three(&self) -> Vec<i64> {
+        let mut top = self.top.clone();
+
+        if let Some(calories) = self.current {
+            Self::add_to_top(&mut top, calories);
+        }
+
+        top
+    }
+}
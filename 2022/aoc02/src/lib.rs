@@ -0,0 +1,294 @@
+use common::{parse_streamed, Answer, Generated, ParseError, Rng, Solution, Streaming};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Scores;
+
+    fn parse(input: &[String]) -> Result<Scores, ParseError> {
+        parse_streamed::<Self>(input)
+    }
+
+    fn part_one(scores: &Scores) -> Answer {
+        scores.part_one.into()
+    }
+
+    fn part_two(scores: &Scores) -> Answer {
+        scores.part_two.into()
+    }
+}
+
+// Each round's score only gets added to the total, so it's added up as the
+// rounds come in rather than keeping them all around.
+impl Streaming for Day02 {
+    fn add_line(scores: &mut Scores, line: &str) -> Result<(), ParseError> {
+        let round = Round::parse(line)?;
+
+        scores.part_one += calculate_round_score(&round.opponent, &round.you);
+
+        let you = choose_shape(round.opponent, &round.outcome);
+        scores.part_two += calculate_round_score(&round.opponent, &you);
+
+        Ok(())
+    }
+}
+
+impl Generated for Day02 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        (0..std::cmp::max(1, size * 25))
+            .map(|_| format!("{} {}", rng.pick(&['A', 'B', 'C']), rng.pick(&['X', 'Y', 'Z'])))
+            .collect()
+    }
+}
+
+/// Total score for each part's reading of the strategy guide.
+#[derive(Debug, Default, PartialEq)]
+pub struct Scores {
+    pub part_one: i64,
+    pub part_two: i64,
+}
+
+// The second column means a different thing in each part; a shape to play in
+// part one, and the outcome we're after in part two. So we keep both readings.
+#[derive(Debug, PartialEq)]
+pub struct Round {
+    opponent: Shape,
+    you: Shape,
+    outcome: Outcome,
+}
+
+impl Round {
+    pub fn parse(input_line: &str) -> Result<Round, ParseError> {
+        let opponent = input_line.chars().nth(0)
+            .and_then(Shape::from_char)
+            .ok_or_else(|| ParseError::at_char(input_line, 0, "A, B or C"))?;
+
+        let second = input_line.chars().nth(2);
+
+        let (you, outcome) = second
+            .and_then(Shape::from_char)
+            .zip(second.and_then(Outcome::from_char))
+            .ok_or_else(|| ParseError::at_char(input_line, 2, "X, Y or Z"))?;
+
+        Ok(Round { opponent, you, outcome })
+    }
+}
+
+pub fn choose_shape(opponent: Shape, outcome: &Outcome) -> Shape {
+    match (opponent, outcome) {
+        (Shape::Rock, Outcome::Loss) => Shape::Scissors,
+        (Shape::Rock, Outcome::Win) => Shape::Paper,
+        (Shape::Paper, Outcome::Loss) => Shape::Rock,
+        (Shape::Paper, Outcome::Win) => Shape::Scissors,
+        (Shape::Scissors, Outcome::Loss) => Shape::Paper,
+        (Shape::Scissors, Outcome::Win) => Shape::Rock,
+        _ => opponent,
+    }
+}
+
+pub fn calculate_round_score(opponent: &Shape, you: &Shape) -> i64 {
+    let shape_score = you.value();
+    let outcome_score = match (opponent, you) {
+        (Shape::Scissors, Shape::Rock) => 6,
+        (Shape::Rock, Shape::Paper) => 6,
+        (Shape::Paper, Shape::Scissors) => 6,
+        (Shape::Rock, Shape::Rock) => 3,
+        (Shape::Paper, Shape::Paper) => 3,
+        (Shape::Scissors, Shape::Scissors) => 3,
+        _ => 0,
+    };
+
+    shape_score + outcome_score
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Shape {
+    Rock,
+    Paper,
+    Scissors
+}
+
+impl Shape {
+    pub fn value(&self) -> i64 {
+        match self {
+            Self::Rock => 1,
+            Self::Paper => 2,
+            Self::Scissors => 3,
+        }
+    }
+
+    pub fn from_char(c: char) -> Option<Shape> {
+        // X,Y,Z only relevant for part one of the challenge.
+        match c {
+            'A' | 'X' => Some(Self::Rock),
+            'B' | 'Y' => Some(Self::Paper),
+            'C' | 'Z' => Some(Self::Scissors),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub fn from_char(c: char) -> Option<Outcome> {
+        match c {
+            'X' => Some(Self::Loss),
+            'Y' => Some(Self::Draw),
+            'Z' => Some(Self::Win),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Shape;
+    use crate::Outcome;
+    use crate::calculate_round_score;
+    use crate::Round;
+    use crate::choose_shape;
+
+
+    #[test]
+    fn correctly_converts_char_to_shape() {
+        assert_eq!(
+            Shape::from_char('A').unwrap(),
+            Shape::Rock,
+        );
+
+        assert_eq!(
+            Shape::from_char('B').unwrap(),
+            Shape::Paper,
+        );
+
+        assert_eq!(
+            Shape::from_char('C').unwrap(),
+            Shape::Scissors,
+        );
+
+        assert_eq!(
+            Shape::from_char('X').unwrap(),
+            Shape::Rock,
+        );
+
+        assert_eq!(
+            Shape::from_char('Y').unwrap(),
+            Shape::Paper,
+        );
+
+        assert_eq!(
+            Shape::from_char('Z').unwrap(),
+            Shape::Scissors,
+        );
+
+        assert_eq!(
+            Shape::from_char('Q'),
+            None,
+        );
+    }
+
+    #[test]
+    fn calculates_correct_round_score() {
+        assert_eq!(
+            calculate_round_score(
+                &Shape::from_char('A').unwrap(),
+                &Shape::from_char('Y').unwrap(),
+            ),
+            8,
+        );
+
+        assert_eq!(
+            calculate_round_score(
+                &Shape::from_char('B').unwrap(),
+                &Shape::from_char('X').unwrap(),
+            ),
+            1,
+        );
+
+        assert_eq!(
+            calculate_round_score(
+                &Shape::from_char('C').unwrap(),
+                &Shape::from_char('Z').unwrap(),
+            ),
+            6,
+        );
+    }
+
+    #[test]
+    fn round_parses_shapes_for_part_1() {
+        let shapes = |line| {
+            let round = Round::parse(line).unwrap();
+            (round.opponent, round.you)
+        };
+
+        assert_eq!(
+            shapes("A Y"),
+            (Shape::Rock, Shape::Paper),
+        );
+
+        assert_eq!(
+            shapes("B X"),
+            (Shape::Paper, Shape::Rock),
+        );
+
+        assert_eq!(
+            shapes("C Z"),
+            (Shape::Scissors, Shape::Scissors),
+        );
+    }
+
+    #[test]
+    fn round_rejects_bad_lines() {
+        assert!(Round::parse("D Y").is_err());
+        assert!(Round::parse("A Q").is_err());
+        assert!(Round::parse("A").is_err());
+        assert!(Round::parse("").is_err());
+    }
+
+    #[test]
+    fn correct_converts_char_to_outcome() {
+        assert_eq!(
+            Outcome::from_char('X').unwrap(),
+            Outcome::Loss,
+        );
+
+        assert_eq!(
+            Outcome::from_char('Y').unwrap(),
+            Outcome::Draw,
+        );
+
+        assert_eq!(
+            Outcome::from_char('Z').unwrap(),
+            Outcome::Win,
+        );
+    }
+
+    #[test]
+    fn round_chooses_shapes_for_part_2() {
+        let shapes = |line| {
+            let round = Round::parse(line).unwrap();
+            (round.opponent, choose_shape(round.opponent, &round.outcome))
+        };
+
+        assert_eq!(
+            shapes("A Y"),
+            (Shape::Rock, Shape::Rock),
+        );
+
+        assert_eq!(
+            shapes("B X"),
+            (Shape::Paper, Shape::Rock),
+        );
+
+        assert_eq!(
+            shapes("C Z"),
+            (Shape::Scissors, Shape::Rock),
+        );
+    }
+}
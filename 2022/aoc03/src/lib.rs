@@ -0,0 +1,256 @@
+use common::{parse_lines, Answer, Generated, ParseError, Rng, Solution};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<Rucksack>;
+
+    fn parse(input: &[String]) -> Result<Vec<Rucksack>, ParseError> {
+        parse_lines(input, Rucksack::parse)
+    }
+
+    fn part_one(rucksacks: &Vec<Rucksack>) -> Answer {
+        let total = rucksacks
+            .iter()
+            .fold(0, |acc, rucksack| {
+                acc + get_priority(
+                    get_item_type_in_both(
+                        rucksack.compartment_one(),
+                        rucksack.compartment_two(),
+                    ).unwrap()
+                )
+            });
+
+        total.into()
+    }
+
+    fn part_two(rucksacks: &Vec<Rucksack>) -> Answer {
+        let total = rucksacks
+            .chunks(3)
+            .fold(0, |acc, g| {
+                acc + get_priority(
+                    get_item_type_in_all(
+                        &g[0].item_types,
+                        &g[1].item_types,
+                        &g[2].item_types,
+                    ).unwrap(),
+                )
+            });
+
+        total.into()
+    }
+}
+
+// A group of three elves per `size`. Each rucksack only has the one item type
+// in both compartments, and each group only the one badge, which the solution
+// relies on.
+impl Generated for Day03 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        let item_types: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        let mut lines = vec![];
+
+        for _ in 0..std::cmp::max(1, size) {
+            let badge = *rng.pick(&item_types);
+
+            // Every other item type goes in at most two of the three rucksacks
+            let mut allowed: Vec<Vec<char>> = vec![vec![badge]; 3];
+
+            for item in item_types.iter().filter(|i| **i != badge) {
+                let left_out = rng.below(3);
+
+                for (elf, items) in allowed.iter_mut().enumerate() {
+                    if elf != left_out && !rng.one_in(3) {
+                        items.push(*item);
+                    }
+                }
+            }
+
+            for mut items in allowed {
+                rng.shuffle(&mut items);
+
+                // In both compartments, then a split of the rest between them
+                let shared = items.pop().unwrap();
+                let split = rng.below(items.len() + 1);
+                let (left_only, right_only) = items.split_at(split);
+
+                let half = rng.range(2, 16) as usize;
+                let mut fill = |only: &[char]| -> Vec<char> {
+                    let mut half_items = vec![shared];
+                    half_items.extend((1..half).map(|_| if only.is_empty() { shared } else { *rng.pick(only) }));
+                    half_items
+                };
+
+                let mut left = fill(left_only);
+                let mut right = fill(right_only);
+
+                // The badge has to make it in somewhere
+                if shared != badge {
+                    let half = if left_only.contains(&badge) { &mut left } else { &mut right };
+                    half[1] = badge;
+                }
+
+                rng.shuffle(&mut left);
+                rng.shuffle(&mut right);
+                lines.push(left.iter().chain(right.iter()).collect());
+            }
+        }
+
+        lines
+    }
+}
+
+pub fn get_priority(item_type: char) -> u32 {
+    let ascii = item_type as u32;
+
+    match ascii {
+        // A-Z
+        65..=90 => ascii - 64 + 26,
+        // a-z
+        97..=122 => ascii - 96,
+        _ => 0,
+    }
+}
+
+pub fn get_item_type_in_both(left: &str, right: &str) -> Option<char> {
+    let mut left_chars: Vec<char> = left.chars().collect();
+    left_chars.sort();
+    left_chars.dedup();
+
+    let mut right_chars: Vec<char> = right.chars().collect();
+    right_chars.sort();
+    right_chars.dedup();
+
+    for c in left_chars.iter() {
+        if right_chars.contains(c) {
+            return Some(*c)
+        }
+    }
+
+    None
+}
+
+pub fn get_item_type_in_all(one: &str, two: &str, three: &str) -> Option<char> {
+    let mut one_chars: Vec<char> = one.chars().collect();
+    one_chars.sort();
+    one_chars.dedup();
+
+    let mut two_chars: Vec<char> = two.chars().collect();
+    two_chars.sort();
+    two_chars.dedup();
+
+    let mut three_chars: Vec<char> = three.chars().collect();
+    three_chars.sort();
+    three_chars.dedup();
+
+    let mut remaining = one_chars.clone();
+
+    remaining.retain(|i| {
+        two_chars.contains(i) && three_chars.contains(i)
+    });
+
+    remaining.get(0).copied()
+}
+
+pub struct Rucksack {
+    item_types: String
+}
+
+impl Rucksack {
+    pub fn new(input: String) -> Rucksack {
+        Rucksack { item_types: input }
+    }
+
+    // Item types are letters, and both compartments hold as many items
+    pub fn parse(input: &str) -> Result<Rucksack, ParseError> {
+        if let Some(column) = input.chars().position(|c| !c.is_ascii_alphabetic()) {
+            return Err(ParseError::at_char(input, column, "an item type (a-z or A-Z)"));
+        }
+
+        if !input.len().is_multiple_of(2) {
+            return Err(ParseError::in_line(input, input, "an even number of items"));
+        }
+
+        Ok(Rucksack::new(input.to_string()))
+    }
+
+    pub fn compartment_one(&self) -> &str {
+        &self.item_types[0..(self.item_types.len() / 2)]
+    }
+
+    pub fn compartment_two(&self) -> &str {
+        &self.item_types[(self.item_types.len() / 2)..]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Rucksack;
+    use crate::get_priority;
+    use crate::get_item_type_in_both;
+    use crate::get_item_type_in_all;
+
+    #[test]
+    fn rucksack_splits_compartments() {
+        assert_eq!(
+            Rucksack::new(format!("AABB")).compartment_one(),
+            "AA",
+        );
+
+        assert_eq!(
+            Rucksack::new(format!("AABB")).compartment_two(),
+            "BB",
+        );
+    }
+
+    #[test]
+    fn get_priority_reports_correct_values() {
+        assert_eq!(get_priority('a'), 1);
+        assert_eq!(get_priority('b'), 2);
+        assert_eq!(get_priority('y'), 25);
+        assert_eq!(get_priority('z'), 26);
+        assert_eq!(get_priority('A'), 27);
+        assert_eq!(get_priority('B'), 28);
+        assert_eq!(get_priority('Y'), 51);
+        assert_eq!(get_priority('Z'), 52);
+    }
+
+    #[test]
+    fn get_item_type_in_both_correct() {
+        assert_eq!(
+            get_item_type_in_both("vJrwpWtwJgWr", "hcsFMMfFFhFp").unwrap(),
+            'p',
+        );
+
+        assert_eq!(
+            get_item_type_in_both("jqHRNqRjqzjGDLGL", "rsFMfFZSrLrFZsSL").unwrap(),
+            'L',
+        );
+
+        assert_eq!(
+            get_item_type_in_both("PmmdzqPrV", "vPwwTWBwg").unwrap(),
+            'P',
+        );
+    }
+
+    #[test]
+    fn get_item_type_in_all_correct() {
+        assert_eq!(
+            get_item_type_in_all(
+                "vJrwpWtwJgWrhcsFMMfFFhFp",
+                "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+                "PmmdzqPrVvPwwTWBwg",
+            ).unwrap(),
+            'r',
+        );
+
+        assert_eq!(
+            get_item_type_in_all(
+                "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+                "ttgJtRGJQctTZtZT",
+                "CrZsJsPPZsGzwwsLwLmpwMDw",
+            ).unwrap(),
+            'Z',
+        );
+    }
+}
+
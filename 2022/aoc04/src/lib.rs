@@ -0,0 +1,167 @@
+use std::ops::RangeInclusive;
+
+use common::{parse_number, parse_streamed, Answer, Generated, ParseError, Rng, Solution, Streaming};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = PairCounts;
+
+    fn parse(input: &[String]) -> Result<PairCounts, ParseError> {
+        parse_streamed::<Self>(input)
+    }
+
+    fn part_one(counts: &PairCounts) -> Answer {
+        counts.full_containment.into()
+    }
+
+    fn part_two(counts: &PairCounts) -> Answer {
+        counts.overlaps.into()
+    }
+}
+
+// Both parts just count pairs, which can be done as they come in
+impl Streaming for Day04 {
+    fn add_line(counts: &mut PairCounts, line: &str) -> Result<(), ParseError> {
+        let (a, b) = parse_range_pair(line)?;
+
+        if is_range_contained_in_other(a.clone(), b.clone()) {
+            counts.full_containment += 1;
+        }
+
+        if overlaps(a, b) {
+            counts.overlaps += 1;
+        }
+
+        Ok(())
+    }
+}
+
+impl Generated for Day04 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        let mut range = || {
+            let start = rng.range(1, 99);
+            format!("{start}-{}", rng.range(start, std::cmp::min(99, start + 40)))
+        };
+
+        (0..std::cmp::max(1, size * 10))
+            .map(|_| format!("{},{}", range(), range()))
+            .collect()
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct PairCounts {
+    // Pairs where one range fully contains the other
+    pub full_containment: u32,
+    // Pairs that overlap at all
+    pub overlaps: u32,
+}
+
+pub fn parse_range(str_range: &str) -> Result<RangeInclusive<i32>, ParseError> {
+    let (start, end) = str_range
+        .split_once('-')
+        .ok_or_else(|| ParseError::in_line(str_range, str_range, "a range like 2-4"))?;
+
+    Ok(parse_number(str_range, start)?..=parse_number(str_range, end)?)
+}
+
+pub fn parse_range_pair(
+    str_ranges: &str,
+) -> Result<(RangeInclusive<i32>, RangeInclusive<i32>), ParseError> {
+    let (a, b) = str_ranges
+        .split_once(',')
+        .ok_or_else(|| ParseError::end_of_line(str_ranges, "a comma between two ranges"))?;
+
+    Ok((
+        parse_range(a).map_err(|e| e.within(str_ranges, a))?,
+        parse_range(b).map_err(|e| e.within(str_ranges, b))?,
+    ))
+}
+
+pub fn is_range_contained_in_other(
+    range_a: RangeInclusive<i32>,
+    range_b: RangeInclusive<i32>,
+) -> bool {
+    let (a_start, a_end) = (*range_a.start(), *range_a.end());
+    let (b_start, b_end) = (*range_b.start(), *range_b.end());
+
+    a_start >= b_start && a_end <= b_end || b_start >= a_start && b_end <= a_end
+}
+
+pub fn overlaps(range_a: RangeInclusive<i32>, range_b: RangeInclusive<i32>) -> bool {
+    range_a.contains(range_b.start()) || range_a.contains(range_b.end()) ||
+        range_b.contains(range_a.start()) || range_b.contains(range_a.end())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn parse_range_works() {
+        assert_eq!(parse_range("2-4").unwrap(), 2..=4);
+        assert_eq!(parse_range("6-8").unwrap(), 6..=8);
+        assert_eq!(parse_range("2-3").unwrap(), 2..=3);
+        assert_eq!(parse_range("1337-9001").unwrap(), 1337..=9001);
+    }
+
+    #[test]
+    fn parse_range_pair_errors() {
+        let err = parse_range_pair("2-4,6-x").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (6, "x"));
+
+        let err = parse_range_pair("2-4").unwrap_err();
+        assert_eq!(err.expected, "a comma between two ranges");
+    }
+
+    #[test]
+    fn parse_range_pair_works() {
+        assert_eq!(
+            parse_range_pair("2-4,6-8").unwrap(),
+            (2..=4, 6..=8),
+        );
+
+        assert_eq!(
+            parse_range_pair("2-3,4-5").unwrap(),
+            (2..=3, 4..=5),
+        );
+
+        assert_eq!(
+            parse_range_pair("5-7,7-9").unwrap(),
+            (5..=7, 7..=9),
+        );
+
+        assert_eq!(
+            parse_range_pair("1337-9001,6052-8080").unwrap(),
+            (1337..=9001, 6052..=8080),
+        );
+    }
+
+    #[test]
+    fn range_containment_check() {
+        assert!(is_range_contained_in_other(3..=5, 2..=8));
+        assert!(is_range_contained_in_other(2..=8, 3..=5));
+        assert!(is_range_contained_in_other(1..=5, 1..=4));
+        assert!(is_range_contained_in_other(1..=4, 1..=5));
+        assert!(is_range_contained_in_other(1..=5, 3..=5));
+        assert!(is_range_contained_in_other(3..=5, 1..=5));
+        assert!(is_range_contained_in_other(4..=9, 4..=9));
+
+        assert!( ! is_range_contained_in_other(3..=5, 1..=4));
+        assert!( ! is_range_contained_in_other(1..=4, 3..=5));
+        assert!( ! is_range_contained_in_other(1..=4, 6..=8));
+        assert!( ! is_range_contained_in_other(6..=8, 1..=4));
+        assert!( ! is_range_contained_in_other(1..=5, 5..=7));
+        assert!( ! is_range_contained_in_other(5..=7, 1..=5));
+    }
+
+    #[test]
+    fn range_overlap_check() {
+        assert!(overlaps(5..=7, 7..=9));
+        assert!(overlaps(7..=9, 5..=7));
+        assert!(overlaps(2..=8, 3..=7));
+        assert!(overlaps(3..=7, 2..=8));
+        assert!(! overlaps(3..=5, 6..=9));
+    }
+}
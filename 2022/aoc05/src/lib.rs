@@ -0,0 +1,351 @@
+use common::{parse_number, Answer, Generated, ParseError, Rng, Solution};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = (StackMachine, Vec<MoveInstruction>);
+
+    fn parse(input: &[String]) -> Result<Self::Input, ParseError> {
+        let stacks = parse_stacks(input)?;
+        let instructions = parse_instructions(input, stacks.len())?;
+
+        Ok((StackMachine::new(stacks), instructions))
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        let (machine, instructions) = input;
+        let mut machine = machine.clone();
+
+        for inst in instructions {
+            machine.execute_move_9000(inst);
+        }
+
+        machine.get_top_crates().iter().collect::<String>().into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        let (machine, instructions) = input;
+        let mut machine = machine.clone();
+
+        for inst in instructions {
+            machine.execute_move_9001(inst);
+        }
+
+        machine.get_top_crates().iter().collect::<String>().into()
+    }
+}
+
+// Up to nine stacks, as the stack numbers are only ever one digit, then moves
+// for every bit of `size`. No move takes a stack's last crate, so there's
+// always a crate on top of each one at the end.
+impl Generated for Day05 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        let stack_count = rng.range(3, 9) as usize;
+        let mut heights: Vec<usize> = (0..stack_count).map(|_| rng.range(1, 8) as usize).collect();
+
+        // At least one crate has to be free to move
+        heights[0] = std::cmp::max(heights[0], 2);
+
+        let tallest = *heights.iter().max().unwrap();
+        let mut lines = vec![];
+
+        for level in (0..tallest).rev() {
+            let row: Vec<String> = heights
+                .iter()
+                .map(|h| if *h > level { format!("[{}]", (b'A' + rng.below(26) as u8) as char) } else { "   ".to_string() })
+                .collect();
+
+            lines.push(row.join(" "));
+        }
+
+        lines.push((1..=stack_count).map(|n| format!(" {n} ")).collect::<Vec<String>>().join(" "));
+        lines.push(String::new());
+
+        for _ in 0..std::cmp::max(1, size * 5) {
+            let movable: Vec<usize> = (0..stack_count).filter(|s| heights[*s] > 1).collect();
+            let origin = *rng.pick(&movable);
+            let target = (origin + 1 + rng.below(stack_count - 1)) % stack_count;
+            let amount = rng.range(1, heights[origin] as i64 - 1) as usize;
+
+            heights[origin] -= amount;
+            heights[target] += amount;
+            lines.push(format!("move {amount} from {} to {}", origin + 1, target + 1));
+        }
+
+        lines
+    }
+}
+
+fn get_stack_count(raw_stacks: &Vec<String>) -> usize {
+    raw_stacks.last().unwrap().chars().filter(|c| *c != ' ').count()
+}
+
+fn get_items_at_level(level: &String, stack_count: usize) -> Result<Vec<Option<char>>, ParseError> {
+    (1..(stack_count * 4 - 1)).step_by(4).map(|i| {
+        let c = level.chars().nth(i);
+
+        match c {
+            None => Ok(None),
+            Some(' ') => Ok(None),
+            Some(c) if c.is_ascii_uppercase() => Ok(Some(c)),
+            _ => Err(ParseError::at_char(level, i, "a crate letter or a gap")),
+        }
+    }).collect()
+}
+
+pub fn parse_stacks(input: &[String]) -> Result<Vec<Vec<char>>, ParseError> {
+    let relevant_input: Vec<String> = input
+        .iter()
+        .take_while(|l| !l.trim().is_empty())
+        .map(|s| s.to_owned())
+        .collect();
+
+    // Need at least the row of stack numbers at the bottom
+    if relevant_input.is_empty() {
+        let line = input.first().map(|l| l.as_str()).unwrap_or("");
+        return Err(ParseError::in_line(line, line, "a drawing of the crate stacks"));
+    }
+
+    let stack_count = get_stack_count(&relevant_input);
+
+    let mut stacks: Vec<Vec<char>> = Vec::with_capacity(stack_count);
+
+    for _ in 0..stack_count {
+        stacks.push(Vec::new());
+    }
+
+    for (y, level) in relevant_input[..relevant_input.len() - 1].iter().enumerate().rev() {
+        let items = get_items_at_level(level, stack_count).map_err(|e| e.on_line(y))?;
+        for (i, item) in items.iter().enumerate() {
+            if let Some(c) = item {
+                stacks[i].push(*c);
+            }
+        }
+    }
+
+    Ok(stacks)
+}
+
+pub fn parse_instructions(input: &[String], stack_count: usize) -> Result<Vec<MoveInstruction>, ParseError> {
+    input
+        .iter()
+        .enumerate()
+        .skip_while(|(_, l)| !l.trim().is_empty())
+        .skip_while(|(_, l)| l.trim().is_empty())
+        .map(|(y, l)| {
+            let instruction = MoveInstruction::new(l).map_err(|e| e.on_line(y))?;
+
+            // Both stack numbers need to be in the drawing
+            for (word, stack) in [(3, instruction.origin), (5, instruction.target)] {
+                if stack == 0 || stack > stack_count {
+                    let token = l.split(' ').nth(word).unwrap();
+                    let expected = format!("a stack number from 1 to {stack_count}");
+                    return Err(ParseError::in_line(l, token, expected).on_line(y));
+                }
+            }
+
+            Ok(instruction)
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+pub struct MoveInstruction {
+    amount: usize,
+    origin: usize,
+    target: usize,
+}
+
+impl MoveInstruction {
+    // Reads "move <amount> from <origin> to <target>"
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let split: Vec<&str> = input.split(' ').collect();
+
+        for (i, word) in [(0, "move"), (2, "from"), (4, "to")] {
+            match split.get(i) {
+                Some(w) if *w == word => {}
+                Some(w) => return Err(ParseError::in_line(input, w, format!("`{word}`"))),
+                None => return Err(ParseError::end_of_line(input, format!("`{word}`"))),
+            }
+        }
+
+        let number = |i: usize| match split.get(i) {
+            Some(token) => parse_number(input, token),
+            None => Err(ParseError::end_of_line(input, "a number")),
+        };
+
+        let amount = number(1)?;
+        let origin = number(3)?;
+        let target = number(5)?;
+
+        if let Some(extra) = split.get(6) {
+            return Err(ParseError::in_line(input, extra, "the end of the line"));
+        }
+
+        Ok(Self { amount, origin, target })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StackMachine {
+    stacks: Vec<Vec<char>>,
+}
+
+impl StackMachine {
+    pub fn new(stacks: Vec<Vec<char>>) -> StackMachine {
+        StackMachine { stacks }
+    }
+
+    pub fn move_one_crate(&mut self, origin: usize, target: usize) {
+        let c = self.stacks[origin - 1].pop().unwrap();
+        self.stacks[target - 1].push(c);
+    }
+
+    // Part one
+    pub fn execute_move_9000(&mut self, instruction: &MoveInstruction) {
+        for _ in 0..instruction.amount {
+            self.move_one_crate(instruction.origin, instruction.target);
+        }
+    }
+
+    // Part two
+    pub fn execute_move_9001(&mut self, instruction: &MoveInstruction) {
+        // split_off, append
+        let origin_len = self.stacks[instruction.origin - 1].len();
+        let split_index = origin_len - instruction.amount;
+
+        let mut sub_stack = self.stacks[instruction.origin - 1].split_off(split_index);
+        self.stacks[instruction.target - 1].append(&mut sub_stack);
+    }
+
+    pub fn get_top_crates(&self) -> Vec<char> {
+        self.stacks.iter().map(|s| *s.last().unwrap()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn counts_stacks_correctly() {
+        let input = vec![
+            "[D]        ".to_string(),
+            "[N] [C]    ".to_string(),
+            "[Z] [M] [P]".to_string(),
+            " 1   2   3 ".to_string(),
+        ];
+
+        assert_eq!(get_stack_count(&input), 3);
+
+
+        let input = vec![
+            "    [G] [R]                 [P]    ".to_string(),
+            "    [H] [W]     [T] [P]     [H]    ".to_string(),
+            "    [F] [T] [P] [B] [D]     [N]    ".to_string(),
+            "[L] [T] [M] [Q] [L] [C]     [Z]    ".to_string(),
+            "[G] [L] [F] [D] [M] [V] [T] [J] [H]".to_string(),
+            " 1   2   3   4   5   6   7   8   9 ".to_string(),
+        ];
+
+        assert_eq!(get_stack_count(&input), 9);
+    }
+
+    #[test]
+    fn get_items_at_level_works() {
+        let input = format!("[N] [C]    ");
+        assert_eq!(get_items_at_level(&input, 3).unwrap(), vec![Some('N'), Some('C'), None]);
+
+        let input = format!("    [C]    ");
+        assert_eq!(get_items_at_level(&input, 4).unwrap(), vec![None, Some('C'), None, None]);
+    }
+
+    #[test]
+    fn parse_stacks_works_correctly() {
+        let input = vec![
+            "[D]        ".to_string(),
+            "[N] [C]    ".to_string(),
+            "[Z] [M] [P]".to_string(),
+            " 1   2   3 ".to_string(),
+        ];
+
+        assert_eq!(
+            parse_stacks(&input).unwrap(),
+            vec![
+                vec!['Z', 'N', 'D'],
+                vec!['M', 'C'],
+                vec!['P'],
+            ],
+        );
+    }
+
+    #[test]
+    fn build_move_instruction() {
+        assert_eq!(
+            MoveInstruction::new("move 1 from 2 to 1").unwrap(),
+            MoveInstruction { amount: 1, origin: 2, target: 1 },
+        );
+        assert_eq!(
+            MoveInstruction::new("move 3 from 21 to 7").unwrap(),
+            MoveInstruction { amount: 3, origin: 21, target: 7 },
+        );
+    }
+
+    #[test]
+    fn reports_bad_input() {
+        let err = MoveInstruction::new("move 3 from x to 7").unwrap_err();
+        assert_eq!((err.column, err.text.as_str(), err.expected.as_str()), (12, "x", "a number"));
+
+        let err = MoveInstruction::new("move 3 to 7").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (7, "to"));
+
+        let input = vec![
+            "[D]    ".to_string(),
+            "[N] [C]".to_string(),
+            " 1   2 ".to_string(),
+            "".to_string(),
+            "move 1 from 2 to 1".to_string(),
+            "move 1 from 1 to 3".to_string(),
+        ];
+
+        let err = Day05::parse(&input).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (5, 17, "3"));
+
+        let err = parse_stacks(&["[D] [c]".to_string(), " 1   2 ".to_string()]).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (0, 5, "c"));
+    }
+
+    #[test]
+    fn stack_machine_moves_crates_9000() {
+        let stacks = vec![
+            vec!['Z', 'N', 'D'],
+            vec!['M', 'C'],
+            vec!['P'],
+        ];
+
+        let mut machine = StackMachine::new(stacks);
+
+        let instruction = MoveInstruction { amount: 2, origin: 1, target: 3 };
+
+        machine.execute_move_9000(&instruction);
+
+        assert_eq!(machine.get_top_crates(), vec!['Z', 'C', 'N']);
+    }
+
+    #[test]
+    fn stack_machine_moves_crates_9001() {
+        let stacks = vec![
+            vec!['Z', 'N', 'D'],
+            vec!['M', 'C'],
+            vec!['P'],
+        ];
+
+        let mut machine = StackMachine::new(stacks);
+
+        let instruction = MoveInstruction { amount: 2, origin: 1, target: 3 };
+
+        machine.execute_move_9001(&instruction);
+
+        assert_eq!(machine.get_top_crates(), vec!['Z', 'C', 'D']);
+    }
+}
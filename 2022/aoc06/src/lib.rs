@@ -0,0 +1,110 @@
+use common::{Answer, Generated, ParseError, Rng, Solution};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    // Datastream buffer
+    type Input = String;
+
+    fn parse(input: &[String]) -> Result<String, ParseError> {
+        let buffer = input.first().map(|l| l.as_str()).unwrap_or("");
+
+        if buffer.is_empty() {
+            return Err(ParseError::end_of_line(buffer, "a datastream buffer"));
+        }
+
+        // Markers are told apart by comparing chars, so anything goes as long
+        // as it isn't whitespace sneaking in
+        match buffer.chars().position(|c| !c.is_ascii_graphic()) {
+            Some(column) => Err(ParseError::at_char(buffer, column, "a datastream character")),
+            None => Ok(buffer.to_string()),
+        }
+    }
+
+    fn part_one(buffer: &String) -> Answer {
+        locate_start_of_packet(buffer, 4).into()
+    }
+
+    fn part_two(buffer: &String) -> Answer {
+        // Lucky guess lol
+        locate_start_of_packet(buffer, 14).into()
+    }
+}
+
+// Drawn from only three letters at first, so there's no marker of either kind,
+// then from thirteen, so there's a start-of-packet marker but no
+// start-of-message one, then a message marker and anything after that
+impl Generated for Day06 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        let letters: Vec<char> = ('a'..='z').collect();
+        let stretch = std::cmp::max(1, size * 10);
+        let mut buffer = String::new();
+
+        buffer.extend((0..stretch).map(|_| *rng.pick(&letters[..3])));
+        buffer.extend((0..stretch).map(|_| *rng.pick(&letters[..13])));
+
+        let mut marker = letters.clone();
+        rng.shuffle(&mut marker);
+        buffer.extend(&marker[..14]);
+
+        buffer.extend((0..stretch * 2).map(|_| *rng.pick(&letters)));
+
+        vec![buffer]
+    }
+}
+
+pub fn locate_start_of_packet(input: &str, marker_length: usize) -> usize {
+    for start in 0..input.chars().count() {
+        let slice = &input[start..(start + marker_length)];
+        let mut chars: Vec<char> = slice.chars().collect();
+        chars.sort();
+        chars.dedup();
+        if chars.len() == marker_length {
+            return start + marker_length
+        }
+    }
+
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn locates_start_of_packet() {
+        assert_eq!(
+            locate_start_of_packet(
+                "bvwbjplbgvbhsrlpgdmjqwftvncz",
+                4,
+            ),
+            5,
+        );
+
+        assert_eq!(
+            locate_start_of_packet(
+                "nppdvjthqldpwncqszvftbrmjlhg",
+                4,
+            ),
+            6,
+        );
+
+
+        assert_eq!(
+            locate_start_of_packet(
+                "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
+                4,
+            ),
+            10,
+        );
+
+        assert_eq!(
+            locate_start_of_packet(
+                "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw",
+                4,
+            ),
+            11,
+        );
+
+    }
+}
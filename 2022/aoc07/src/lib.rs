@@ -0,0 +1,407 @@
+use common::{Answer, Generated, NumberParam, Param, ParseError, Params, Rng, Solution};
+
+pub struct Day07;
+
+const DISK_SIZE: NumberParam = NumberParam::new("disk_size", 70000000, "How big the whole disk is").at_least(0);
+const NEEDED: NumberParam = NumberParam::new("needed", 30000000, "How much free space the update needs, for part two").at_least(0);
+
+impl Solution for Day07 {
+    type Input = Disk;
+
+    const PARAMS: &'static [Param] = &[DISK_SIZE.param(), NEEDED.param()];
+
+    fn parse(input: &[String]) -> Result<Disk, ParseError> {
+        Self::parse_with(input, &Params::new())
+    }
+
+    fn parse_with(input: &[String], params: &Params) -> Result<Disk, ParseError> {
+        let mut parser = Parser::new();
+        parser.parse(input)?;
+
+        Ok(Disk {
+            nodes: parser.nodes,
+            size: params.number(DISK_SIZE) as u64,
+            needed: params.number(NEEDED) as u64,
+        })
+    }
+
+    fn part_one(disk: &Disk) -> Answer {
+        let nodes = &disk.nodes;
+
+        // Goal: find all dirs with total size of _at most_ 100 000, sum their
+        // sizes
+        // important: nesting does not matter for this so a directory and its
+        // ancestor can individually be counted as this (which seems counterintuitive)
+        let dirs: Vec<&Node> = nodes.iter().filter(|n| n.size.is_none()).collect();
+
+        let filtered_dir_sizes = dirs.iter().filter_map(|d| {
+            let size = dir_size(nodes, d);
+
+            if size <= 100000 {
+                Some(size)
+            } else {
+                None
+            }
+        });
+
+        let total_size: u64 = filtered_dir_sizes.sum();
+
+        total_size.into()
+    }
+
+    fn part_two(disk: &Disk) -> Answer {
+        let nodes = &disk.nodes;
+        let total_size: u64 = nodes.iter().filter_map(|n| n.size).sum();
+
+        let need_to_free = (disk.needed + total_size).saturating_sub(disk.size);
+
+        let mut big_enough_sizes: Vec<u64> = nodes
+            .iter()
+            .filter(|n| n.size.is_none())
+            .map(|d| dir_size(nodes, d))
+            .filter(|size| *size >= need_to_free)
+            .collect();
+
+        big_enough_sizes.sort();
+
+        big_enough_sizes[0].into()
+    }
+}
+
+// A couple of directories for every bit of `size`, in a random tree. Part two
+// never counts the root as a directory to delete, so one of the top level
+// directories gets a file big enough to free up the space on its own.
+impl Generated for Day07 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        let dir_count = std::cmp::max(1, size * 2);
+        let total = rng.range(45_000_000, 65_000_000) as u64;
+
+        // (name, parent) for each directory under the root, which is 0
+        let mut dirs: Vec<(String, usize)> = vec![("/".to_string(), 0)];
+        // (name, size, directory) for each file
+        let mut files: Vec<(String, u64, usize)> = vec![];
+        // Names taken in each directory
+        let mut names: Vec<Vec<String>> = vec![vec![]; dir_count + 1];
+
+        let mut new_name = |rng: &mut Rng, dir: usize, extension: bool| {
+            loop {
+                let length = rng.range(1, 8) as usize;
+                let mut name: String = (0..length).map(|_| (b'a' + rng.below(26) as u8) as char).collect();
+
+                if extension && rng.one_in(2) {
+                    name += *rng.pick(&[".txt", ".dat", ".log", ".lst", ".ext"]);
+                }
+
+                if !names[dir].contains(&name) {
+                    names[dir].push(name.clone());
+                    return name;
+                }
+            }
+        };
+
+        for i in 1..=dir_count {
+            // The first one's always at the top, to hold the big file
+            let parent = if i == 1 { 0 } else { rng.below(i) };
+            let name = new_name(rng, parent, false);
+
+            dirs.push((name, parent));
+        }
+
+        for dir in 0..dirs.len() {
+            for _ in 0..rng.range(0, 4) {
+                let name = new_name(rng, dir, true);
+                files.push((name, rng.range(1000, 350_000) as u64, dir));
+            }
+        }
+
+        // The other files stay under half the total
+        let sum: u64 = files.iter().map(|(_, size, _)| size).sum();
+
+        if sum > total / 2 {
+            files.iter_mut().for_each(|(_, size, _)| *size = std::cmp::max(1, *size * (total / 2) / sum));
+        }
+
+        let sum: u64 = files.iter().map(|(_, size, _)| size).sum();
+        let big_name = new_name(rng, 1, true);
+        files.push((big_name, total - sum, 1));
+
+        let mut lines = vec!["$ cd /".to_string()];
+        list_dir(&dirs, &files, 0, &mut lines);
+
+        lines
+    }
+}
+
+// Terminal output for looking through directory `dir` and everything under it
+fn list_dir(dirs: &[(String, usize)], files: &[(String, u64, usize)], dir: usize, lines: &mut Vec<String>) {
+    let subdirs: Vec<usize> = (1..dirs.len()).filter(|d| dirs[*d].1 == dir).collect();
+
+    lines.push("$ ls".to_string());
+    lines.extend(subdirs.iter().map(|d| format!("dir {}", dirs[*d].0)));
+    lines.extend(files.iter().filter(|f| f.2 == dir).map(|(name, size, _)| format!("{size} {name}")));
+
+    for d in subdirs {
+        lines.push(format!("$ cd {}", dirs[d].0));
+        list_dir(dirs, files, d, lines);
+        lines.push("$ cd ..".to_string());
+    }
+}
+
+// Total size of all files anywhere under directory `dir`
+fn dir_size(nodes: &[Node], dir: &Node) -> u64 {
+    nodes
+        .iter()
+        .filter(|n| n.path.starts_with(&dir.path))
+        .filter_map(|f| f.size)
+        .sum()
+}
+
+// Everything on the disk, with how big it is and how much space the update
+// needs
+pub struct Disk {
+    nodes: Vec<Node>,
+    size: u64,
+    needed: u64,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Node {
+    path: String,
+    size: Option<u64>,
+}
+
+impl Node {
+    pub fn parse(input: &str, cur_dir: &str) -> Option<Node> {
+        let split: Vec<&str> = input.split(' ').collect();
+
+        let first_token = *split.get(0)?;
+        let second_token = *split.get(1)?;
+
+        match first_token {
+            "dir" => Some(
+                Node {
+                    path: format!("{cur_dir}{second_token}/"),
+                    size: None,
+                }
+            ),
+
+            "$" => None,
+
+            _ => match u64::from_str_radix(first_token, 10) {
+                Ok(size) => Some(
+                    Node {
+                        path: format!("{cur_dir}{second_token}"),
+                        size: Some(size),
+                    }
+                ),
+
+                _ => None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    ChangeDirRoot,
+    ChangeDirUp,
+    ChangeDirNamed(String),
+    List,
+}
+
+impl Command {
+    /// Parses a command from a line starting with `$`.
+    /// Returns None if the line does not start with '$' or some other unexpected
+    /// input is given.
+    pub fn parse(input: &str) -> Option<Command> {
+        let split: Vec<&str> = input.split(' ').collect();
+
+        if *split.get(0)? != "$" {
+            return None;
+        }
+
+        let cmd = *split.get(1)?;
+
+        if cmd == "ls" {
+            return Some(Self::List);
+        }
+
+
+        if cmd == "cd" {
+            let target = *split.get(2)?;
+
+            return match target {
+                "/" => Some(Self::ChangeDirRoot),
+                ".." => Some(Self::ChangeDirUp),
+                _ => Some(Self::ChangeDirNamed(target.to_string()))
+            };
+        }
+
+        None
+    }
+}
+
+#[derive(Debug)]
+pub struct Parser {
+    expect_node: bool,
+    cur_dir: String,
+    nodes: Vec<Node>,
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parser {
+    pub fn new() -> Parser {
+        Parser {
+            expect_node: false,
+            cur_dir: "/".to_string(),
+            nodes: vec![],
+        }
+    }
+
+    fn get_parent(current: &str) -> String {
+        if current == "/" {
+            return current.to_string();
+        }
+
+        let current_split: Vec<&str> = current.split("/").collect();
+        if current_split.len() <= 3 {
+            return "/".to_string();
+        }
+
+        format!("{}/", current_split[..current_split.len()-2].join("/"))
+    }
+
+    fn execute_command(&mut self, command: Command) {
+        match command {
+            Command::ChangeDirRoot => {
+                self.cur_dir = "/".to_string();
+                self.expect_node = false;
+            }
+
+            Command::ChangeDirUp => {
+                self.cur_dir = Self::get_parent(&self.cur_dir);
+                self.expect_node = false;
+            }
+
+            Command::ChangeDirNamed(name) => {
+                self.cur_dir = format!("{}{}/", self.cur_dir, name);
+                self.expect_node = false;
+            }
+
+            Command::List => {
+                self.expect_node = true;
+            }
+        }
+    }
+
+    fn parse_line(&mut self, line: &str) -> Result<(), ParseError> {
+        if self.expect_node {
+            let node = Node::parse(line, &self.cur_dir);
+
+            if let Some(node) = node {
+                self.nodes.push(node);
+                return Ok(());
+            }
+        }
+
+        let command = Command::parse(line);
+
+        match command {
+            Some(command) => {
+                self.execute_command(command);
+                Ok(())
+            }
+
+            None => {
+                let expected = if self.expect_node {
+                    "a command, `dir <name>` or `<size> <name>`"
+                } else {
+                    "a command, `$ cd <dir>` or `$ ls`"
+                };
+
+                // Point at what comes after the prompt, if there is one
+                let token = line.strip_prefix("$ ").unwrap_or(line);
+                Err(ParseError::in_line(line, token, expected))
+            }
+        }
+    }
+
+    pub fn parse(&mut self, input: &[String]) -> Result<&Vec<Node>, ParseError> {
+        // Lines formats to expect:
+        // 1. Lines starting with '$':
+        //    a. `$ ls`: directory listing follows
+        //    b. `cd (target)`: change to other directory
+        //        allowed targets:
+        //        - `/`: Go to root directory
+        //        - `..`: Go one directory up
+        //        - <some name>: go one directory deeper, to named directory
+        // 2. Directory listing lines:
+        //    a. `dir <name>`: directory that may contain more stuff
+        //    b. `<decimal digits> <filename>`: File preceded by its size
+        //
+
+        for (i, line) in input.iter().enumerate() {
+            self.parse_line(line).map_err(|e| e.on_line(i))?;
+        }
+
+        self.nodes.sort();
+        self.nodes.dedup();
+
+        Ok(&self.nodes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn parse_command_works() {
+        assert_eq!(Command::parse("invalid"), None);
+        assert_eq!(Command::parse("$ ls"), Some(Command::List));
+        assert_eq!(Command::parse("$ cd /"), Some(Command::ChangeDirRoot));
+        assert_eq!(Command::parse("$ cd .."), Some(Command::ChangeDirUp));
+        assert_eq!(Command::parse("$ cd foo"), Some(Command::ChangeDirNamed("foo".to_string())));
+    }
+
+    #[test]
+    fn parse_node_works() {
+        assert_eq!(Node::parse("lkdjfgkljdf fsjkh", "/"), None);
+        assert_eq!(Node::parse("$ ls", "/"), None);
+        assert_eq!(Node::parse("dir foobar.roo", "/"), Some(Node { path: "/foobar.roo/".to_string(), size: None } ));
+        assert_eq!(
+            Node::parse("123 roo.foo", "/"),
+            Some(
+                Node { path: "/roo.foo".to_string(), size: Some(123) }
+            ),
+        );
+    }
+
+    #[test]
+    fn parser_reports_bad_lines() {
+        let input = vec![
+            "$ cd /".to_string(),
+            "$ ls".to_string(),
+            "dir a".to_string(),
+            "$ cd".to_string(),
+        ];
+
+        let err = Parser::new().parse(&input).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 2, "cd"));
+
+        let err = Parser::new().parse(&["$ rm -rf /".to_string()]).unwrap_err();
+        assert_eq!(err.expected, "a command, `$ cd <dir>` or `$ ls`");
+    }
+
+    #[test]
+    fn test_get_parent() {
+        assert_eq!(Parser::get_parent("/"), "/".to_string());
+        assert_eq!(Parser::get_parent("/foo/"), "/".to_string());
+        assert_eq!(Parser::get_parent("/foo/bar/"), "/foo/".to_string());
+    }
+}
@@ -0,0 +1,361 @@
+use common::{Answer, Generated, Grid, ParseError, Rng, Solution};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = TreeField;
+
+    fn parse(input: &[String]) -> Result<TreeField, ParseError> {
+        TreeField::from_text(input)
+    }
+
+    fn part_one(tree_field: &TreeField) -> Answer {
+        let mut tree_field = tree_field.clone();
+        tree_field.scan_cover();
+
+        let visible_count = tree_field.trees.rows().fold(0, |acc, row| {
+            acc + row.iter().fold(0, |r_acc, tree| {
+                r_acc + match tree.is_visible() {
+                    true => 1,
+                    false => 0
+                }
+            })
+        });
+
+        Answer::from(visible_count)
+    }
+
+    fn part_two(tree_field: &TreeField) -> Answer {
+        let mut tree_field = tree_field.clone();
+        tree_field.scan_visibility();
+
+        let highest_vis = tree_field.trees.rows().fold(0, |row_best, row| {
+            row.iter().fold(row_best, |best, tree| {
+                let score = tree.scenic_score();
+
+                if score > best {
+                    score
+                } else {
+                    best
+                }
+            })
+        });
+
+        Answer::from(highest_vis)
+    }
+}
+
+// A square forest `size` trees across
+impl Generated for Day08 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        let side = std::cmp::max(2, size);
+
+        (0..side)
+            .map(|_| (0..side).map(|_| (b'0' + rng.below(10) as u8) as char).collect())
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Tree {
+    height: i8,
+    cover_north: Option<i8>,
+    cover_east: Option<i8>,
+    cover_south: Option<i8>,
+    cover_west: Option<i8>,
+    view_north: i64,
+    view_east: i64,
+    view_south: i64,
+    view_west: i64,
+}
+
+impl Tree {
+    pub fn new(height: i8) -> Tree {
+        Tree {
+            height,
+            cover_north: None,
+            cover_east: None,
+            cover_south: None,
+            cover_west: None,
+            view_north: 0,
+            view_east: 0,
+            view_south: 0,
+            view_west: 0,
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        let (n, e, s, w) = (
+            self.cover_north.unwrap_or(0),
+            self.cover_east.unwrap_or(0),
+            self.cover_south.unwrap_or(0),
+            self.cover_west.unwrap_or(0),
+        );
+
+        let h = self.height;
+
+        n < h || e < h || s < h || w < h
+    }
+
+    pub fn scenic_score(&self) -> i64 {
+        let (n, e, s, w) = (
+            self.view_north,
+            self.view_east,
+            self.view_south,
+            self.view_west,
+        );
+
+        n * e * s * w
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TreeField {
+    trees: Grid<Tree>
+}
+
+impl TreeField {
+    pub fn from_text(input: &[String]) -> Result<TreeField, ParseError> {
+        let trees = Grid::try_parse(input, "a tree height (0-9)", |t| {
+            t.to_digit(10).map(|h| Tree::new(h as i8))
+        })?;
+
+        Ok(TreeField { trees })
+    }
+
+    pub fn scan_cover(&mut self) {
+        let (w, h) = (self.trees.width(), self.trees.height());
+
+        for y in 0..h {
+            self.scan_cover_line((0..w).map(|x| (x, y)), |t, cover| t.cover_west = Some(cover));
+            self.scan_cover_line((0..w).rev().map(|x| (x, y)), |t, cover| t.cover_east = Some(cover));
+        }
+
+        for x in 0..w {
+            self.scan_cover_line((0..h).map(|y| (x, y)), |t, cover| t.cover_north = Some(cover));
+            self.scan_cover_line((0..h).rev().map(|y| (x, y)), |t, cover| t.cover_south = Some(cover));
+        }
+    }
+
+    /// Cover scanning:
+    ///
+    /// The idea here is that we keep track of the highest tree we've
+    /// seen so far, in the direction we're scanning. As we come across each
+    /// tree, we store the height of the highest tree we've seen before
+    /// encountering the current tree.
+    ///
+    /// If the current tree is taller than the tallest one we've seen so far,
+    /// we make note of the new tallest height.
+    ///
+    /// Then, we can evaluate whether the tree is visible from that direction
+    /// by checking if it's taller than the tallest tree found on that side.
+    ///
+    /// `line` walks in from the edge the cover is measured from, and
+    /// `set_cover` stores the result on the matching side of the tree.
+    ///
+    /// This approach means we only need to iterate over any one row/column
+    /// once for a given directional scan. I don't know how you'd express that
+    /// in big O notation because I never took computer science, but it's got
+    /// to count for something.
+    fn scan_cover_line<L, F>(&mut self, line: L, mut set_cover: F)
+    where
+        L: Iterator<Item = (usize, usize)>,
+        F: FnMut(&mut Tree, i8),
+    {
+        let mut cover = -1;
+
+        for loc in line {
+            let tree = &mut self.trees[loc];
+            set_cover(tree, cover);
+
+            if tree.height > cover {
+                cover = tree.height;
+            }
+        }
+    }
+
+    pub fn scan_visibility(&mut self) {
+        let (w, h) = (self.trees.width(), self.trees.height());
+
+        for y in 0..h {
+            self.scan_visibility_line((0..w).map(|x| (x, y)), |t, view| t.view_west = view);
+            self.scan_visibility_line((0..w).rev().map(|x| (x, y)), |t, view| t.view_east = view);
+        }
+
+        for x in 0..w {
+            self.scan_visibility_line((0..h).map(|y| (x, y)), |t, view| t.view_north = view);
+            self.scan_visibility_line((0..h).rev().map(|y| (x, y)), |t, view| t.view_south = view);
+        }
+    }
+
+    /// Visibility scanning:
+    ///
+    /// Somewhat similar to cover scanning, this is done separately for each
+    /// direction.
+    ///
+    /// Since we have only 10 total possible tree heights, we can store how
+    /// far you can see from each height.
+    ///
+    /// As we move in from one side, we assign the visibility + 1 from the
+    /// height of the current tree, to that tree's view in this direction.
+    ///
+    /// Then we update visibility at each height:
+    /// - At heights up to the current tree, the visibility is reset to 0,
+    ///   since the current tree blocks the view.
+    /// - At heights higher than the current tree, we increment number of
+    ///   visible trees to include the current tree.
+    ///
+    /// This approach means we only need to iterate over any one row/column
+    /// once for a given directional scan. I don't know how you'd express that
+    /// in big O notation because I never took computer science, but it's got
+    /// to count for something.
+    fn scan_visibility_line<L, F>(&mut self, line: L, mut set_view: F)
+    where
+        L: Iterator<Item = (usize, usize)>,
+        F: FnMut(&mut Tree, i64),
+    {
+        // Current distance visible from each height, height set by
+        // index in vis_h. Distance is # of trees
+        let mut vis_h = [ -1, -1, -1, -1, -1, -1, -1, -1, -1, -1 ];
+
+        for loc in line {
+            let tree = &mut self.trees[loc];
+            set_view(tree, vis_h[tree.height as usize] + 1);
+
+            // Mark new visibility at height
+            (0..=(tree.height)).for_each(|h| vis_h[h as usize] = 0);
+            ((tree.height+1)..=9).for_each(|h| vis_h[h as usize] += 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_new_tree() {
+        assert_eq!(
+            Tree::new(3),
+            Tree {
+                height: 3,
+                cover_north: None,
+                cover_east: None,
+                cover_south: None,
+                cover_west: None,
+                view_north: 0,
+                view_east: 0,
+                view_south: 0,
+                view_west: 0,
+
+            },
+        );
+    }
+
+    #[test]
+    fn test_parsing_tree_field() {
+        let input = vec![
+            "123".to_string(),
+            "456".to_string(),
+            "789".to_string(),
+        ];
+
+        assert_eq!(
+            TreeField::from_text(&input).unwrap(),
+            TreeField {
+                trees: Grid::from_vec(3, vec![
+                    Tree::new(1), Tree::new(2), Tree::new(3),
+                    Tree::new(4), Tree::new(5), Tree::new(6),
+                    Tree::new(7), Tree::new(8), Tree::new(9),
+                ]),
+            }
+        );
+    }
+
+    #[test]
+    fn test_scanning_cover() {
+        let input = vec![
+            "30373".to_string(),
+            "25512".to_string(),
+            "65332".to_string(),
+            "33549".to_string(),
+            "35390".to_string(),
+        ];
+
+        let mut field = TreeField::from_text(&input).unwrap();
+        field.scan_cover();
+
+        // The top-left 5 is visible from the left and top. (It isn't visible from the right or
+        // bottom since other trees of height 5 are in the way.)
+        assert!(field.trees[(1, 1)].is_visible());
+
+        // The top-middle 5 is visible from the top and right.
+        assert!(field.trees[(2, 1)].is_visible());
+
+        // The top-right 1 is not visible from any direction; for it to be visible, there would
+        // need to only be trees of height 0 between it and an edge.
+        assert!( ! field.trees[(3, 1)].is_visible());
+
+        // The left-middle 5 is visible, but only from the right.
+        assert!(field.trees[(1, 2)].is_visible());
+
+        // The center 3 is not visible from any direction; for it to be visible, there would need
+        // to be only trees of at most height 2 between it and an edge.
+        assert!( ! field.trees[(2, 2)].is_visible());
+
+        // The right-middle 3 is visible from the right.
+        assert!(field.trees[(3, 2)].is_visible());
+
+        // In the bottom row, the middle 5 is visible, but the 3 and 4 are not.
+        assert!(field.trees[(2, 3)].is_visible());
+
+        // Check all the outside ones are visible - north edge
+        for tree in field.trees.row(0).iter() {
+            assert!(tree.is_visible());
+        }
+
+        // south edge
+        for tree in field.trees.row(4).iter() {
+            assert!(tree.is_visible());
+        }
+
+        for row in field.trees.rows() {
+            // west edge
+            assert!(row[0].is_visible());
+
+            // east edge
+            assert!(row[4].is_visible());
+        }
+    }
+
+    #[test]
+    fn test_scanning_visibility() {
+        let input = vec![
+            "30373".to_string(),
+            "25512".to_string(),
+            "65332".to_string(),
+            "33549".to_string(),
+            "35390".to_string(),
+        ];
+
+        let mut field = TreeField::from_text(&input).unwrap();
+        field.scan_visibility();
+
+        assert_eq!(field.trees[(2, 1)].scenic_score(), 4);
+        assert_eq!(field.trees[(2, 3)].scenic_score(), 8);
+
+        for tree in field.trees.row(0).iter() {
+            assert_eq!(tree.scenic_score(), 0);
+        }
+
+        for tree in field.trees.row(4).iter() {
+            assert_eq!(tree.scenic_score(), 0);
+        }
+
+        for row in field.trees.rows() {
+            assert_eq!(row[0].scenic_score(), 0);
+            assert_eq!(row[4].scenic_score(), 0);
+        }
+    }
+}
+
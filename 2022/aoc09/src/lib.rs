@@ -0,0 +1,344 @@
+use std::collections::HashSet;
+
+use common::{parse_lines, parse_number, Answer, Direction, Frame, Generated, ParseError, Part, Point2, Rng, Simulated, Simulation, Solution, YAxis};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<(Direction, usize)>;
+
+    fn parse(input: &[String]) -> Result<Self::Input, ParseError> {
+        parse_lines(input, |l| {
+            let (dir, count) = l
+                .split_once(' ')
+                .ok_or_else(|| ParseError::end_of_line(l, "a space and a step count"))?;
+
+            let instr = match dir.chars().collect::<Vec<char>>()[..] {
+                [ch @ ('U' | 'R' | 'D' | 'L')] => Direction::from_char(ch),
+                _ => None,
+            };
+
+            let instr = instr.ok_or_else(|| ParseError::in_line(l, dir, "U, R, D or L"))?;
+
+            Ok((instr, parse_number(l, count)?))
+        })
+    }
+
+    fn part_one(moves: &Self::Input) -> Answer {
+        simulate(moves, 2).into()
+    }
+
+    fn part_two(moves: &Self::Input) -> Answer {
+        simulate(moves, 10).into()
+    }
+}
+
+impl Generated for Day09 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        (0..std::cmp::max(1, size * 20))
+            .map(|_| format!("{} {}", rng.pick(&['U', 'R', 'D', 'L']), rng.range(1, 19)))
+            .collect()
+    }
+}
+
+// Runs all moves on a rope of given length, returns the number of distinct
+// positions visited by its tail
+fn simulate(moves: &[(Direction, usize)], length: usize) -> usize {
+    let mut bridge = RopeBridge::new(length);
+
+    moves.iter().for_each(|(instr, count)| {
+        (0..*count).for_each(|_| {
+            bridge.move_head(instr);
+        });
+    });
+
+    bridge.tail_positions.len()
+}
+
+impl Simulated for Day09 {
+    fn simulation(moves: &Self::Input, part: Part) -> Box<dyn Simulation> {
+        let length = if part == Part::One { 2 } else { 10 };
+
+        Box::new(RopeSimulation {
+            bridge: RopeBridge::new(length),
+            // One step at a time, so each gets a frame
+            moves: moves.iter().flat_map(|(instr, count)| (0..*count).map(|_| *instr)).collect(),
+            next_move: 0,
+        })
+    }
+}
+
+// Up is +y here
+pub type Position = Point2<isize>;
+
+// Touching, diagonally or on top of each other included
+pub fn is_adjacent(a: Position, b: Position) -> bool {
+    a.chebyshev(b) <= 1
+}
+
+#[derive(Debug)]
+pub struct RopeBridge {
+    knots: Vec<Position>,
+    length: usize,
+    tail_positions: HashSet<Position>,
+}
+
+impl RopeBridge {
+    pub fn new(length: usize) -> Self {
+        let mut bridge = Self {
+            knots: (0..length).map(|_| Position { x: 0, y: 0 }).collect(),
+            length,
+            tail_positions: HashSet::new(),
+        };
+
+        bridge.tail_positions.insert(bridge.knots[length - 1].clone());
+        bridge
+    }
+
+    fn set_knot(&mut self, index: usize, pos: &Position) {
+        self.knots[index] = *pos;
+
+        if index == self.length - 1 {
+            self.tail_positions.insert(*pos);
+        }
+    }
+
+    pub fn head(&self) -> Position {
+        self.knots[0]
+    }
+
+    pub fn tail(&self) -> Position {
+        self.knots[self.length - 1]
+    }
+
+    pub fn move_head(&mut self, instr: &Direction) {
+        self.set_knot(0, &self.knots[0].step(*instr, YAxis::Up));
+
+        for i in 1..(self.length) {
+            let puller = self.knots[i - 1];
+            let pulled = self.knots[i];
+
+            if is_adjacent(pulled, puller) {
+                continue;
+            }
+
+            let x_diff = pulled.x - puller.x;
+            let y_diff = pulled.y - puller.y;
+
+            let new_x = if x_diff > 1 || x_diff < -1 {
+                puller.x + if pulled.x > puller.x { 1 } else { -1 }
+            } else {
+                if y_diff > 1 || y_diff < -1 { puller.x } else { pulled.x }
+            };
+
+            let new_y = if y_diff > 1 || y_diff < -1 {
+                puller.y + if pulled.y > puller.y { 1 } else { -1 }
+            } else {
+                if x_diff > 1 || x_diff < -1 { puller.y } else { pulled.y }
+            };
+
+            self.set_knot(i, &Position { x: new_x, y: new_y });
+        }
+    }
+}
+
+pub struct RopeSimulation {
+    bridge: RopeBridge,
+    moves: Vec<Direction>,
+    next_move: usize,
+}
+
+impl Simulation for RopeSimulation {
+    fn step(&mut self) -> bool {
+        match self.moves.get(self.next_move) {
+            Some(instr) => {
+                self.bridge.move_head(instr);
+                self.next_move += 1;
+                true
+            }
+
+            None => false,
+        }
+    }
+
+    // Drawn like the puzzle does: `#` where the tail's been, `s` for the
+    // start, and knots over those, each one covering the ones behind it.
+    fn render_frame(&self) -> Frame {
+        // y counts up here, but down in a frame
+        let cell = |pos: &Position, c: char| ((pos.x, -pos.y), c);
+        let length = self.bridge.length;
+
+        let mut cells: Vec<((isize, isize), char)> = self.bridge.tail_positions
+            .iter()
+            .map(|pos| cell(pos, '#'))
+            .collect();
+
+        cells.push(cell(&Position { x: 0, y: 0 }, 's'));
+
+        cells.extend(self.bridge.knots.iter().enumerate().rev().map(|(i, knot)| {
+            let c = match i {
+                0 => 'H',
+                _ if length == 2 => 'T',
+                _ => char::from_digit(i as u32, 10).unwrap_or('T'),
+            };
+
+            cell(knot, c)
+        }));
+
+        Frame::draw(cells, '.')
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn pos(x: isize, y: isize) -> Position {
+        Position { x, y }
+    }
+
+    #[test]
+    fn test_simulation() {
+        let input: Vec<String> = ["R 4", "U 4", "L 3", "D 1", "R 4", "D 1", "L 5", "R 2"]
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+
+        let moves = Day09::parse(&input).unwrap();
+        let mut rope = Day09::simulation(&moves, Part::One);
+
+        (0..4).for_each(|_| assert!(rope.step()));
+        assert_eq!(rope.render_frame().cells.to_string(), "s##TH");
+
+        // 24 steps in all, 4 of them done
+        assert_eq!(common::frames(rope.as_mut()).count(), 1 + 20);
+
+        let mut rope = Day09::simulation(&moves, Part::Two);
+        (0..24).for_each(|_| assert!(rope.step()));
+
+        // The tail never moves, so 6 ends up covering 7, 8, 9 and the start
+        assert_eq!(rope.render_frame().cells.to_string(), ".1H3\n.5..\n6...");
+    }
+
+    #[test]
+    fn test_position_is_adjacent() {
+        assert!(is_adjacent(pos(0, 0), pos(0, 0)));
+        assert!(is_adjacent(pos(0, 0), pos(0, 1)));
+        assert!(is_adjacent(pos(0, 0), pos(0, -1)));
+        assert!(is_adjacent(pos(0, 0), pos(1, 0)));
+        assert!(is_adjacent(pos(0, 0), pos(1, 1)));
+        assert!(is_adjacent(pos(0, 0), pos(1, -1)));
+        assert!(is_adjacent(pos(0, 0), pos(-1, 0)));
+        assert!(is_adjacent(pos(0, 0), pos(-1, 1)));
+        assert!(is_adjacent(pos(0, 0), pos(-1, -1)));
+        assert!( ! is_adjacent(pos(0, 0), pos(0, 2)));
+        assert!( ! is_adjacent(pos(0, 0), pos(0, -2)));
+        assert!( ! is_adjacent(pos(0, 0), pos(2, 0)));
+        assert!( ! is_adjacent(pos(0, 0), pos(2, 2)));
+        assert!( ! is_adjacent(pos(0, 0), pos(2, -2)));
+        assert!( ! is_adjacent(pos(0, 0), pos(-2, 0)));
+        assert!( ! is_adjacent(pos(0, 0), pos(-2, 2)));
+        assert!( ! is_adjacent(pos(0, 0), pos(-2, -2)));
+    }
+
+    #[test]
+    // No unit tests for part two, but the principle is the same so ehhh.
+    fn test_move_head() {
+        let mut bridge = RopeBridge::new(2);
+
+        // Init
+        assert_eq!(bridge.head(), pos(0, 0));
+        assert_eq!(bridge.tail(), pos(0, 0));
+
+        // R 4
+        bridge.move_head(&Direction::East);
+        assert_eq!(bridge.head(), pos(1, 0));
+        assert_eq!(bridge.tail(), pos(0, 0));
+        bridge.move_head(&Direction::East);
+        assert_eq!(bridge.head(), pos(2, 0));
+        assert_eq!(bridge.tail(), pos(1, 0));
+        bridge.move_head(&Direction::East);
+        assert_eq!(bridge.head(), pos(3, 0));
+        assert_eq!(bridge.tail(), pos(2, 0));
+        bridge.move_head(&Direction::East);
+        assert_eq!(bridge.head(), pos(4, 0));
+        assert_eq!(bridge.tail(), pos(3, 0));
+
+        // U4
+        bridge.move_head(&Direction::North);
+        assert_eq!(bridge.head(), pos(4, 1));
+        assert_eq!(bridge.tail(), pos(3, 0));
+        bridge.move_head(&Direction::North);
+        assert_eq!(bridge.head(), pos(4, 2));
+        assert_eq!(bridge.tail(), pos(4, 1));
+        bridge.move_head(&Direction::North);
+        assert_eq!(bridge.head(), pos(4, 3));
+        assert_eq!(bridge.tail(), pos(4, 2));
+        bridge.move_head(&Direction::North);
+        assert_eq!(bridge.head(), pos(4, 4));
+        assert_eq!(bridge.tail(), pos(4, 3));
+
+        // L3
+        bridge.move_head(&Direction::West);
+        assert_eq!(bridge.head(), pos(3, 4));
+        assert_eq!(bridge.tail(), pos(4, 3));
+        bridge.move_head(&Direction::West);
+        assert_eq!(bridge.head(), pos(2, 4));
+        assert_eq!(bridge.tail(), pos(3, 4));
+        bridge.move_head(&Direction::West);
+        assert_eq!(bridge.head(), pos(1, 4));
+        assert_eq!(bridge.tail(), pos(2, 4));
+
+        // D1
+        bridge.move_head(&Direction::South);
+        assert_eq!(bridge.head(), pos(1, 3));
+        assert_eq!(bridge.tail(), pos(2, 4));
+
+        // R4
+        bridge.move_head(&Direction::East);
+        assert_eq!(bridge.head(), pos(2, 3));
+        assert_eq!(bridge.tail(), pos(2, 4));
+        bridge.move_head(&Direction::East);
+        assert_eq!(bridge.head(), pos(3, 3));
+        assert_eq!(bridge.tail(), pos(2, 4));
+        bridge.move_head(&Direction::East);
+        assert_eq!(bridge.head(), pos(4, 3));
+        assert_eq!(bridge.tail(), pos(3, 3));
+        bridge.move_head(&Direction::East);
+        assert_eq!(bridge.head(), pos(5, 3));
+        assert_eq!(bridge.tail(), pos(4, 3));
+
+        // D1
+        bridge.move_head(&Direction::South);
+        assert_eq!(bridge.head(), pos(5, 2));
+        assert_eq!(bridge.tail(), pos(4, 3));
+
+        // L5
+        bridge.move_head(&Direction::West);
+        assert_eq!(bridge.head(), pos(4, 2));
+        assert_eq!(bridge.tail(), pos(4, 3));
+        bridge.move_head(&Direction::West);
+        assert_eq!(bridge.head(), pos(3, 2));
+        assert_eq!(bridge.tail(), pos(4, 3));
+        bridge.move_head(&Direction::West);
+        assert_eq!(bridge.head(), pos(2, 2));
+        assert_eq!(bridge.tail(), pos(3, 2));
+        bridge.move_head(&Direction::West);
+        assert_eq!(bridge.head(), pos(1, 2));
+        assert_eq!(bridge.tail(), pos(2, 2));
+        bridge.move_head(&Direction::West);
+        assert_eq!(bridge.head(), pos(0, 2));
+        assert_eq!(bridge.tail(), pos(1, 2));
+
+        // R2
+        bridge.move_head(&Direction::East);
+        assert_eq!(bridge.head(), pos(1, 2));
+        assert_eq!(bridge.tail(), pos(1, 2));
+        bridge.move_head(&Direction::East);
+        assert_eq!(bridge.head(), pos(2, 2));
+        assert_eq!(bridge.tail(), pos(1, 2));
+
+        assert_eq!(bridge.tail_positions.len(), 13);
+    }
+}
@@ -0,0 +1,624 @@
+use common::{parse_number, Answer, Generated, NumberParam, Param, ParseError, Params, Rng, Solution};
+
+pub struct Day11;
+
+const ROUNDS_ONE: NumberParam = NumberParam::new("rounds_one", 20, "How many rounds part one watches").at_least(0);
+const ROUNDS_TWO: NumberParam = NumberParam::new("rounds_two", 10000, "How many rounds part two watches").at_least(0);
+const RELIEF: NumberParam = NumberParam::new("relief", 3, "What worry levels get divided by after each inspection in part one").at_least(1);
+
+impl Solution for Day11 {
+    type Input = KeepAway;
+
+    const PARAMS: &'static [Param] = &[ROUNDS_ONE.param(), ROUNDS_TWO.param(), RELIEF.param()];
+
+    fn parse(input: &[String]) -> Result<KeepAway, ParseError> {
+        Self::parse_with(input, &Params::new())
+    }
+
+    fn parse_with(input: &[String], params: &Params) -> Result<KeepAway, ParseError> {
+        Ok(KeepAway {
+            troop: Troop::parse(input)?,
+            rounds_one: params.number(ROUNDS_ONE) as usize,
+            rounds_two: params.number(ROUNDS_TWO) as usize,
+            relief: params.number(RELIEF) as usize,
+        })
+    }
+
+    fn part_one(game: &KeepAway) -> Answer {
+        let mut troop = game.troop.clone();
+
+        (0..game.rounds_one).for_each(|_| troop.process_round(game.relief));
+
+        troop.monkey_business().into()
+    }
+
+    fn part_two(game: &KeepAway) -> Answer {
+        let mut troop = game.troop.clone();
+
+        (0..game.rounds_two).for_each(|_| troop.process_round(1));
+
+        troop.monkey_business().into()
+    }
+}
+
+// Each monkey tests for a different prime, so that worry levels kept under
+// their product still fit in a usize when squared
+impl Generated for Day11 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        let mut primes = vec![2, 3, 5, 7, 11, 13, 17, 19, 23];
+        rng.shuffle(&mut primes);
+
+        let count = (2 + size * 6 / 100).clamp(2, primes.len());
+        let mut lines = vec![];
+
+        for (monkey, divisor) in primes.iter().take(count).enumerate() {
+            let items: Vec<String> = (0..rng.range(1, 4 + size as i64 / 25))
+                .map(|_| rng.range(50, 99).to_string())
+                .collect();
+
+            let operation = match rng.below(5) {
+                0 => "old * old".to_string(),
+                1 | 2 => format!("old * {}", rng.range(2, 19)),
+                _ => format!("old + {}", rng.range(1, 8)),
+            };
+
+            // Never throwing to itself
+            let mut target = || (monkey + 1 + rng.below(count - 1)) % count;
+            let (if_true, if_false) = (target(), target());
+
+            if monkey > 0 {
+                lines.push(String::new());
+            }
+
+            lines.extend([
+                format!("Monkey {monkey}:"),
+                format!("  Starting items: {}", items.join(", ")),
+                format!("  Operation: new = {operation}"),
+                format!("  Test: divisible by {divisor}"),
+                format!("    If true: throw to monkey {if_true}"),
+                format!("    If false: throw to monkey {if_false}"),
+            ]);
+        }
+
+        lines
+    }
+}
+
+fn parse_start_items(input: &str) -> Result<Vec<usize>, ParseError> {
+    let (_, items) = input
+        .split_once(": ")
+        .ok_or_else(|| ParseError::end_of_line(input, "`: ` followed by the items"))?;
+
+    items.split(", ").map(|i| parse_number(input, i)).collect()
+}
+
+fn parse_number_at_end(input: &str) -> Result<usize, ParseError> {
+    parse_number(input, input.split(" ").last().unwrap())
+}
+
+fn parse_divisor(input: &str) -> Result<usize, ParseError> {
+    match parse_number_at_end(input)? {
+        0 => Err(ParseError::in_line(input, input.split(" ").last().unwrap(), "a divisor above 0")),
+        divisor => Ok(divisor),
+    }
+}
+
+// Errors come back on line 0 for input1 and line 1 for input2
+fn parse_targets(input1: &str, input2: &str) -> Result<(usize, usize), ParseError> {
+    let target1 = parse_number_at_end(input1)?;
+    let target2 = parse_number_at_end(input2).map_err(|e| e.on_line(1))?;
+    Ok((target1, target2))
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Operand {
+    Old,
+    Integer(usize)
+}
+
+// Only one operand is given because operand 1 is always Operand::Old
+#[derive(Debug, PartialEq, Clone)]
+enum Operation {
+    Multiply(Operand),
+    Add(Operand),
+}
+
+impl Operation {
+    pub fn parse(input: &str) -> Result<Operation, ParseError> {
+        let split: Vec<&str> = input.split(" ").collect();
+
+        if split.len() < 2 {
+            return Err(ParseError::end_of_line(input, "an operator and an operand"));
+        }
+
+        let operand_raw = split[split.len() - 1];
+        let operation_raw = split[split.len() - 2];
+
+        let operand = match operand_raw {
+            "old" => Operand::Old,
+            _ => Operand::Integer(parse_number(input, operand_raw)?),
+        };
+
+        match operation_raw {
+            "*" => Ok(Operation::Multiply(operand)),
+            "+" => Ok(Operation::Add(operand)),
+            _ => Err(ParseError::in_line(input, operation_raw, "`*` or `+`")),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+struct Monkey {
+    // Items at their worry level monkey carries
+    // Please pretend with me that this `usize` here and everywhere these
+    // values came through weren't a `u128` for some time.
+    // Turns out part 2 was designed so you couldn't get away with "let's just throw a larger data
+    // type at it". (: 
+    items: Vec<usize>,
+
+    // Operation that happens to worry level as monkey inspects item
+    worry_operation: Operation,
+
+    // We check if the worry level after worry_operation, _and then divided by 3_ (rounded down)
+    // is divisable by this number. If it is, the item, at that worry level, is added to the
+    // monkey at target 0. If it isn't, it goes to monkey at target 1.
+    divisor: usize,
+
+    // Two possible monkeys an item transfers to
+    targets: (usize, usize),
+
+    // Number of times this monkey has inspected any item
+    inspection_count: usize
+}
+
+impl Monkey {
+    fn new(
+        starting_items: Vec<usize>,
+        worry_operation: Operation,
+        divisor: usize,
+        targets: (usize, usize)
+    ) -> Monkey {
+        Monkey {
+            items: starting_items,
+            worry_operation,
+            divisor,
+            targets,
+            inspection_count: 0
+        }
+    }
+
+    // Line numbers in errors count from the "Monkey n:" line
+    pub fn parse(input: &[String]) -> Result<Monkey, ParseError> {
+        if input.len() < 6 {
+            let last = input.len().saturating_sub(1);
+            let line = input.get(last).map(|l| l.as_str()).unwrap_or("");
+            return Err(ParseError::end_of_line(line, "6 lines describing a monkey").on_line(last));
+        }
+
+        if !input[0].starts_with("Monkey ") {
+            return Err(ParseError::in_line(&input[0], &input[0], "`Monkey <n>:`"));
+        }
+
+        let starting_items_input = input.iter().nth(1).unwrap();
+        let operation_input = input.iter().nth(2).unwrap();
+        let divisor_input = input.iter().nth(3).unwrap();
+        let target1_input = input.iter().nth(4).unwrap();
+        let target2_input = input.iter().nth(5).unwrap();
+
+        let starting_items = parse_start_items(starting_items_input).map_err(|e| e.on_line(1))?;
+        let worry_operation = Operation::parse(operation_input).map_err(|e| e.on_line(2))?;
+        let divisor = parse_divisor(divisor_input).map_err(|e| e.on_line(3))?;
+        let targets = parse_targets(target1_input, target2_input).map_err(|e| {
+            let line = e.line + 4;
+            e.on_line(line)
+        })?;
+
+        Ok(Monkey::new(starting_items, worry_operation, divisor, targets))
+    }
+
+    fn inspect(&mut self, item: usize, worry_limit: usize) -> usize {
+        self.inspection_count += 1;
+
+        let result = match &self.worry_operation {
+            Operation::Multiply(operand) => {
+                item * match operand {
+                    Operand::Old => item,
+                    Operand::Integer(i) => *i,
+                }
+            }
+
+            Operation::Add(operand) => item + match operand {
+                Operand::Old => item,
+                Operand::Integer(i) => *i,
+            }
+        };
+
+        result % worry_limit
+    }
+
+    fn decide_throw_target(&self, worry_level: usize) -> usize {
+        let (m1, m2) = self.targets;
+        if worry_level.is_multiple_of(self.divisor as usize) { m1 } else { m2 }
+    }
+
+    /// Considers the items held, inspects them, then throws them.
+    /// Mutates self.items, self.inspection_count.
+    /// Returns vector of tuples, where in each tuple:
+    ///  - 0 = target monkey
+    ///  - 1 = item's worry level thrown at that monkey
+    pub fn process_round(&mut self, worry_divisor: usize, worry_limit: usize) -> Vec<(usize, usize)> {
+        let items = self.items.clone();
+        let inspected_items: Vec<usize> = items
+            .iter()
+            .map(|i| self.inspect(*i, worry_limit))
+            .map(|i| i / worry_divisor) // worry level decreases after inspection - or does it?
+            .collect();
+
+        let throw_data: Vec<(usize, usize)> = inspected_items
+            .iter()
+            .map(|i| (self.decide_throw_target(*i), *i))
+            .collect();
+
+        self.items.clear();
+
+        throw_data
+    }
+}
+
+// The monkeys, and how long to watch them for
+pub struct KeepAway {
+    troop: Troop,
+    rounds_one: usize,
+    rounds_two: usize,
+    relief: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Troop {
+    monkeys: Vec<Monkey>,
+    worry_limit: usize,
+}
+
+impl Troop {
+    pub fn parse(input: &[String]) -> Result<Troop, ParseError> {
+        let mut monkeys: Vec<Monkey> = vec![];
+
+        // Wow I'm sure this is a disgusting way to read the input, but for
+        // some reason this one was giving me a lot of trouble.
+        for offset in (0..input.len()).step_by(7) {
+            let monkey_lines: Vec<String> = input[offset..]
+                .iter()
+                .take(6)
+                .map(|l| l.to_string())
+                .collect();
+
+            let monkey = Monkey::parse(&monkey_lines).map_err(|e| {
+                let line = e.line + offset;
+                e.on_line(line)
+            })?;
+
+            monkeys.push(monkey);
+        }
+
+        if monkeys.is_empty() {
+            return Err(ParseError::end_of_line("", "a monkey"));
+        }
+
+        // Every monkey has to throw to one of the others
+        for (i, monkey) in monkeys.iter().enumerate() {
+            let (t1, t2) = monkey.targets;
+
+            for (line_in_block, target) in [(4, t1), (5, t2)] {
+                if target >= monkeys.len() || target == i {
+                    let line_index = i * 7 + line_in_block;
+                    let line = &input[line_index];
+                    let expected = format!("another monkey's number, below {}", monkeys.len());
+
+                    return Err(
+                        ParseError::in_line(line, line.split(" ").last().unwrap(), expected)
+                            .on_line(line_index)
+                    );
+                }
+            }
+        }
+
+        // Worry limit is the maximum worry value for any one item.
+        // As soon as worry exceeds a value _all_ the monkeys can divide by, being
+        // larger does not make a functional difference. Thus, we use this worry limit
+        // in every inspection to make sure our number sizes stay down, keeping our
+        // worry levels manageable.
+        // I didn't bother with a "lowest common multiple" here and naively just mulitiplied all
+        // the divisors together. As it turns out, that fits in a `usize` just fine on my machine.
+        let worry_limit = monkeys.iter().fold(1, |acc, m| acc * m.divisor as usize);
+
+        Ok(Troop { monkeys, worry_limit })
+    }
+
+    pub fn process_round(&mut self, worry_divisor: usize) {
+        (0..self.monkeys.len()).for_each(|monkey_index| {
+            let monkey = &mut self.monkeys[monkey_index];
+
+            let throw_data = monkey.process_round(worry_divisor, self.worry_limit);
+
+            for (target_monkey_index, item) in throw_data {
+                self.monkeys[target_monkey_index].items.push(item);
+            }
+        });
+    }
+
+    pub fn monkey_business(&self) -> usize {
+        let mut inspections: Vec<usize> = self.monkeys
+            .iter()
+            .map(|m| m.inspection_count)
+            .collect();
+
+        inspections.sort();
+        inspections.reverse();
+
+        inspections[0] * inspections[1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_parsing_operation() {
+        assert_eq!(
+            Operation::parse("  Operation: new = old * 19").unwrap(),
+            Operation::Multiply(Operand::Integer(19)),
+        );
+        assert_eq!(
+            Operation::parse("  Operation: new = old + 6").unwrap(),
+            Operation::Add(Operand::Integer(6)),
+        );
+        assert_eq!(
+            Operation::parse("  Operation: new = old * old").unwrap(),
+            Operation::Multiply(Operand::Old),
+        );
+    }
+
+    #[test]
+    fn test_parse_start_items() {
+        assert_eq!(
+            parse_start_items("  Starting items: 79, 98").unwrap(),
+            vec![79, 98],
+        );
+        assert_eq!(
+            parse_start_items("  Starting items: 54, 65, 75, 74").unwrap(),
+            vec![54, 65, 75, 74],
+        );
+        assert_eq!(
+            parse_start_items("  Starting items: 79, 60, 97").unwrap(),
+            vec![79, 60, 97],
+        );
+        assert_eq!(
+            parse_start_items("  Starting items: 74").unwrap(),
+            vec![74],
+        );
+    }
+
+    #[test]
+    fn test_parse_divisor() {
+        assert_eq!(
+            parse_divisor("  Test: divisible by 23").unwrap(),
+            23,
+        );
+    }
+
+    #[test]
+    fn test_parse_targets() {
+        assert_eq!(
+            parse_targets(
+                "    If true: throw to monkey 2",
+                "    If false: throw to monkey 3",
+            ).unwrap(),
+            (2, 3),
+        );
+    }
+
+    #[test]
+    fn test_parse_monkey() {
+        let input = vec![
+            "Monkey 0:".to_string(),
+            "  Starting items: 79, 98".to_string(),
+            "  Operation: new = old * 19".to_string(),
+            "  Test: divisible by 23".to_string(),
+            "    If true: throw to monkey 2".to_string(),
+            "    If false: throw to monkey 3".to_string(),
+        ];
+
+        assert_eq!(
+            Monkey::parse(&input).unwrap(),
+            Monkey {
+                items: vec![79, 98],
+                worry_operation: Operation::Multiply(Operand::Integer(19)),
+                divisor: 23,
+                targets: (2, 3),
+                inspection_count: 0
+            },
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let mut input = vec![
+            "Monkey 0:".to_string(),
+            "  Starting items: 79, 98".to_string(),
+            "  Operation: new = old / 19".to_string(),
+            "  Test: divisible by 23".to_string(),
+            "    If true: throw to monkey 1".to_string(),
+            "    If false: throw to monkey 1".to_string(),
+            "".to_string(),
+            "Monkey 1:".to_string(),
+            "  Starting items: 79".to_string(),
+            "  Operation: new = old + 1".to_string(),
+            "  Test: divisible by 3".to_string(),
+            "    If true: throw to monkey 0".to_string(),
+            "    If false: throw to monkey 2".to_string(),
+        ];
+
+        let err = Troop::parse(&input).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 23, "/"));
+
+        input[2] = "  Operation: new = old * 19".to_string();
+        let err = Troop::parse(&input).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (12, 30, "2"));
+
+        let err = Troop::parse(&input[..10]).unwrap_err();
+        assert_eq!(err.line, 9);
+    }
+
+    #[test]
+    fn test_monkey_inspect() {
+        let mut monkey = Monkey {
+            items: vec![79, 98],
+            worry_operation: Operation::Multiply(Operand::Integer(19)),
+            divisor: 23,
+            targets: (2, 3),
+            inspection_count: 0
+        };
+
+        // Note that the inspect function itself does not mutate Monkey.items
+        // It only mutates inspection_count.
+
+        assert_eq!(monkey.inspect(79, usize::MAX), 1501);
+        assert_eq!(monkey.inspection_count, 1);
+
+        assert_eq!(monkey.inspect(98, usize::MAX), 1862);
+        assert_eq!(monkey.inspection_count, 2);
+    }
+
+    #[test]
+    fn test_monkey_decide_throw_target() {
+        let monkey = Monkey {
+            items: vec![79, 98],
+            worry_operation: Operation::Multiply(Operand::Integer(19)),
+            divisor: 23,
+            targets: (2, 3),
+            inspection_count: 0
+        };
+
+        assert_eq!(monkey.decide_throw_target(500), 3);
+        assert_eq!(monkey.decide_throw_target(620), 3);
+
+        assert_eq!(monkey.decide_throw_target(322), 2);
+    }
+
+    #[test]
+    fn test_monkey_process_round() {
+        // Note: in this test, the monkeys don't interact yet.
+        let mut monkey0 = Monkey {
+            items: vec![79, 98],
+            worry_operation: Operation::Multiply(Operand::Integer(19)),
+            divisor: 23,
+            targets: (2, 3),
+            inspection_count: 0
+        };
+
+        let mut monkey1 = Monkey {
+            items: vec![54, 65, 75, 74],
+            worry_operation: Operation::Add(Operand::Integer(6)),
+            divisor: 19,
+            targets: (2, 0),
+            inspection_count: 0
+        };
+
+        assert_eq!(
+            monkey0.process_round(3, usize::MAX),
+            vec![ (3, 500), (3, 620) ],
+        );
+        assert_eq!(monkey0.inspection_count, 2);
+
+        assert_eq!(
+            monkey1.process_round(3, usize::MAX),
+            vec![ (0, 20), (0, 23), (0, 27), (0, 26) ],
+        );
+    }
+
+    #[test]
+    fn test_troop_process_round() {
+        let mut troop = Troop {
+            monkeys: vec![
+                Monkey { // 0
+                    items: vec![79, 98],
+                    worry_operation: Operation::Multiply(Operand::Integer(19)),
+                    divisor: 23,
+                    targets: (2, 3),
+                    inspection_count: 0
+                },
+
+                Monkey { // 1
+                    items: vec![54, 65, 75, 74],
+                    worry_operation: Operation::Add(Operand::Integer(6)),
+                    divisor: 19,
+                    targets: (2, 0),
+                    inspection_count: 0
+                },
+
+                Monkey { // 2
+                    items: vec![79, 60, 97],
+                    worry_operation: Operation::Multiply(Operand::Old),
+                    divisor: 13,
+                    targets: (1, 3),
+                    inspection_count: 0,
+                },
+
+                Monkey { // 3
+                    items: vec![74],
+                    worry_operation: Operation::Add(Operand::Integer(3)),
+                    divisor: 17,
+                    targets: (0, 1),
+                    inspection_count: 0,
+                },
+            ],
+
+            worry_limit: 23 * 19 * 13 * 17,
+        };
+
+        // Round 1
+        troop.process_round(3);
+        assert_eq!(troop.monkeys[0].items, vec![20, 23, 27, 26]);
+        assert_eq!(troop.monkeys[1].items, vec![2080, 25, 167, 207, 401, 1046]);
+        assert_eq!(troop.monkeys[2].items, vec![]);
+        assert_eq!(troop.monkeys[3].items, vec![]);
+
+        // Round 2
+        troop.process_round(3);
+        assert_eq!(troop.monkeys[0].items, vec![695, 10, 71, 135, 350]);
+        assert_eq!(troop.monkeys[1].items, vec![43, 49, 58, 55, 362]);
+        assert_eq!(troop.monkeys[2].items, vec![]);
+        assert_eq!(troop.monkeys[3].items, vec![]);
+
+        // Round 3
+        troop.process_round(3);
+        assert_eq!(troop.monkeys[0].items, vec![16, 18, 21, 20, 122]);
+        assert_eq!(troop.monkeys[1].items, vec![1468, 22, 150, 286, 739]);
+        assert_eq!(troop.monkeys[2].items, vec![]);
+        assert_eq!(troop.monkeys[3].items, vec![]);
+
+        // Round 4
+        troop.process_round(3);
+        assert_eq!(troop.monkeys[0].items, vec![491, 9, 52, 97, 248, 34]);
+        assert_eq!(troop.monkeys[1].items, vec![39, 45, 43, 258]);
+        assert_eq!(troop.monkeys[2].items, vec![]);
+        assert_eq!(troop.monkeys[3].items, vec![]);
+
+        // Round 20
+        (4..20).for_each(|_| troop.process_round(3));
+        assert_eq!(troop.monkeys[0].items, vec![10, 12, 14, 26, 34]);
+        assert_eq!(troop.monkeys[1].items, vec![245, 93, 53, 199, 115]);
+        assert_eq!(troop.monkeys[2].items, vec![]);
+        assert_eq!(troop.monkeys[3].items, vec![]);
+
+        // Inspection counts:
+        assert_eq!(troop.monkeys[0].inspection_count, 101);
+        assert_eq!(troop.monkeys[1].inspection_count, 95);
+        assert_eq!(troop.monkeys[2].inspection_count, 7);
+        assert_eq!(troop.monkeys[3].inspection_count, 105);
+
+        // And finally, monkey business
+        assert_eq!(troop.monkey_business(), 10605);
+    }
+}
@@ -0,0 +1,230 @@
+use common::{search, Answer, Generated, Grid, ParseError, Point2, Rng, Solution};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Graph;
+
+    fn parse(input: &[String]) -> Result<Graph, ParseError> {
+        Graph::parse(input)
+    }
+
+    fn part_one(graph: &Graph) -> Answer {
+        let shortest_path_steps = graph
+            .find_shortest_path(
+                &graph.begin,
+                false, // reverse
+                |n| n == &graph.end // at destination?
+            )
+            .unwrap();
+
+        shortest_path_steps.into()
+    }
+
+    fn part_two(graph: &Graph) -> Answer {
+        // Here we don't know the starting point, so we're path-finding
+        // in reverse, starting at the known end.
+        let shortest_path_steps = graph
+            .find_shortest_path(
+                &graph.end,
+                true, // reverse
+                |n| n.altitude == 1 // at destination?
+            )
+            .unwrap();
+
+        shortest_path_steps.into()
+    }
+}
+
+// A trail snaking along every other row climbs steadily from S to E, so
+// there's always a way up. The rows in between are somewhere around the
+// heights either side of them, which makes for shortcuts.
+impl Generated for Day12 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        let (width, height) = (8 + size * 3 / 2, 5 + size * 36 / 100);
+        let mut trail = vec![];
+
+        for y in (0..height).step_by(2) {
+            let row: Vec<(usize, usize)> = (0..width).map(|x| (x, y)).collect();
+
+            if (y / 2) % 2 == 0 {
+                trail.extend(row);
+            } else {
+                trail.extend(row.into_iter().rev());
+            }
+
+            // Down to the next row, at whichever end this one finished
+            if y + 2 < height {
+                let (x, _) = *trail.last().unwrap();
+                trail.push((x, y + 1));
+            }
+        }
+
+        // 0 for a, up to 25 for z
+        let mut altitudes: Grid<Option<i64>> = Grid::new(width, height, None);
+        let last = trail.len() - 1;
+
+        for (i, loc) in trail.iter().enumerate() {
+            altitudes[*loc] = Some((25 * i / last) as i64);
+        }
+
+        let mut map = Grid::new(width, height, 'a');
+
+        for y in 0..height {
+            for x in 0..width {
+                let altitude = altitudes[(x, y)].unwrap_or_else(|| {
+                    let above = altitudes[(x, y - 1)].unwrap();
+                    let below = altitudes.get(x, y + 1).copied().flatten().unwrap_or(above);
+                    rng.range(std::cmp::min(above, below) - 1, std::cmp::max(above, below) + 1).clamp(0, 25)
+                });
+
+                map[(x, y)] = (b'a' + altitude as u8) as char;
+            }
+        }
+
+        map[trail[0]] = 'S';
+        map[trail[last]] = 'E';
+
+        map.rows().map(|row| row.iter().collect()).collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Position {
+    loc: Point2<usize>,
+    altitude: usize
+}
+
+impl Position {
+    pub fn can_access(&self, other: &Self, reverse: bool) -> bool {
+        // Must be at most 1 square away, and not diagonally
+        if self.loc.manhattan(other.loc) > 1 {
+            return false;
+        }
+
+        // Other must be any distance below, same level, or at most 1 above
+        if reverse {
+            other.altitude >= self.altitude - 1
+        } else {
+            self.altitude >= other.altitude - 1
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Graph {
+    begin: Position,
+    end: Position,
+    field: Grid<usize>,
+}
+
+impl Graph {
+
+    pub fn parse(input: &[String]) -> Result<Self, ParseError> {
+        let map = Grid::try_parse(input, "a height (a-z), S or E", |c| match c {
+            'a'..='z' | 'S' | 'E' => Some(c),
+            _ => None,
+        })?;
+
+        // Map a-z + S/E to altitudes
+        let field = map.map(|c| match c {
+            'S' => 1,
+            'E' => 26,
+            // 'a' = ascii 97
+            // a = 1
+            _ => *c as usize - 96,
+        });
+
+        let last_line = input.last().map(|l| l.as_str()).unwrap_or("");
+        let missing = |what: &str| {
+            ParseError::end_of_line(last_line, what).on_line(input.len().saturating_sub(1))
+        };
+
+        let begin = map.find(|c| *c == 'S').ok_or_else(|| missing("a start position (S)"))?;
+        let end = map.find(|c| *c == 'E').ok_or_else(|| missing("a best signal position (E)"))?;
+
+        Ok(Graph {
+            begin: Position { loc: begin.into(), altitude: 1 },
+            end: Position { loc: end.into(), altitude: 26 },
+            field,
+        })
+    }
+
+    pub fn get_neighbours(&self, node: &Position, reverse: bool) -> Vec<Position> {
+        self.field
+            .neighbours4(node.loc.x, node.loc.y)
+            .map(|loc| Position { loc: loc.into(), altitude: self.field[loc] })
+            .filter(|n| node.can_access(n, reverse))
+            .collect()
+    }
+
+    // Finds the shortest path from start to end using breadth-first-search
+    // Returns the number of steps required to get there
+    pub fn find_shortest_path<T>(
+        &self,
+        start: &Position,
+        reverse: bool, // Reverses the check for which neighbours are accessible
+                       // when traversing from opposite direction
+        reached_goal: T // A lambda that, given a position, decides that we've reached where we
+                        // need to be
+    ) -> Option<usize> where T: Fn(&Position) -> bool {
+        // If the search runs out of nodes, we've traversed the entire graph
+        // from begin without finding the end; it is unreachable.
+        search::bfs(*start, |pos| self.get_neighbours(pos, reverse), reached_goal).cost()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn testing_parsing_map() {
+        let graph = Graph::parse(&vec![
+             "Sabqponm".to_string(),
+             "abcryxxl".to_string(),
+             "accszExk".to_string(),
+             "acctuvwj".to_string(),
+             "abdefghi".to_string(),
+        ]).unwrap();
+
+        assert_eq!(graph.begin, Position { loc: Point2::new(0, 0), altitude: 1 });
+        assert_eq!(graph.end, Position { loc: Point2::new(5, 2), altitude: 26 });
+        assert_eq!(graph.field[(0, 0)], 1);
+        assert_eq!(graph.field[(3, 0)], 17);
+        assert_eq!(graph.field[(5, 2)], 26);
+        assert_eq!(graph.field[(0, 4)], 1);
+        assert_eq!(graph.field[(7, 4)], 9);
+    }
+
+    #[test]
+    fn finds_shortest_path() {
+        let graph = Graph::parse(&vec![
+             "Sabqponm".to_string(),
+             "abcryxxl".to_string(),
+             "accszExk".to_string(),
+             "acctuvwj".to_string(),
+             "abdefghi".to_string(),
+        ]).unwrap();
+
+        // Part 1
+        assert_eq!(
+            graph.find_shortest_path(
+                &graph.begin,
+                false, // reverse
+                |p| p == &graph.end
+            ).unwrap(),
+            31,
+        );
+
+        // Part 2
+        assert_eq!(
+            graph.find_shortest_path(
+                &graph.end,
+                true, // reverse
+                |p| p.altitude == 1,
+            ).unwrap(),
+            29,
+        );
+    }
+}
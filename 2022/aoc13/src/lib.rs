@@ -0,0 +1,307 @@
+use std::cmp::Ordering;
+extern crate json;
+use json::JsonValue;
+
+// I was going to roll my own parser but it didn't work out, and I have limited
+// time today, so I opted for just parsing it as json.
+//
+// Could rework further to make `compare` output Ordering to begin with, but
+// as I mentioned, limited time.
+
+use common::{Answer, Generated, ParseError, Rng, Solution};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    // All packets in input order, blank separator lines dropped
+    type Input = Vec<JsonValue>;
+
+    fn parse(input: &[String]) -> Result<Vec<JsonValue>, ParseError> {
+        let packets = input
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| ! l.trim().is_empty())
+            .map(|(i, l)| parse_packet(l).map_err(|e| e.on_line(i)))
+            .collect::<Result<Vec<JsonValue>, ParseError>>()?;
+
+        // Part one works through them in pairs
+        if !packets.len().is_multiple_of(2) {
+            let last = input.len().saturating_sub(1);
+            let line = input.get(last).map(|l| l.as_str()).unwrap_or("");
+            return Err(ParseError::end_of_line(line, "another packet to finish the pair").on_line(last));
+        }
+
+        Ok(packets)
+    }
+
+    fn part_one(packets: &Vec<JsonValue>) -> Answer {
+        // A pair of equal packets isn't in the right order, nor the wrong one,
+        // so it doesn't count
+        let result = packets
+            .chunks(2)
+            .enumerate()
+            .fold(0, |acc, (i, pair)| {
+                acc + if compare(&pair[0], &pair[1]) == Some(true) { i + 1 }  else { 0 }
+            });
+
+        result.into()
+    }
+
+    fn part_two(packets: &Vec<JsonValue>) -> Answer {
+        let mut packets = packets.clone();
+
+        // Add divider packets
+        packets.push(json::parse("[[2]]").unwrap());
+        packets.push(json::parse("[[6]]").unwrap());
+
+        packets.sort_by(compare_ordering);
+
+        let first_pos = packets.iter().position(|p| {
+            p.len() == 1 && p[0].len() == 1 && p[0][0] == 2
+        }).unwrap() + 1;
+
+
+        let second_pos = packets.iter().position(|p| {
+            p.len() == 1 && p[0].len() == 1 && p[0][0] == 6
+        }).unwrap() + 1;
+
+        (first_pos * second_pos).into()
+    }
+}
+
+// A pair of packets for every bit of `size`. `compare` can't make anything of
+// a pair that's in neither order, so those get another go.
+impl Generated for Day13 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        let mut lines = vec![];
+
+        for pair in 0..std::cmp::max(1, size * 3 / 2) {
+            let (left, right) = loop {
+                let (left, right) = (random_packet(rng, 0), random_packet(rng, 0));
+
+                if compare(&json::parse(&left).unwrap(), &json::parse(&right).unwrap()).is_some() {
+                    break (left, right);
+                }
+            };
+
+            if pair > 0 {
+                lines.push(String::new());
+            }
+
+            lines.extend([left, right]);
+        }
+
+        lines
+    }
+}
+
+fn random_packet(rng: &mut Rng, depth: usize) -> String {
+    let items: Vec<String> = (0..rng.range(0, 5))
+        .map(|_| {
+            if depth < 4 && rng.one_in(3) {
+                random_packet(rng, depth + 1)
+            } else {
+                rng.range(0, 10).to_string()
+            }
+        })
+        .collect();
+
+    format!("[{}]", items.join(","))
+}
+
+// Packets are lists of integers and more lists, and `compare` relies on
+// nothing else getting through, so we check for that before handing the line
+// to json.
+pub fn parse_packet(line: &str) -> Result<JsonValue, ParseError> {
+    if let Some(column) = line.chars().position(|c| !matches!(c, '[' | ']' | ',' | '0'..='9')) {
+        return Err(ParseError::at_char(line, column, "`[`, `]`, `,` or a digit"));
+    }
+
+    if !line.starts_with('[') {
+        return Err(ParseError::at_char(line, 0, "a list, starting with `[`"));
+    }
+
+    match json::parse(line) {
+        Ok(packet) => Ok(packet),
+
+        // json counts columns from 1
+        Err(json::Error::UnexpectedCharacter { column, .. }) => {
+            Err(ParseError::at_char(line, column - 1, "a list item or the end of a list"))
+        }
+
+        Err(_) => Err(ParseError::end_of_line(line, "`]`")),
+    }
+}
+
+pub fn compare(left: &JsonValue, right: &JsonValue) -> Option<bool> {
+    match (left, right) {
+        (JsonValue::Number(left_num), JsonValue::Number(right_num)) => {
+            let (l, r): (f32, f32) = ((*left_num).into(), (*right_num).into());
+
+            if l < r {
+                Some(true)
+            } else if l > r {
+                Some(false)
+            } else {
+                None
+            }
+        },
+
+        (JsonValue::Number(_), JsonValue::Array(_)) => {
+            compare(&JsonValue::Array(vec![left.clone()]), right)
+        },
+
+        (JsonValue::Array(_), JsonValue::Number(_)) => {
+            compare(left, &JsonValue::Array(vec![right.clone()]))
+        },
+
+        (JsonValue::Array(left_arr), JsonValue::Array(right_arr)) => {
+            if left_arr.len() == 0 && right_arr.len() == 0 {
+                None
+            } else if left_arr.len() == 0 {
+                Some(true)
+            } else if right_arr.len() == 0 {
+                Some(false)
+            } else {
+                left_arr.iter().zip(right_arr.iter())
+                    .map(|(l, r)| compare(l, r))
+                    .find_map(|c| c)
+                    .or({
+                        if left_arr.len() < right_arr.len() {
+                            Some(true)
+                        } else if left_arr.len() > right_arr.len() {
+                            Some(false)
+                        } else {
+                            None
+                        }
+                    })
+            }
+        }
+
+        // parse_packet only lets lists and numbers through
+        _ => unreachable!("Encountered unsupported combination")
+    }
+}
+
+pub fn compare_ordering(left: &JsonValue, right: &JsonValue) -> Ordering {
+    match compare(left, right) {
+        Some(true) => Ordering::Less,
+        None => Ordering::Equal,
+        Some(false) => Ordering::Greater,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_item_parse() {
+        assert_eq!(json::parse("[]").unwrap().len(), 0);
+
+        let number_in_list = json::parse("[2]").unwrap();
+        assert_eq!(number_in_list.len(), 1);
+        assert_eq!(number_in_list[0], 2);
+
+        let number_and_array = json::parse("[3,[4,5]]").unwrap();
+        assert_eq!(number_and_array.len(), 2);
+        assert_eq!(number_and_array[0], 3);
+        assert_eq!(number_and_array[1][0], 4);
+        assert_eq!(number_and_array[1][1], 5);
+
+        assert!(matches!(number_and_array[1], JsonValue::Array(_)));
+    }
+
+    #[test]
+    fn test_packet_parse_errors() {
+        assert_eq!(parse_packet("[1,[2]]").unwrap()[1][0], 2);
+
+        let err = parse_packet("[1,\"a\"]").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (3, "\""));
+
+        let err = parse_packet("[1,,2]").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (3, ","));
+
+        let err = parse_packet("[1,[2]").unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (6, "`]`"));
+
+        let err = parse_packet("3").unwrap_err();
+        assert_eq!(err.column, 0);
+    }
+
+    #[test]
+    fn test_compares_correctly() {
+        assert_eq!(
+            compare(
+                &json::parse("[1,1,3,1,1]").unwrap(),
+                &json::parse("[1,1,5,1,1]").unwrap(),
+            ),
+            Some(true),
+        );
+
+        assert_eq!(
+            compare(
+                &json::parse("[[1],[2,3,4]]").unwrap(),
+                &json::parse("[[1],4]").unwrap(),
+            ),
+            Some(true),
+        );
+
+        assert_eq!(
+            compare(
+                &json::parse("[9]").unwrap(),
+                &json::parse("[[8,7,6]]").unwrap(),
+            ),
+            Some(false),
+        );
+
+        assert_eq!(
+            compare(
+                &json::parse("[[4,4],4,4]").unwrap(),
+                &json::parse("[[4,4],4,4,4]").unwrap(),
+            ),
+            Some(true),
+        );
+
+        assert_eq!(
+            compare(
+                &json::parse("[7,7,7,7]").unwrap(),
+                &json::parse("[7,7,7]").unwrap(),
+            ),
+            Some(false),
+        );
+
+        assert_eq!(
+            compare(
+                &json::parse("[]").unwrap(),
+                &json::parse("[3]").unwrap(),
+            ),
+            Some(true),
+        );
+
+        assert_eq!(
+            compare(
+                &json::parse("[[[]]]").unwrap(),
+                &json::parse("[[]]").unwrap(),
+            ),
+            Some(false),
+        );
+
+        assert_eq!(
+            compare(
+                &json::parse("[1,[2,[3,[4,[5,6,7]]]],8,9]").unwrap(),
+                &json::parse("[1,[2,[3,[4,[5,6,0]]]],8,9]").unwrap(),
+            ),
+            Some(false),
+        );
+    }
+
+    #[test]
+    fn test_equal_pair_not_in_order() {
+        let lines: Vec<String> = ["[1,[2]]", "[1,[2]]", "", "[1]", "[2]"].iter().map(|l| l.to_string()).collect();
+        let packets = Day13::parse(&lines).unwrap();
+
+        assert_eq!(compare(&packets[0], &packets[1]), None);
+        assert_eq!(Day13::part_one(&packets), Answer::from(2));
+    }
+}
@@ -0,0 +1,486 @@
+use std::collections::{HashMap, HashSet};
+
+use common::{parse_lines, parse_number, Answer, Frame, Generated, ParseError, Part, Point2, Rng, Simulated, Simulation, Solution};
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    // Scanned cave without a floor; part two adds one to its own copy
+    type Input = Cave;
+
+    fn parse(input: &[String]) -> Result<Cave, ParseError> {
+        Cave::process_scan(Point2::new(500, 0), false, input)
+    }
+
+    fn part_one(cave: &Cave) -> Answer {
+        // Pretend there's no floor
+        let mut cave = cave.clone();
+
+        loop {
+            // drop_sand returns None if there's nowhere for the sand to settle
+            if cave.drop_sand().is_none() {
+                break;
+            }
+        }
+
+        cave.count_sand().into()
+    }
+
+    fn part_two(cave: &Cave) -> Answer {
+        // Oh dang there _is_ a floor
+        let mut cave = cave.clone();
+        cave.floor = Some(cave.rock_bottom + 2);
+
+        loop {
+            // Since here we have an infinite floor, there always is somewhere for
+            // sand to settle. So we stop when the sand settles at its origin point.
+            if cave.drop_sand().unwrap() == cave.sand_origin {
+                break;
+            }
+        }
+
+        cave.count_sand().into()
+    }
+}
+
+impl Simulated for Day14 {
+    fn simulation(cave: &Cave, part: Part) -> Box<dyn Simulation> {
+        let mut cave = cave.clone();
+
+        if part == Part::Two {
+            cave.floor = Some(cave.rock_bottom + 2);
+        }
+
+        Box::new(cave)
+    }
+}
+
+// Paths of rock turning a corner every so often, spread out under where the
+// sand comes in, which gets wider and deeper with `size`.
+//
+// Sand can only pile up half as high as the rock under it is wide, so keeping
+// the rock that far below the top means the sand never gets stuck at the
+// opening in part one.
+impl Generated for Day14 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        let spread = 10 + size as i64 / 2;
+        let (top, bottom) = (spread + 2, spread + 12 + size as i64 * 3 / 2);
+
+        (0..std::cmp::max(1, size * 3 / 2))
+            .map(|_| {
+                let (mut x, mut y) = (500 + rng.range(-spread, spread), rng.range(top, bottom));
+                let mut points = vec![format!("{x},{y}")];
+                let horizontal_first = rng.one_in(2);
+
+                for turn in 0..rng.range(1, 5) {
+                    let length = rng.range(1, 10) * if rng.one_in(2) { 1 } else { -1 };
+
+                    if (turn % 2 == 0) == horizontal_first {
+                        x = (x + length).clamp(500 - spread, 500 + spread);
+                    } else {
+                        y = (y + length).clamp(top, bottom);
+                    }
+
+                    points.push(format!("{x},{y}"));
+                }
+
+                points.join(" -> ")
+            })
+            .collect()
+    }
+}
+
+pub type Loc = Point2<usize>;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum Material { Rock, Sand }
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Cave {
+    // Sand falls from here
+    sand_origin: Loc,
+
+    // Lowest piece of rock found in the scan
+    rock_bottom: usize,
+
+    // If we're dealing with a model of a cave that has a floor,
+    // this is the level it's at (rock_bottom+2)
+    floor: Option<usize>,
+
+    // Keep track of things.
+    // This contains each location that has something we care about;
+    // rock, and sand.
+    stuff: HashMap<Loc, Material>,
+}
+
+impl Cave {
+    pub fn process_scan(sand_origin: Loc, has_floor: bool, input: &[String]) -> Result<Self, ParseError> {
+        let stuff: HashMap<Loc, Material> = parse_lines(input, Self::get_rocks_from_scan_line)?
+            .into_iter()
+            // Each line produces a set of rock locations,
+            // which we aggregate here into a larger set
+            .flatten()
+            // Pair all those rock locations with the material
+            .map(|loc| (loc, Material::Rock))
+            // And produce the final map of loc => material
+            .collect();
+
+        // Locate the furthest down location with rock
+        let rock_bottom = stuff
+            .iter()
+            .filter(|(_, m)| **m == Material::Rock)
+            .fold(0, |acc, (loc, _)| {
+                if acc > loc.y { acc } else { loc.y }
+            });
+
+        // If this cave model has a floor, derive it from rock_bottom
+        let floor = if has_floor { Some(rock_bottom + 2) } else { None };
+
+        Ok(Self {
+            sand_origin,
+            rock_bottom,
+            floor,
+            stuff,
+        })
+    }
+
+    fn get_rocks_from_scan_line(input: &str) -> Result<HashSet<Loc>, ParseError> {
+        let raw_locs: Vec<&str> = input
+            // Split the line into locations
+            .split(" -> ")
+            .collect();
+
+        let locs: Vec<Loc> = raw_locs
+            .iter()
+            .map(|raw_loc| {
+                // Convert a raw str location into a Loc
+                let (x_raw, y_raw) = raw_loc
+                    .split_once(",")
+                    .ok_or_else(|| ParseError::in_line(input, raw_loc, "a location like 498,4"))?;
+
+                Ok(Point2::new(parse_number(input, x_raw)?, parse_number(input, y_raw)?))
+            })
+            // And collect that into a list of locations
+            .collect::<Result<Vec<Loc>, ParseError>>()?;
+
+        // Segments have to be straight, or we'd not know which rocks to fill in
+        for (i, pair) in locs.windows(2).enumerate() {
+            let (l1, l2) = (pair[0], pair[1]);
+
+            if l1.x != l2.x && l1.y != l2.y {
+                return Err(ParseError::in_line(
+                    input,
+                    raw_locs[i + 1],
+                    "a location in a straight line from the previous one",
+                ));
+            }
+        }
+
+        // This bit is kinda complex. Given the description, all segments
+        // are straight lines, so either their X or their Y coords will match.
+        // So from this, we technically iterate over every set of 2 locs as
+        // we move through the list (note the range stops 1 off the end)
+        Ok((0..(locs.len() - 1))
+            // Map index to a pair of locations
+            .map(|i| (locs[i], locs[i+1]))
+            .flat_map(|(Point2 { x: l1_x, y: l1_y }, Point2 { x: l2_x, y: l2_y })| {
+                // For this pair of locations, produce a set of distinct locations
+                // from the first up to and including the last
+                // Since it's flat_map, the contents of set of locations are
+                // added to that from previous pairs in the list, and any dupes
+                // removed as we go
+                if l1_x != l2_x {
+                    // Implied that y components are equal
+                    let x_range = if l1_x < l2_x { l1_x..=l2_x } else { l2_x..=l1_x };
+                    x_range.map(|x| Point2::new(x, l1_y)).collect::<HashSet<Loc>>()
+                } else {
+                    // x components are equal, implied y components are not equal
+                    let y_range = if l1_y < l2_y { l1_y..=l2_y } else { l2_y..=l1_y };
+                    y_range.map(|y| Point2::new(l1_x, y)).collect::<HashSet<Loc>>()
+                }
+            })
+            // Collect it into a single set of locations and return that,
+            // we now have a comprehensive list of all the locations with rock
+            // described by this scanner line.
+            .collect())
+    }
+
+
+    // Check whether a given location has anything in it at the moment.
+    // If this cave has a floor, this will return true for any location where
+    // the y component >= self.floor
+    fn is_vacant(&self, loc: &Loc) -> bool {
+        if let Some(floor_level) = self.floor {
+            if loc.y >= floor_level {
+                return false;
+            }
+        }
+
+        !self.stuff.contains_key(loc)
+    }
+
+    // Tries the 3 possible locations underneath the given location
+    // and returns the first one that's vacant, or None if none are.
+    // Order: directly down, down left, down right
+    fn get_available_loc_under(&self, loc: Loc) -> Option<Loc> {
+        let Point2 { x: origin_x, y: origin_y } = loc;
+
+        let locs_to_try = [
+            Point2::new(origin_x, origin_y + 1),
+            Point2::new(origin_x - 1, origin_y + 1),
+            Point2::new(origin_x + 1, origin_y +1)
+        ];
+
+        locs_to_try
+            .iter()
+            .find(|l| self.is_vacant(l))
+            .cloned()
+    }
+
+    // Drops one unit of sand from self.sand_origin
+    // Possibly mutates self.stuff by adding the resting location mapped to
+    // sand.
+    // Returns the resting location or None if it dropped into the void.
+    pub fn drop_sand(&mut self) -> Option<Loc> {
+        let mut current_loc = self.sand_origin;
+
+        while let Some(next_loc) = self.get_available_loc_under(current_loc) {
+            if next_loc.y > self.rock_bottom && self.floor.is_none() {
+                // Fell beyond the lowest rock, not settling anywhere
+                return None;
+            }
+
+            current_loc = next_loc;
+        }
+
+        // current loc then is sand to be added to stuff.
+        self.stuff.insert(current_loc.clone(), Material::Sand);
+        Some(current_loc)
+    }
+
+    // Counts the number sand items that have settled in the cave
+    pub fn count_sand(&self) -> usize {
+        self.stuff.iter().filter(|(_, m)| **m == Material::Sand).count()
+    }
+}
+
+// A step is one unit of sand coming to rest
+impl Simulation for Cave {
+    fn step(&mut self) -> bool {
+        // With a floor, it all ends when the sand's piled up to the origin
+        if self.stuff.contains_key(&self.sand_origin) {
+            return false;
+        }
+
+        self.drop_sand().is_some()
+    }
+
+    fn render_frame(&self) -> Frame {
+        let loc = |l: Loc| (l.x as isize, l.y as isize);
+
+        let mut cells = vec![(loc(self.sand_origin), '+')];
+
+        cells.extend(self.stuff.iter().map(|(l, material)| {
+            (loc(*l), if *material == Material::Rock { '#' } else { 'o' })
+        }));
+
+        // The floor goes on forever, so only draw it as far as there's anything above it
+        if let Some(floor_level) = self.floor {
+            let min_x = cells.iter().map(|((x, _), _)| *x).min().unwrap();
+            let max_x = cells.iter().map(|((x, _), _)| *x).max().unwrap();
+
+            cells.extend(((min_x - 1)..=(max_x + 1)).map(|x| ((x, floor_level as isize), '#')));
+        }
+
+        Frame::draw(cells, '.')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn locs(locs: &[(usize, usize)]) -> HashSet<Loc> {
+        locs.iter().map(|l| Point2::from(*l)).collect()
+    }
+
+    #[test]
+    fn test_cave_get_rocks_from_scan_line() {
+
+        assert_eq!(
+            Cave::get_rocks_from_scan_line("498,4 -> 498,6 -> 496,6").unwrap(),
+            locs(&[
+                (498, 4), (498, 5), (498, 6),
+                (497, 6), (496,6)
+            ]),
+        );
+
+        assert_eq!(
+            Cave::get_rocks_from_scan_line("503,4 -> 502,4 -> 502,9 -> 494,9").unwrap(),
+            locs(&[
+                (503, 4), (502, 4),
+                (502, 5), (502, 6), (502, 7), (502, 8), (502, 9),
+                (501, 9), (500, 9), (499, 9), (498, 9), (497, 9), (496, 9), (495, 9), (494, 9),
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_cave_scan_errors() {
+        let err = Cave::get_rocks_from_scan_line("498,4 -> 498,x").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (13, "x"));
+
+        let err = Cave::get_rocks_from_scan_line("498,4 -> 500,6").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (9, "500,6"));
+
+        let err = Cave::process_scan(Point2::new(500, 0), false, &["1,1".to_string(), "2".to_string()]).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 0));
+    }
+
+    #[test]
+    fn test_cave_process_scan() {
+        let input = vec![
+            "498,4 -> 498,6 -> 496,6".to_string(),
+            "503,4 -> 502,4 -> 502,9 -> 494,9".to_string(),
+        ];
+
+        assert_eq!(
+            Cave::process_scan(Point2::new(500, 0), false, &input).unwrap(),
+            Cave {
+                sand_origin: Point2::new(500, 0),
+                rock_bottom: 9,
+                floor: None,
+                stuff: HashMap::from([
+                    (Point2::new(498, 4), Material::Rock),
+                    (Point2::new(498, 5), Material::Rock),
+                    (Point2::new(498, 6), Material::Rock),
+                    (Point2::new(497, 6), Material::Rock),
+                    (Point2::new(496, 6), Material::Rock),
+                    (Point2::new(503, 4), Material::Rock),
+                    (Point2::new(502, 4), Material::Rock),
+                    (Point2::new(502, 5), Material::Rock),
+                    (Point2::new(502, 6), Material::Rock),
+                    (Point2::new(502, 7), Material::Rock),
+                    (Point2::new(502, 8), Material::Rock),
+                    (Point2::new(502, 9), Material::Rock),
+                    (Point2::new(501, 9), Material::Rock),
+                    (Point2::new(500, 9), Material::Rock),
+                    (Point2::new(499, 9), Material::Rock),
+                    (Point2::new(498, 9), Material::Rock),
+                    (Point2::new(497, 9), Material::Rock),
+                    (Point2::new(496, 9), Material::Rock),
+                    (Point2::new(495, 9), Material::Rock),
+                    (Point2::new(494, 9), Material::Rock),
+                ]),
+            }
+        );
+
+        assert_eq!(
+            Cave::process_scan(Point2::new(500, 0), true, &input).unwrap(),
+            Cave {
+                sand_origin: Point2::new(500, 0),
+                rock_bottom: 9,
+                floor: Some(11),
+                stuff: HashMap::from([
+                    (Point2::new(498, 4), Material::Rock),
+                    (Point2::new(498, 5), Material::Rock),
+                    (Point2::new(498, 6), Material::Rock),
+                    (Point2::new(497, 6), Material::Rock),
+                    (Point2::new(496, 6), Material::Rock),
+                    (Point2::new(503, 4), Material::Rock),
+                    (Point2::new(502, 4), Material::Rock),
+                    (Point2::new(502, 5), Material::Rock),
+                    (Point2::new(502, 6), Material::Rock),
+                    (Point2::new(502, 7), Material::Rock),
+                    (Point2::new(502, 8), Material::Rock),
+                    (Point2::new(502, 9), Material::Rock),
+                    (Point2::new(501, 9), Material::Rock),
+                    (Point2::new(500, 9), Material::Rock),
+                    (Point2::new(499, 9), Material::Rock),
+                    (Point2::new(498, 9), Material::Rock),
+                    (Point2::new(497, 9), Material::Rock),
+                    (Point2::new(496, 9), Material::Rock),
+                    (Point2::new(495, 9), Material::Rock),
+                    (Point2::new(494, 9), Material::Rock),
+                ]),
+            }
+        );
+    }
+
+    #[test]
+    fn test_drop_sand_without_floor() {
+        let input = vec![
+            "498,4 -> 498,6 -> 496,6".to_string(),
+            "503,4 -> 502,4 -> 502,9 -> 494,9".to_string(),
+        ];
+
+        let mut cave = Cave::process_scan(Point2::new(500, 0), false, &input).unwrap();
+
+        assert_eq!(cave.drop_sand().unwrap(), Point2::new(500, 8));
+        assert_eq!(cave.drop_sand().unwrap(), Point2::new(499, 8));
+        assert_eq!(cave.drop_sand().unwrap(), Point2::new(501, 8));
+        assert_eq!(cave.drop_sand().unwrap(), Point2::new(500, 7));
+        assert_eq!(cave.drop_sand().unwrap(), Point2::new(498, 8));
+
+        assert_eq!(cave.count_sand(), 5);
+
+        // Drop sand until it no longer settles anywhere
+        loop {
+            if cave.drop_sand().is_none() { break; }
+        }
+
+        assert_eq!(cave.count_sand(), 24);
+    }
+
+    #[test]
+    fn test_drop_sand_with_floor() {
+        let input = vec![
+            "498,4 -> 498,6 -> 496,6".to_string(),
+            "503,4 -> 502,4 -> 502,9 -> 494,9".to_string(),
+        ];
+
+        let mut cave = Cave::process_scan(Point2::new(500, 0), true, &input).unwrap();
+
+        // Drop sand until we clog the origin
+        loop {
+            if cave.drop_sand().unwrap() == cave.sand_origin {
+                break;
+            }
+        }
+
+        assert_eq!(cave.count_sand(), 93);
+    }
+
+    #[test]
+    fn test_simulation() {
+        let input = vec![
+            "498,4 -> 498,6 -> 496,6".to_string(),
+            "503,4 -> 502,4 -> 502,9 -> 494,9".to_string(),
+        ];
+
+        let mut cave = Day14::simulation(&Day14::parse(&input).unwrap(), Part::One);
+        (0..5).for_each(|_| assert!(cave.step()));
+
+        assert_eq!(
+            cave.render_frame().cells.to_string(),
+            [
+                "......+...",
+                "..........",
+                "..........",
+                "..........",
+                "....#...##",
+                "....#...#.",
+                "..###...#.",
+                "......o.#.",
+                "....oooo#.",
+                "#########.",
+            ].join("\n"),
+        );
+
+        // 24 units come to rest, then the rest falls into the abyss
+        assert_eq!(common::frames(cave.as_mut()).count(), 1 + 19);
+
+        let mut cave = Day14::simulation(&Day14::parse(&input).unwrap(), Part::Two);
+        assert_eq!(common::frames(cave.as_mut()).count(), 1 + 93);
+    }
+}
@@ -0,0 +1,434 @@
+use std::ops::RangeInclusive;
+use std::collections::HashSet;
+use std::cmp::{min,max};
+
+use common::{parse_lines, parse_number, Answer, Generated, NumberParam, Param, ParseError, Params, Point2, Progress, Rng, Solution};
+
+pub struct Day15;
+
+const ROW: NumberParam = NumberParam::new("row", 2000000, "The row part one counts the covered positions of");
+const LIMIT: NumberParam = NumberParam::new("limit", 4000000, "How far across and down part two looks for the distress beacon").at_least(0);
+
+impl Solution for Day15 {
+    type Input = Scan;
+
+    // The example only goes up to 20, so it looks at different rows
+    const PARAMS: &'static [Param] = &[ROW.param(), LIMIT.param()];
+
+    fn parse(input: &[String]) -> Result<Scan, ParseError> {
+        Self::parse_with(input, &Params::new())
+    }
+
+    fn parse_with(input: &[String], params: &Params) -> Result<Scan, ParseError> {
+        Ok(Scan {
+            sensors: parse_lines(input, parse_sensor_line)?,
+            row: params.number(ROW) as isize,
+            limit: params.number(LIMIT) as isize,
+        })
+    }
+
+    fn part_one(scan: &Scan) -> Answer {
+        let sensors = &scan.sensors;
+        let y = scan.row;
+
+        let ranges = sensors
+            .iter()
+            .filter(|s| s.intersects_y(y))
+            .map(|s| s.coverage_at_y(y))
+            .collect::<Vec<RangeInclusive<isize>>>();
+
+        let simplified_ranges = simplify_ranges(ranges);
+
+        let beacons_in_ranges = sensors
+            .iter()
+            .filter(|(_, b)| {
+                if b.y != y {
+                    false
+                } else {
+                    simplified_ranges.iter().any(|r| r.contains(&b.x))
+                }
+            })
+            .map(|(_, b)| b.x)
+            .collect::<HashSet<isize>>();
+
+        let coverage = simplified_ranges
+            .iter()
+            .fold(0, |acc, r| acc + r.end() - r.start() + 1) - beacons_in_ranges.len() as isize;
+
+        coverage.into()
+    }
+
+    fn part_two(scan: &Scan) -> Answer {
+        let sensors = &scan.sensors;
+        let limit = scan.limit;
+
+        // So this feels like a naive approach, but I'm betting on my implementation
+        // of part one being efficient enough that I can get away with running it up to
+        // 4 million times.
+        // Turns out it was a decent bet, because it found the solution for my input
+        // after about 26 seconds,
+        // somewhere between 3.3M and 3.4M (~82.5% into the search space), running on a 9 year old
+        // machine on a single core.
+        //
+        // Heh, a cool exercise could be to split up this search across more CPU cores.
+        let mut progress = Progress::new("scanning rows", limit as u64 + 1);
+
+        let target_y = (0..=limit).find(|y| {
+            if y % 10000 == 0 {
+                progress.set(*y as u64);
+            }
+
+            let ranges = sensors
+                .iter()
+                .filter(|s| s.intersects_y(*y))
+                .map(|s| s.coverage_at_y(*y))
+                .collect::<Vec<RangeInclusive<isize>>>();
+
+            let simplified_ranges = simplify_ranges(ranges);
+
+            ! simplified_ranges.iter().any(|r| r.contains(&0) && r.contains(&limit))
+        });
+
+        // Every row covered all the way across: there's nowhere for it to be
+        let Some(target_y) = target_y else {
+            return Answer::Unsolved;
+        };
+
+        drop(progress);
+        common::debug!("distress beacon is on row {target_y}");
+
+        // What's one more time?
+        let mut simplified_ranges = simplify_ranges(
+            sensors
+                .iter()
+                .filter(|s| s.intersects_y(target_y))
+                .map(|s| s.coverage_at_y(target_y))
+                .collect::<Vec<RangeInclusive<isize>>>()
+        ).iter().map(|r| *r.start()..=*r.end()).collect::<Vec<RangeInclusive<isize>>>();
+
+        simplified_ranges.sort_by(|ar, br| ar.start().cmp(br.start()));
+
+        // Just past the first range, unless that leaves a gap before it
+        let target_x = match simplified_ranges.first() {
+            Some(r) if *r.start() <= 0 => r.end() + 1,
+            _ => 0,
+        };
+
+        // Always 4000000, even when searching a smaller area like the example's
+        let tuning_freq = target_x * 4000000 + target_y;
+
+        tuning_freq.into()
+    }
+}
+
+// Made for the real input's row and limit. Four sensors out past the corners
+// of the search area cover all of it apart from the one spot the distress
+// beacon is at, and the rest, one for every few of `size`, stop short of it.
+impl Generated for Day15 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        let limit = 4000000;
+        let distress = Point2::new(rng.range(0, limit) as isize, rng.range(0, limit) as isize);
+
+        // Each of these reaches to a diagonal through the distress beacon,
+        // with their own beacon just next to it
+        let reach = rng.range(limit + 1, limit + 500000) as isize;
+        let mut sensors: Vec<Sensor> = vec![
+            (distress + Point2::new(reach, reach), distress + Point2::new(1, 0)),
+            (distress - Point2::new(reach, reach), distress - Point2::new(1, 0)),
+            (distress + Point2::new(reach, -reach), distress + Point2::new(1, 0)),
+            (distress - Point2::new(reach, -reach), distress - Point2::new(1, 0)),
+        ];
+
+        while sensors.len() < 4 + size / 4 {
+            let sensor = Point2::new(rng.range(0, limit) as isize, rng.range(0, limit) as isize);
+            let distance = sensor.manhattan(distress);
+
+            if distance < 2 {
+                continue;
+            }
+
+            let radius = rng.range(1, distance as i64 - 1) as isize;
+            let dx = rng.range(-radius as i64, radius as i64) as isize;
+            let dy = (radius - dx.abs()) * if rng.one_in(2) { 1 } else { -1 };
+
+            sensors.push((sensor, sensor + Point2::new(dx, dy)));
+        }
+
+        rng.shuffle(&mut sensors);
+
+        sensors
+            .iter()
+            .map(|(s, b)| format!("Sensor at x={}, y={}: closest beacon is at x={}, y={}", s.x, s.y, b.x, b.y))
+            .collect()
+    }
+}
+
+pub type Loc = Point2<isize>;
+pub type Sensor = (Loc, Loc);
+
+pub struct Scan {
+    pub sensors: Vec<Sensor>,
+    // Row to count the covered positions on
+    pub row: isize,
+    // Highest x and y the distress beacon can be at
+    pub limit: isize,
+}
+
+pub trait SensorCalculations {
+    fn beacon_distance(&self) -> isize;
+    fn coverage_at_y(&self, y: isize) -> RangeInclusive<isize>;
+    fn intersects_y(&self, y: isize) -> bool;
+}
+
+impl SensorCalculations for Sensor {
+    fn beacon_distance(&self) -> isize {
+        let (sensor, beacon) = self;
+        sensor.manhattan(*beacon)
+    }
+
+    fn coverage_at_y(&self, y: isize) -> RangeInclusive<isize> {
+        let (sensor, _) = self;
+        let half_width = self.beacon_distance() - (y - sensor.y).abs();
+
+        (sensor.x - half_width)..=(sensor.x + half_width)
+    }
+
+    fn intersects_y(&self, y: isize) -> bool {
+        let (sensor, _) = self;
+        (y - sensor.y).abs() <= self.beacon_distance()
+    }
+}
+
+/// Simplifies a set of ranges, combining overlapping ranges into one larger
+/// range, producing a set of the minimum number of ranges required to represent
+/// the input ranges.
+///
+/// There probably is some very common algorithm for this, but I don't know it,
+/// and I'm pretty proud of what I came up with here.
+pub fn simplify_ranges(in_ranges: Vec<RangeInclusive<isize>>) -> HashSet<RangeInclusive<isize>> {
+    // Compare ranges to tuples, easier to work with in this function.
+    let mut ranges: Vec<(isize, isize)> = in_ranges
+        .iter()
+        .map(|r| (*r.start(), *r.end()))
+        .collect();
+
+    let mut simplified_ranges: HashSet<RangeInclusive<isize>> = HashSet::new();
+
+    // Once we run out of ranges, we're done. A row no sensor reaches never
+    // had any.
+    while !ranges.is_empty() {
+        // Step : sort our ranges by start. This ensures overlapping ranges
+        // will follow one another
+        ranges.sort_by(|(a_s, _), (bs, _)| { a_s.cmp(bs) });
+
+        let combined = ranges
+            .iter()
+            // Starting with the first range, grow the range until we run out of
+            // overlapping ranges
+            // Hm, this could be improved by not checking any further ranges
+            // after first one that doesn't overlap.
+            .fold(ranges[0], |acc, r| {
+                let (a_s, ae) = acc;
+                let (rs, re) = *r;
+
+                if (rs <= a_s && a_s <= re) || (rs <= ae && ae <= re) {
+                    (min(a_s, rs), max(ae, re))
+                } else {
+                    // TODO in this case we should essentially exit from the
+                    // fold.
+                    // Perhaps it can be achieved with `scan`?
+                    (a_s, ae)
+                }
+            });
+
+        // Remove any ranges that are overlapped by the combined one just found
+        ranges = ranges
+            .iter()
+            .filter(|(rs, _) | {
+                // Only keep ranges not overlapping with combined range
+                let (cs, ce) = combined;
+                ce < *rs || cs > *rs
+            })
+            .map(|r| *r)
+            .collect();
+
+        let (cs, ce) = combined;
+        simplified_ranges.insert(cs..=ce);
+    }
+
+    simplified_ranges
+}
+
+pub fn parse_sensor_line(input: &str) -> Result<Sensor, ParseError> {
+    let split: Vec<&str> = input.split(" ").collect();
+
+    // Disgusting parsing routine time
+    // Split by spaces, we're interested in the following number items
+    let values = vec![2, 3, 8, 9]
+        .iter()
+        .map(|i| {
+            let item = split
+                .get(*i)
+                .ok_or_else(|| ParseError::end_of_line(input, "a coordinate like x=2"))?;
+
+            // Then for each of these items, remove cruft we don't want and keep
+            // only the bit that can be parsed as an isize: what's after the `=`,
+            // minus the `,` (sensor x, beacon x) or `:` (sensor y) after it.
+            let value = item
+                .split_once("=")
+                .map(|(_, v)| v.trim_end_matches([',', ':']))
+                .ok_or_else(|| ParseError::in_line(input, item, "a coordinate like x=2"))?;
+
+            parse_number(input, value)
+        })
+        .collect::<Result<Vec<isize>, ParseError>>()?;
+
+    Ok((Point2::new(values[0], values[1]), Point2::new(values[2], values[3])))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn sensor(at: (isize, isize), beacon: (isize, isize)) -> Sensor {
+        (at.into(), beacon.into())
+    }
+
+    #[test]
+    fn test_sensor_calculates_beacon_distance() {
+        assert_eq!(sensor((2, 18), (-2, 15)).beacon_distance(), 7);
+        assert_eq!(sensor((9, 16), (10, 16)).beacon_distance(), 1);
+    }
+
+    #[test]
+    // Out of reach rows give an empty range on purpose
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_sensor_coverage_at_y() {
+        let s = sensor((8, 7), (2, 10));
+
+        assert_eq!(s.coverage_at_y(-3), 9..=7);
+        assert_eq!(s.coverage_at_y(-2), 8..=8);
+        assert_eq!(s.coverage_at_y(-1), 7..=9);
+        assert_eq!(s.coverage_at_y(0), 6..=10);
+        assert_eq!(s.coverage_at_y(1), 5..=11);
+        assert_eq!(s.coverage_at_y(2), 4..=12);
+        assert_eq!(s.coverage_at_y(3), 3..=13);
+        assert_eq!(s.coverage_at_y(4), 2..=14);
+        assert_eq!(s.coverage_at_y(5), 1..=15);
+        assert_eq!(s.coverage_at_y(6), 0..=16);
+        assert_eq!(s.coverage_at_y(7), -1..=17);
+        assert_eq!(s.coverage_at_y(8), 0..=16);
+        assert_eq!(s.coverage_at_y(9), 1..=15);
+        assert_eq!(s.coverage_at_y(10), 2..=14);
+        assert_eq!(s.coverage_at_y(11), 3..=13);
+        assert_eq!(s.coverage_at_y(12), 4..=12);
+        assert_eq!(s.coverage_at_y(13), 5..=11);
+        assert_eq!(s.coverage_at_y(14), 6..=10);
+        assert_eq!(s.coverage_at_y(15), 7..=9);
+        assert_eq!(s.coverage_at_y(16), 8..=8);
+        assert_eq!(s.coverage_at_y(17), 9..=7);
+    }
+
+    #[test]
+    fn test_sensor_intersects_y() {
+        let s = sensor((8, 7), (2, 10));
+
+        assert!( ! s.intersects_y(-3));
+
+        for i in -2..=16 {
+            println!("intersects {i}: {}", s.intersects_y(i));
+            assert!(s.intersects_y(i));
+        }
+
+        assert!( ! s.intersects_y(17));
+    }
+
+    #[test]
+    fn test_parse_sensor_line() {
+        assert_eq!(
+            parse_sensor_line(
+                "Sensor at x=2300471, y=2016823: closest beacon is at x=2687171, y=2822745",
+            ).unwrap(),
+            sensor((2300471, 2016823), (2687171, 2822745)),
+        );
+
+        assert_eq!(
+            parse_sensor_line(
+                "Sensor at x=-471, y=2016823: closest beacon is at x=2687171, y=2822745",
+            ).unwrap(),
+            sensor((-471, 2016823), (2687171, 2822745)),
+        );
+
+        let err = parse_sensor_line("Sensor at x=2, y=1o: closest beacon is at x=3, y=4").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (17, "1o"));
+
+        let err = parse_sensor_line("Sensor at x=2, y=10").unwrap_err();
+        assert_eq!(err.expected, "a coordinate like x=2");
+    }
+
+    #[test]
+    fn test_simplify_ranges() {
+        assert_eq!(
+            simplify_ranges(vec![
+                0..=4,
+                2..=6,
+            ]),
+            HashSet::from([
+                0..=6
+            ])
+        );
+
+        assert_eq!(
+            simplify_ranges(vec![
+                2..=6,
+                0..=4,
+            ]),
+            HashSet::from([
+                0..=6
+            ]),
+        );
+
+        assert_eq!(
+            simplify_ranges(vec![
+                8..=10,
+                10..=13,
+
+                2..=6,
+                0..=4,
+            ]),
+            HashSet::from([
+                0..=6,
+                8..=13,
+            ]),
+        );
+
+        assert_eq!(
+            simplify_ranges(vec![
+               4..=6, // contained in 3..=25, removed
+               8..=10, // contained in 3..=25, removed
+
+               -1..=2, // Overlaps nothing
+               3..=25, // Contains: 4..=6, 8..=10
+               22..=30, // overlaps 3..=25, so extends to 3..=30
+               42..=69, // overlaps nothing
+            ]),
+            HashSet::from([
+                -1..=2,
+                3..=30,
+                42..=69,
+            ]),
+        );
+
+        assert_eq!(simplify_ranges(vec![]), HashSet::new());
+    }
+    #[test]
+    fn test_part_two_nowhere_left() {
+        // One sensor covering the whole area, and one with nothing in it at all
+        let covered = Scan { sensors: vec![sensor((1, 1), (7, 1))], row: 10, limit: 3 };
+        assert_eq!(Day15::part_two(&covered), Answer::Unsolved);
+
+        let empty = Scan { sensors: vec![], row: 10, limit: 3 };
+        assert_eq!(Day15::part_two(&empty), Answer::Number(0));
+    }
+}
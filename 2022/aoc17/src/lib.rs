@@ -0,0 +1,436 @@
+use common::{Answer, Frame, Generated, Grid, NumberParam, Param, ParseError, Params, Part, Rng, Simulated, Simulation, Solution};
+
+// That's right we're gonna do bitwise stuff for this one.
+const SHAPES: &[[u16; 4]; 5] = &[
+    [
+        0b000000000u16,
+        0b000000000u16,
+        0b000000000u16,
+        0b000111100u16,
+    ],
+    [
+        0b000000000u16,
+        0b000010000u16,
+        0b000111000u16,
+        0b000010000u16,
+    ],
+    [
+        0b000000000u16,
+        0b000001000u16,
+        0b000001000u16,
+        0b000111000u16,
+    ],
+    [
+        0b000100000u16,
+        0b000100000u16,
+        0b000100000u16,
+        0b000100000u16,
+    ],
+    [
+        0b000000000u16,
+        0b000000000u16,
+        0b000110000u16,
+        0b000110000u16,
+    ],
+];
+
+const WALL: u16 = 0b100000001u16;
+const FLOOR: u16 = 0b111111111u16;
+
+// Rows of the tower shown in a frame, counting down from just above its top
+const VIEW_HEIGHT: usize = 40;
+
+pub struct Day17;
+
+const ROCKS: NumberParam = NumberParam::new("rocks", 2022, "How many rocks part one drops").at_least(0);
+
+impl Solution for Day17 {
+    type Input = Jets;
+
+    const PARAMS: &'static [Param] = &[ROCKS.param()];
+
+    fn parse(input: &[String]) -> Result<Jets, ParseError> {
+        Self::parse_with(input, &Params::new())
+    }
+
+    fn parse_with(input: &[String], params: &Params) -> Result<Jets, ParseError> {
+        let pattern = input.first().map(|l| l.as_str()).unwrap_or("");
+
+        if pattern.is_empty() {
+            return Err(ParseError::end_of_line(pattern, "a jet pattern of `<` and `>`"));
+        }
+
+        if let Some(column) = pattern.chars().position(|c| c != '<' && c != '>') {
+            return Err(ParseError::at_char(pattern, column, "`<` or `>`"));
+        }
+
+        Ok(Jets { pattern: pattern.to_string(), rocks: params.number(ROCKS) as usize })
+    }
+
+    fn part_one(jets: &Jets) -> Answer {
+        let mut cave = Cave::new(jets.pattern.clone());
+
+        for _ in 0..jets.rocks {
+            cave.drop_rock();
+        }
+
+        cave.stack_height.into()
+    }
+}
+
+impl Simulated for Day17 {
+    // Part two's trillion rocks would take a while to watch, so both stop
+    // where part one does
+    fn simulation(jets: &Jets, _part: Part) -> Box<dyn Simulation> {
+        Box::new(RockFall { cave: Cave::new(jets.pattern.clone()), rocks_left: jets.rocks })
+    }
+}
+
+// Runs of jets blowing the same way, a hundred or so for every bit of `size`
+impl Generated for Day17 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        let length = std::cmp::max(1, size * 100);
+        let mut pattern = String::with_capacity(length);
+
+        while pattern.len() < length {
+            let jet = *rng.pick(&['<', '>']);
+            let run = std::cmp::min(rng.range(1, 4) as usize, length - pattern.len());
+            pattern.extend(std::iter::repeat_n(jet, run));
+        }
+
+        vec![pattern]
+    }
+}
+
+/// Using bitwise logic this checks whether a proposed position of a rock
+/// overlaps at all with existing material in this row.
+// The jet pattern, and how many rocks fall through it
+pub struct Jets {
+    pattern: String,
+    rocks: usize,
+}
+
+fn collides(existing: u16, rock: u16) -> bool {
+    existing & rock != 0
+}
+
+fn push_rock(rock: &Vec<u16>, jet: char) -> Vec<u16> {
+
+    let would_collide = rock
+        .iter()
+        .any(|layer| {
+            collides(
+                WALL,
+                match jet {
+                    '<' => *layer << 1,
+                    '>' => *layer >> 1,
+                    _ => *layer,
+                }
+            )
+        });
+
+    if !would_collide {
+        rock
+            .iter()
+            .map(|layer| {
+                match jet {
+                    '<' => *layer << 1,
+                    '>' => *layer >> 1,
+                    _ => *layer
+                }
+            })
+            .collect()
+    } else {
+        rock.clone()
+    }
+}
+
+pub struct Cave {
+    jet_pattern: String,
+    jet_index: usize,
+    jet_count: usize,
+    rock_index: usize,
+    stack: Vec<u16>,
+    stack_height: usize,
+}
+
+impl Cave {
+    pub fn new(jet_pattern: String) -> Self {
+        Self {
+            jet_pattern,
+            jet_index: 0,
+            jet_count: 0,
+            rock_index: 0,
+            stack: vec![FLOOR],
+            stack_height: 0,
+        }
+    }
+
+    fn next_rock(&mut self) -> Vec<u16> {
+        let rock = self.peek_next_rock();
+        self.rock_index = (self.rock_index + 1) % 5;
+        rock
+    }
+
+    fn peek_next_rock(&self) -> Vec<u16> {
+        let mut rock = vec![];
+        for layer in SHAPES[self.rock_index] {
+            rock.push(layer)
+        }
+
+        rock
+    }
+
+    fn next_jet(&mut self) -> char {
+        let jet_char = self.jet_pattern.chars().nth(self.jet_index).unwrap();
+        self.jet_index = (self.jet_index + 1) % self.jet_pattern.chars().count();
+        self.jet_count += 1;
+        jet_char
+    }
+
+    fn jet_push_rock(&self, y: usize, jet: char, rock: &Vec<u16>) -> Vec<u16> {
+        // Iterate over the layers of the rock + layers of the stack matching its current
+        // position (or use walls if stack not that high yet),
+        // and see if it collides
+
+        let would_collide = rock.iter().rev().enumerate().any(|(i, rock_layer)| {
+            let existing_layer = *self.stack.get(y + i).unwrap_or(&WALL);
+            let shifted = match jet {
+                '<' => *rock_layer << 1,
+                '>' => *rock_layer >> 1,
+                _ => *rock_layer,
+            };
+            collides(existing_layer, shifted)
+        });
+
+        if would_collide {
+            rock.clone()
+        } else {
+            rock.iter().map(|l| {
+                match jet {
+                    '<' => *l << 1,
+                    '>' => *l >> 1,
+                    _ => *l
+                }
+            }).collect()
+        }
+    }
+
+    fn can_lower_rock(&self, y: usize, rock: &Vec<u16>) -> bool {
+        !rock.iter().rev().enumerate().any(|(i, rock_layer)| {
+          let existing_layer = *self.stack.get(y + i - 1).unwrap_or(&WALL);
+           collides(existing_layer, *rock_layer)
+        })
+    }
+
+    // Returns whether it cleared the stack
+    fn deposit_rock(&mut self, y: usize, rock: &Vec<u16>) {
+        let mut stack_y = y;
+
+        rock.iter().rev().for_each(|rock_layer| {
+            if *rock_layer == 0 {
+                return;
+            }
+
+            while self.stack.len() <= stack_y {
+                self.stack.push(WALL);
+                self.stack_height += 1;
+            }
+
+            self.stack[stack_y] |= *rock_layer;
+            stack_y += 1;
+        });
+    }
+
+    pub fn drop_rock(&mut self) {
+        let stack_top = self.stack.len() - 1;
+        let mut rock = self.next_rock();
+        let mut y = stack_top + 4;
+
+        loop {
+            let jet = self.next_jet();
+
+            rock = if y >= self.stack.len() {
+                push_rock(&rock, jet)
+            } else {
+                self.jet_push_rock(y, jet, &rock)
+            };
+
+            if self.can_lower_rock(y, &rock) {
+                // Lower the rock as it won't collide yet
+                y -= 1
+            } else {
+                self.deposit_rock(y, &rock);
+                return;
+            }
+        }
+    }
+}
+
+pub struct RockFall {
+    cave: Cave,
+    rocks_left: usize,
+}
+
+// A step is one rock coming to rest
+impl Simulation for RockFall {
+    fn step(&mut self) -> bool {
+        if self.rocks_left == 0 {
+            return false;
+        }
+
+        self.cave.drop_rock();
+        self.rocks_left -= 1;
+        true
+    }
+
+    // Only the top of the tower, where the rocks are landing. Every frame has
+    // the same size and origin, so the view scrolls up along with the tower.
+    fn render_frame(&self) -> Frame {
+        let stack = &self.cave.stack;
+        // Along with the gap of three rows each new rock starts out above
+        let top = std::cmp::max(stack.len() + 2, VIEW_HEIGHT - 1);
+
+        let cells = (top + 1 - VIEW_HEIGHT..=top)
+            .rev()
+            .flat_map(|y| {
+                let layer = *stack.get(y).unwrap_or(&WALL);
+
+                (0..9).rev().map(move |bit| match (y, bit) {
+                    (0, 0) | (0, 8) => '+',
+                    (0, _) => '-',
+                    (_, 0) | (_, 8) => '|',
+                    _ if layer & (1 << bit) != 0 => '#',
+                    _ => '.',
+                })
+            })
+            .collect();
+
+        Frame::new((0, 0), Grid::from_vec(9, cells))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn testing_works() {
+        assert_eq!(2,  1 + 1);
+    }
+
+    #[test]
+    fn test_next_rock() {
+        let jet_pattern_raw = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>".to_string();
+        let mut cave = Cave::new(jet_pattern_raw);
+
+        let rock = cave.next_rock();
+        assert_eq!(cave.rock_index, 1);
+        assert_eq!(rock, vec![
+            0b000000000u16,
+            0b000000000u16,
+            0b000000000u16,
+            0b000111100u16,
+        ]);
+
+        let rock = cave.next_rock();
+        assert_eq!(cave.rock_index, 2);
+        assert_eq!(rock, vec![
+            0b000000000u16,
+            0b000010000u16,
+            0b000111000u16,
+            0b000010000u16,
+        ]);
+
+        let rock = cave.next_rock();
+        assert_eq!(cave.rock_index, 3);
+        assert_eq!(rock, vec![
+            0b000000000u16,
+            0b000001000u16,
+            0b000001000u16,
+            0b000111000u16,
+        ]);
+
+        let rock = cave.next_rock();
+        assert_eq!(cave.rock_index, 4);
+        assert_eq!(rock, vec![
+            0b000100000u16,
+            0b000100000u16,
+            0b000100000u16,
+            0b000100000u16,
+        ]);
+
+        let rock = cave.next_rock();
+        assert_eq!(cave.rock_index, 0);
+        assert_eq!(rock, vec![
+            0b000000000u16,
+            0b000000000u16,
+            0b000110000u16,
+            0b000110000u16,
+        ]);
+
+        let rock = cave.next_rock();
+        assert_eq!(cave.rock_index, 1);
+        assert_eq!(rock, vec![
+            0b000000000u16,
+            0b000000000u16,
+            0b000000000u16,
+            0b000111100u16,
+        ]);
+    }
+
+    #[test]
+    fn test_drop_rock() {
+        let jet_pattern_raw = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>".to_string();
+        let mut cave = Cave::new(jet_pattern_raw);
+
+        assert_eq!(
+            cave.stack, vec![FLOOR]
+        );
+
+        cave.drop_rock();
+        assert_eq!(
+            cave.stack, vec![
+                FLOOR,
+                WALL | 0b000111100,
+            ]
+        );
+
+        cave.drop_rock();
+        assert_eq!(
+            cave.stack, vec![
+                FLOOR,
+                WALL | 0b000111100,
+                WALL | 0b000010000,
+                WALL | 0b000111000,
+                WALL | 0b000010000,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_part_one() {
+        let jet_pattern_raw = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>".to_string();
+        let mut cave = Cave::new(jet_pattern_raw);
+
+        for _ in 0..2022 {
+            cave.drop_rock();
+        }
+
+        assert_eq!(cave.stack.len() - 1, 3068);
+    }
+
+    #[test]
+    fn test_simulation() {
+        let jets = Day17::parse(&[">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>".to_string()]).unwrap();
+        let mut rocks = Day17::simulation(&jets, Part::One);
+
+        (0..2).for_each(|_| assert!(rocks.step()));
+
+        let frame = rocks.render_frame().cells.to_string();
+        let bottom: Vec<&str> = frame.lines().skip(VIEW_HEIGHT - 5).collect();
+
+        assert_eq!(bottom, vec!["|...#...|", "|..###..|", "|...#...|", "|..####.|", "+-------+"]);
+        assert_eq!(common::frames(rocks.as_mut()).count(), 1 + 2020);
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+
+use common::{parse_lines, parse_number, Answer, Generated, NumberParam, Param, ParseError, Params, Progress, Rng, Solution};
+
+pub struct Day20;
+
+const KEY: NumberParam = NumberParam::new("key", 811589153, "The decryption key part two multiplies every number by");
+const ROUNDS: NumberParam = NumberParam::new("rounds", 10, "How many times part two mixes the file").at_least(0);
+
+impl Solution for Day20 {
+    type Input = File;
+
+    const PARAMS: &'static [Param] = &[KEY.param(), ROUNDS.param()];
+
+    fn parse(input: &[String]) -> Result<File, ParseError> {
+        Self::parse_with(input, &Params::new())
+    }
+
+    fn parse_with(input: &[String], params: &Params) -> Result<File, ParseError> {
+        let values: Vec<isize> = parse_lines(input, |l| parse_number(l, l))?;
+
+        // Grove coordinates are counted from the 0
+        if !values.contains(&0) {
+            let last = input.len().saturating_sub(1);
+            let line = input.get(last).map(|l| l.as_str()).unwrap_or("");
+            return Err(ParseError::end_of_line(line, "a 0 somewhere in the file").on_line(last));
+        }
+
+        Ok(File {
+            values,
+            key: params.number(KEY) as isize,
+            rounds: params.number(ROUNDS) as usize,
+        })
+    }
+
+    fn part_one(file: &File) -> Answer {
+        let mut enc_file = EncFile::init(&file.values, 1);
+        enc_file.mix();
+
+        enc_file.grove_coord_sum().into()
+    }
+
+    fn part_two(file: &File) -> Answer {
+        let mut enc_file = EncFile::init(&file.values, file.key);
+        let mut progress = Progress::new("mixing", (file.rounds * file.values.len()) as u64);
+
+        (0..file.rounds).for_each(|_| {
+            enc_file.mix_reporting(&mut progress);
+        });
+
+        drop(progress);
+
+        enc_file.grove_coord_sum().into()
+    }
+}
+
+// About fifty numbers for every bit of `size`, drawn from a pool small enough that
+// plenty of them come up more than once. Only the one 0, though.
+impl Generated for Day20 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        // Lengths that go into 1000 would have every grove coordinate land on the 0
+        let length = size * 50 + rng.range(1, 49) as usize;
+        let pool: Vec<i64> = (0..std::cmp::max(1, length / 3)).map(|_| rng.range(-10000, 10000)).collect();
+
+        let mut values: Vec<i64> = (0..length - 1)
+            .map(|_| *rng.pick(&pool))
+            .map(|v| if v == 0 { 1 } else { v })
+            .collect();
+
+        values.insert(rng.below(length), 0);
+        values.iter().map(|v| v.to_string()).collect()
+    }
+}
+
+// The encrypted numbers, and how to decrypt them for part two
+pub struct File {
+    values: Vec<isize>,
+    key: isize,
+    rounds: usize,
+}
+
+// Absolute index, new pos
+type PosMove = (usize, usize);
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Entry {
+    pos: usize,
+    val: isize,
+}
+
+impl Entry {
+    fn calc_pos(&self, list_len: usize) -> usize {
+        let mut new_pos = (self.pos as isize + (self.val % (list_len as isize -1)))
+            % (list_len as isize - 1);
+
+        // Moving backwards onto the very start wraps around to the end instead, same as
+        // the puzzle example does with -2
+        new_pos = if new_pos < 0 || (new_pos == 0 && self.val < 0) {
+            new_pos - 1 + list_len as isize
+        } else {
+            new_pos
+        };
+
+        new_pos as usize
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct EncFile {
+    entries: Vec<Entry>,
+    len: usize,
+}
+
+impl EncFile {
+    pub fn init(values: &[isize], key: isize) -> Self {
+        EncFile {
+            entries: values
+                .iter()
+                .map(|v| v * key)
+                .enumerate()
+                .map(|(pos, val)| Entry { pos, val })
+                .collect(),
+            len: values.len()
+        }
+    }
+
+    pub fn mix(&mut self) {
+        self.mix_reporting(&mut Progress::new("mixing", self.len as u64));
+    }
+
+    // Ticks `progress` off for every number moved
+    fn mix_reporting(&mut self, progress: &mut Progress) {
+        (0..self.len).for_each(|ai| {
+            let map: HashMap<usize, usize> = self.entries
+                .iter()
+                .enumerate()
+                .map(|(i, e)| (e.pos, i))
+                .collect();
+
+            let old_pos = self.entries[ai].pos;
+            let new_pos = self.entries[ai].calc_pos(self.len);
+
+            let moves = if old_pos < new_pos {
+                ((old_pos + 1)..=new_pos).map(|pos_to_update| {
+                    (map[&pos_to_update], pos_to_update - 1)
+                }).collect::<Vec<PosMove>>()
+            } else {
+                (new_pos..old_pos).map(|pos_to_update| {
+                    (map[&pos_to_update], pos_to_update + 1)
+                }).collect::<Vec<PosMove>>()
+            };
+
+            moves.iter().for_each(|(i, new_pos)| {
+                self.entries[*i].pos = *new_pos;
+            });
+
+            self.entries[ai].pos = new_pos;
+            progress.add(1);
+        });
+    }
+
+    pub fn current_order(&self) -> Vec<isize> {
+        let mut cloned = self.entries.clone();
+        cloned.sort_by_key(|a| a.pos);
+        cloned.iter().map(|e| e.val).collect()
+    }
+
+    pub fn grove_coord_sum(&self) -> isize {
+        let current = self.current_order();
+        let index_0 = current
+            .iter()
+            .enumerate()
+            .find(|(_, v)| **v == 0)
+            .map(|(i, _)| i)
+            .unwrap();
+
+        let indexes = vec![
+            (index_0 + 1000) % self.len,
+            (index_0 + 2000) % self.len,
+            (index_0 + 3000) % self.len,
+        ];
+
+        indexes
+            .iter()
+            .map(|i| current[*i])
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_new_pos() {
+        let entry = Entry { pos: 0, val: 1 };
+        assert_eq!(entry.calc_pos(7), 1);
+
+        let entry = Entry { pos: 0, val: 2 };
+        assert_eq!(entry.calc_pos(7), 2);
+
+        let entry = Entry { pos: 1, val: -3 };
+        assert_eq!(entry.calc_pos(7), 4);
+
+        let entry = Entry { pos: 2, val: 3 };
+        assert_eq!(entry.calc_pos(7), 5);
+
+        let entry = Entry { pos: 2, val: -2 };
+        assert_eq!(entry.calc_pos(7), 6);
+
+        let entry = Entry { pos: 3, val : 0 };
+        assert_eq!(entry.calc_pos(7), 3);
+
+        let entry = Entry { pos: 5, val: 4 };
+        assert_eq!(entry.calc_pos(7), 3);
+
+    }
+
+    #[test]
+    fn test_mix() {
+        let mut file = EncFile {
+            entries: vec![1, 2, -3, 3, -2, 0, 4]
+                .iter()
+                .enumerate()
+                .map(|(i, v)| Entry { pos: i, val: *v })
+                .collect(),
+            len: 7
+        };
+
+        assert_eq!(file.current_order(), vec![1, 2, -3, 3, -2, 0, 4]);
+
+        file.mix();
+
+        assert_eq!(file.current_order(), vec![1, 2, -3, 4, 0, 3, -2]);
+    }
+
+    #[test]
+    fn test_grove_coord() {
+
+        let mut file = EncFile {
+            entries: vec![1, 2, -3, 3, -2, 0, 4]
+                .iter()
+                .enumerate()
+                .map(|(i, v)| Entry { pos: i, val: *v })
+                .collect(),
+            len: 7
+        };
+
+        file.mix();
+
+        assert_eq!(file.grove_coord_sum(), 3);
+    }
+}
\ No newline at end of file
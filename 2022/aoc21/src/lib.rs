@@ -0,0 +1,538 @@
+use std::collections::HashMap;
+
+use common::{parse_lines, parse_number, Answer, Generated, ParseError, Rng, Solution};
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Troop;
+
+    fn parse(input: &[String]) -> Result<Troop, ParseError> {
+        Troop::parse(input)
+    }
+
+    fn part_one(troop: &Troop) -> Answer {
+        troop.get_monkey_yell("root").into()
+    }
+
+    fn part_two(troop: &Troop) -> Answer {
+        troop.get_inverse_monkey_yell("humn").into()
+    }
+}
+
+// Built from the top down, so that both sides of root already yell the same,
+// which makes humn's own number the answer to part two. Everything divides
+// exactly and stays above 0, like in the real input.
+impl Generated for Day21 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        let mut builder = TroopBuilder { lines: vec![], names: vec!["root".to_string(), "humn".to_string()] };
+
+        let humn = rng.range(1, 1000);
+        builder.lines.push(format!("humn: {humn}"));
+
+        // From humn up to just below root, each monkey with a different
+        // monkey yelling the other operand
+        let (mut name, mut value) = ("humn".to_string(), humn);
+
+        for _ in 0..std::cmp::max(1, size / 2) {
+            let parent = builder.name(rng);
+
+            // The other operand, and whether humn's side goes on the left
+            let (other, op, on_left, next) = match rng.below(4) {
+                0 => {
+                    let other = rng.range(1, 100);
+                    (other, '+', rng.one_in(2), value + other)
+                }
+
+                1 if value > 1 => {
+                    let other = rng.range(1, value - 1);
+                    (other, '-', true, value - other)
+                }
+
+                1 => {
+                    let difference = rng.range(1, 100);
+                    (value + difference, '-', false, difference)
+                }
+
+                2 if value < 1_000_000_000 => {
+                    let other = rng.range(2, 5);
+                    (other, '*', rng.one_in(2), value * other)
+                }
+
+                _ => match (2..=10).find(|d| value % d == 0) {
+                    Some(divisor) => (divisor, '/', true, value / divisor),
+                    None => {
+                        let quotient = rng.range(2, 5);
+                        (value * quotient, '/', false, quotient)
+                    }
+                },
+            };
+
+            let other = builder.subtree(rng, other, 3);
+            let (left, right) = if on_left { (name, other) } else { (other, name) };
+
+            builder.lines.push(format!("{parent}: {left} {op} {right}"));
+            name = parent;
+            value = next;
+        }
+
+        let other = builder.subtree(rng, value, 4);
+        // Big enough numbers would overflow if multiplied together
+        let op = if value < 1_000_000_000 { *rng.pick(&['+', '-', '*', '/']) } else { '+' };
+        let (left, right) = if rng.one_in(2) { (name, other) } else { (other, name) };
+        builder.lines.push(format!("root: {left} {op} {right}"));
+
+        rng.shuffle(&mut builder.lines);
+        builder.lines
+    }
+}
+
+struct TroopBuilder {
+    lines: Vec<String>,
+    names: Vec<String>,
+}
+
+impl TroopBuilder {
+    fn name(&mut self, rng: &mut Rng) -> String {
+        loop {
+            let name: String = (0..4).map(|_| (b'a' + rng.below(26) as u8) as char).collect();
+
+            if !self.names.contains(&name) {
+                self.names.push(name.clone());
+                return name;
+            }
+        }
+    }
+
+    // Adds monkeys yelling `value` between them, and returns the name of the
+    // one at the top
+    fn subtree(&mut self, rng: &mut Rng, value: i64, depth: usize) -> String {
+        let name = self.name(rng);
+
+        let operands = match rng.below(4) {
+            _ if depth == 0 || rng.one_in(4) => None,
+            0 if value > 1 => {
+                let left = rng.range(1, value - 1);
+                Some((left, '+', value - left))
+            }
+            1 => {
+                let right = rng.range(1, 100);
+                Some((value + right, '-', right))
+            }
+            2 => (2..=10).find(|d| value % d == 0).map(|d| (d, '*', value / d)),
+            _ => {
+                let right = rng.range(2, 5);
+                Some((value * right, '/', right))
+            }
+        };
+
+        match operands {
+            Some((left, op, right)) => {
+                let left = self.subtree(rng, left, depth - 1);
+                let right = self.subtree(rng, right, depth - 1);
+                self.lines.push(format!("{name}: {left} {op} {right}"));
+            }
+
+            None => self.lines.push(format!("{name}: {value}")),
+        }
+
+        name
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Operation {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl Operation {
+    fn from_str(input: &str) -> Option<Operation> {
+        match input {
+            "+" => Some(Operation::Add),
+            "-" => Some(Operation::Subtract),
+            "*" => Some(Operation::Multiply),
+            "/" => Some(Operation::Divide),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+struct Calculation {
+    op1: String,
+    op2: String,
+    operation: Operation
+}
+
+impl Calculation {
+    fn get_other_operand(&self, not_this: &str) -> &str {
+        if self.op1 == not_this {
+            &self.op2
+        } else {
+            &self.op1
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Yell {
+    Number(isize),
+    Calc(Calculation),
+}
+
+impl Yell {
+    pub fn parse(input: &str) -> Result<Yell, ParseError> {
+        let split = input.split(" ").collect::<Vec<&str>>();
+
+        if split.len() == 1 {
+            return Ok(Yell::Number(parse_number(input, input)?))
+        }
+
+        if split.len() != 3 {
+            return Err(ParseError::in_line(input, input, "a number, or `<monkey> <op> <monkey>`"));
+        }
+
+        let op1 = split[0].to_string();
+        let op2 = split[2].to_string();
+        let operation = Operation::from_str(split[1])
+            .ok_or_else(|| ParseError::in_line(input, split[1], "`+`, `-`, `*` or `/`"))?;
+
+        Ok(Yell::Calc(
+            Calculation { op1, op2, operation },
+        ))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Monkey {
+    name: String,
+    yells: Yell
+}
+
+impl Monkey {
+    pub fn parse(input: &str) -> Result<Monkey, ParseError> {
+        let (name, yell) = input
+            .split_once(": ")
+            .ok_or_else(|| ParseError::end_of_line(input, "`<name>: ` and what the monkey yells"))?;
+
+        Ok(Monkey {
+            name: name.to_string(),
+            yells: Yell::parse(yell).map_err(|e| e.within(input, yell))?
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Troop {
+    monkeys: HashMap<String, Monkey>,
+    // Maps to monkeys that use a given hash in an equation
+    parents: HashMap<String, String>,
+}
+
+impl Troop {
+    pub fn parse(input: &[String]) -> Result<Troop, ParseError> {
+        // Store monkeys in a hashmap by name so they can be looked up quickly.
+        let monkeys = parse_lines(input, Monkey::parse)?
+                .into_iter()
+                .map(|m| (m.name.to_string(), m))
+                .collect::<HashMap<String, Monkey>>();
+
+        // Every monkey waited on has to be in the troop
+        for (i, line) in input.iter().enumerate() {
+            let (name, yell) = line.split_once(": ").unwrap();
+
+            if let Yell::Calc(Calculation { op1, op2, .. }) = &monkeys[name].yells {
+                for (offset, operand) in [(0, op1), (op1.len() + 3, op2)] {
+                    if !monkeys.contains_key(operand) {
+                        let token = &yell[offset..offset + operand.len()];
+                        return Err(ParseError::in_line(line, token, "a monkey in the troop").on_line(i));
+                    }
+                }
+            }
+        }
+
+        // Both parts start from these two
+        for name in ["root", "humn"] {
+            if !monkeys.contains_key(name) {
+                let last = input.len().saturating_sub(1);
+                let line = input.get(last).map(|l| l.as_str()).unwrap_or("");
+                return Err(ParseError::end_of_line(line, format!("a monkey called {name}")).on_line(last));
+            }
+        }
+
+        // Each monkey has at most one monkey that uses this one in an operation.
+        // In part 2 we need to work back up the tree so we need to be able to find a "parent"
+        // efficiently. So as part of parsing we find the parents where applicable and store them
+        // in a separate hash map.
+        let parents = monkeys
+            .keys() // for each monkey name
+            .filter_map(|m| { // Gather a parent, if any
+                monkeys
+                    .iter()
+                    .find(|(_, y)| {
+                        // Find an equation where either one of the
+                        // of the operands matches the current monkey (m)
+                        // Monkeys that just yell a fixed number have no operands, so they
+                        // will enver match as a parent of another
+                        match &y.yells {
+                            Yell::Number(_) => false,
+
+                            Yell::Calc(Calculation { op1, op2, operation: _ }) => {
+                                op1 == m || op2 == m
+                            }
+                        }
+                    })
+                    .map(|(parent, _)| (m.to_string(), parent.to_string()))
+            })
+            .collect();
+
+        Ok(Troop {
+            monkeys,
+            parents,
+        })
+    }
+
+    // Part 1 (and used in 2): work down a tree of operations until we have concrete values
+    // to operate on, return result of operation
+    pub fn get_monkey_yell(&self, monkey_name: &str) -> isize {
+        let monkey =  &self.monkeys[monkey_name];
+
+        match &monkey.yells {
+            Yell::Number(num) => *num,
+            Yell::Calc(Calculation { op1, op2, operation }) => {
+                let op1_n = self.get_monkey_yell(op1);
+                let op2_n = self.get_monkey_yell(op2);
+
+                match operation {
+                    Operation::Add => op1_n + op2_n,
+                    Operation::Subtract => op1_n - op2_n,
+                    Operation::Multiply => op1_n * op2_n,
+                    Operation::Divide => op1_n / op2_n,
+                }
+            }
+        }
+    }
+
+    // Part 2, work backward to solve this monkey's value for its parent's known value
+    // This works back to root to find its value (since there the value is the other operand's value
+    // which is determined the usual way, working down the tree).
+    // Once we've retrieved the parent monkey's value, we can use the value of the other operand of
+    // current monkey's operation along with the operation to solve for X.
+    pub fn get_inverse_monkey_yell(&self, monkey_name: &str) -> isize {
+        let parent_monkey_name = &self.parents[monkey_name];
+        let parent_monkey = &self.monkeys[parent_monkey_name];
+
+        if parent_monkey_name == "root" {
+            return if let Yell::Calc(c) = &parent_monkey.yells {
+                let other = &self.monkeys[c.get_other_operand(monkey_name)];
+                
+                self.get_monkey_yell(&other.name)
+            } else {
+                // Won't happen
+                0
+            }
+        }
+
+        if let Yell::Calc(c) = &parent_monkey.yells {
+            // Get other operand of the calculation
+            let other = &self.monkeys[c.get_other_operand(monkey_name)];
+
+            // name of monkey we're trying to solve for
+            let x_name = monkey_name;
+
+            // Name of operand 1 in the operation (to determine position of X in operation,
+            // which is relevant for solving for X in subtractions and divisions
+            let op1_name: &str = &c.op1;
+
+            // Parent
+            let b = self.get_inverse_monkey_yell(parent_monkey_name);
+
+            // other operand, solve by going down the tree as in part 1
+            let a = self.get_monkey_yell(&other.name);
+
+            // Solve for x
+            match c.operation {
+                // b = x + a
+                // b = a + x
+                Operation::Add => b - a,
+
+                // b = x * a
+                // b = a * x
+                Operation::Multiply => b / a,
+
+                Operation::Subtract => {
+                    if x_name == op1_name {
+                        // b = x - a
+                        b + a
+                    } else {
+                        // b = a - x
+                        a - b
+                    }
+                }
+
+                Operation::Divide => {
+                    if x_name == op1_name {
+                        // b = x / a
+                        b * a
+                    } else {
+                        // b = a / x
+                        a / b
+                    }
+                }
+            }
+        } else {
+            // Won't happen
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_monkey_parse() {
+        assert_eq!(
+            Monkey::parse("dbpl: 5").unwrap(),
+            Monkey {
+                name: "dbpl".to_string(),
+                yells: Yell::Number(5),
+            },
+        );
+
+        assert_eq!(
+            Monkey::parse("root: pppw + sjmn").unwrap(),
+            Monkey {
+                name: "root".to_string(),
+                yells: Yell::Calc(
+                    Calculation {
+                        op1: "pppw".to_string(),
+                        op2: "sjmn".to_string(),
+                        operation: Operation::Add
+                    }
+                ),
+            }
+        );
+
+        assert_eq!(
+            Monkey::parse("root: pppw - sjmn").unwrap(),
+            Monkey {
+                name: "root".to_string(),
+                yells: Yell::Calc(
+                    Calculation {
+                        op1: "pppw".to_string(),
+                        op2: "sjmn".to_string(),
+                        operation: Operation::Subtract
+                    }
+                ),
+            }
+        );
+        assert_eq!(
+            Monkey::parse("root: pppw * sjmn").unwrap(),
+            Monkey {
+                name: "root".to_string(),
+                yells: Yell::Calc(
+                    Calculation {
+                        op1: "pppw".to_string(),
+                        op2: "sjmn".to_string(),
+                        operation: Operation::Multiply
+                    }
+                ),
+            }
+        );
+        assert_eq!(
+            Monkey::parse("root: pppw / sjmn").unwrap(),
+            Monkey {
+                name: "root".to_string(),
+                yells: Yell::Calc(
+                    Calculation {
+                        op1: "pppw".to_string(),
+                        op2: "sjmn".to_string(),
+                        operation: Operation::Divide
+                    }
+                ),
+            }
+        );
+
+        let err = Monkey::parse("root: pppw % sjmn").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (11, "%"));
+
+        let err = Monkey::parse("dbpl: 5x").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (6, "5x"));
+    }
+
+    #[test]
+    fn test_troop_parse_unknown_monkey() {
+        let err = Troop::parse(&vec![
+            "root: humn + abcd".to_string(),
+            "humn: 5".to_string(),
+        ]).unwrap_err();
+
+        assert_eq!((err.line, err.column, err.text.as_str()), (0, 13, "abcd"));
+    }
+
+    #[test]
+    fn test_get_monkey_yell() {
+        let monkeys_input = vec![
+            "root: pppw + sjmn".to_string(),
+            "dbpl: 5".to_string(),
+            "cczh: sllz + lgvd".to_string(),
+            "zczc: 2".to_string(),
+            "ptdq: humn - dvpt".to_string(),
+            "dvpt: 3".to_string(),
+            "lfqf: 4".to_string(),
+            "humn: 5".to_string(),
+            "ljgn: 2".to_string(),
+            "sjmn: drzm * dbpl".to_string(),
+            "sllz: 4".to_string(),
+            "pppw: cczh / lfqf".to_string(),
+            "lgvd: ljgn * ptdq".to_string(),
+            "drzm: hmdt - zczc".to_string(),
+            "hmdt: 32".to_string(),
+        ];
+
+        let troop = Troop::parse(&monkeys_input).unwrap();
+
+        assert_eq!(troop.get_monkey_yell("root"), 152);
+    }
+
+    #[test]
+    fn test_get_inverse_monkey_yell() {
+        let monkeys_input = vec![
+            "root: pppw + sjmn".to_string(),
+            "dbpl: 5".to_string(),
+            "cczh: sllz + lgvd".to_string(),
+            "zczc: 2".to_string(),
+            "ptdq: humn - dvpt".to_string(),
+            "dvpt: 3".to_string(),
+            "lfqf: 4".to_string(),
+            "humn: 5".to_string(),
+            "ljgn: 2".to_string(),
+            "sjmn: drzm * dbpl".to_string(),
+            "sllz: 4".to_string(),
+            "pppw: cczh / lfqf".to_string(),
+            "lgvd: ljgn * ptdq".to_string(),
+            "drzm: hmdt - zczc".to_string(),
+            "hmdt: 32".to_string(),
+        ];
+
+        let troop = Troop::parse(&monkeys_input).unwrap();
+
+        assert_eq!(troop.get_inverse_monkey_yell("humn"), 301);
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            let input = common::generate::<Day21>(seed, 10);
+            let humn = input.iter().find_map(|l| l.strip_prefix("humn: ")).unwrap();
+            let troop = Day21::parse(&input).unwrap();
+
+            assert_eq!(Day21::part_two(&troop), Answer::Number(humn.parse().unwrap()));
+        }
+    }
+}
@@ -0,0 +1,631 @@
+use std::collections::HashMap;
+
+use common::{Answer, Bounds2, Direction, Generated, Grid, NumberParam, Param, ParseError, Params, Point2, Rng, Solution, YAxis};
+
+pub struct Day22;
+
+const FACE_SIZE: NumberParam = NumberParam::new("face_size", 50, "How wide each face of the cube is").at_least(1);
+
+impl Solution for Day22 {
+    type Input = (Map, Vec<Instruction>);
+
+    // The example's cube is folded from faces 4 wide, the real one's are 50
+    const PARAMS: &'static [Param] = &[FACE_SIZE.param()];
+
+    fn parse(input: &[String]) -> Result<Self::Input, ParseError> {
+        Self::parse_with(input, &Params::new())
+    }
+
+    fn parse_with(input: &[String], params: &Params) -> Result<Self::Input, ParseError> {
+        let map = Map::import(input, params.number(FACE_SIZE) as usize)?;
+        let (line, instructions_raw) = input
+            .iter()
+            .enumerate()
+            .skip_while(|(_, l)| !l.is_empty())
+            .skip_while(|(_, l)| l.is_empty())
+            .nth(0)
+            .ok_or_else(|| {
+                let last = input.len().saturating_sub(1);
+                let line = input.get(last).map(|l| l.as_str()).unwrap_or("");
+                ParseError::end_of_line(line, "a blank line, then the path to follow").on_line(last)
+            })?;
+
+        let instructions = Instruction::parse_all(instructions_raw).map_err(|e| e.on_line(line))?;
+
+        Ok((map, instructions))
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        let (map, instructions) = input;
+
+        let cursor = map.execute(instructions);
+
+        cursor.as_password().into()
+    }
+}
+
+// The eleven ways to unfold a cube, one face to a `#`
+const CUBE_NETS: &[&[&str]] = &[
+    &["#...", "####", "#..."],
+    &["#...", "####", ".#.."],
+    &["#...", "####", "..#."],
+    &["#...", "####", "...#"],
+    &[".#..", "####", ".#.."],
+    &[".#..", "####", "..#."],
+    &["##..", ".###", ".#.."],
+    &["##..", ".###", "..#."],
+    &["##..", ".###", "...#"],
+    &["##..", ".##.", "..##"],
+    &["###..", "..###"],
+];
+
+// A randomly turned and flipped cube net, with faces the real input's size,
+// then a path with twenty or so steps and turns for every bit of `size`
+impl Generated for Day22 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        let face_size = 50;
+
+        let mut faces: Vec<Loc> = vec![];
+
+        for (y, row) in rng.pick(CUBE_NETS).iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                if c == '#' {
+                    faces.push(Point2::new(x as isize, y as isize));
+                }
+            }
+        }
+
+        let turns = rng.below(4);
+        let flip = rng.one_in(2);
+
+        for face in faces.iter_mut() {
+            for _ in 0..turns {
+                *face = Point2::new(-face.y, face.x);
+            }
+
+            if flip {
+                face.x = -face.x;
+            }
+        }
+
+        let bounds = Bounds2::from_points(faces.iter().copied()).unwrap();
+        let mut tiles = Grid::new(bounds.width() as usize * face_size, bounds.height() as usize * face_size, ' ');
+
+        for face in faces {
+            let corner = face - bounds.min;
+            let (fx, fy) = (corner.x as usize * face_size, corner.y as usize * face_size);
+
+            for y in fy..fy + face_size {
+                for x in fx..fx + face_size {
+                    tiles[(x, y)] = if rng.one_in(10) { '#' } else { '.' };
+                }
+            }
+        }
+
+        // There has to be somewhere open to start from
+        if !tiles.row(0).contains(&'.') {
+            let first = tiles.row(0).iter().position(|t| *t != ' ').unwrap();
+            tiles[(first, 0)] = '.';
+        }
+
+        let mut lines: Vec<String> = tiles
+            .rows()
+            .map(|row| row.iter().collect::<String>().trim_end().to_string())
+            .collect();
+
+        let mut path = rng.range(1, 50).to_string();
+
+        for _ in 0..std::cmp::max(1, size * 10) {
+            path.push(*rng.pick(&['L', 'R']));
+            path += &rng.range(1, 50).to_string();
+        }
+
+        lines.push(String::new());
+        lines.push(path);
+        lines
+    }
+}
+
+type Loc = Point2<isize>;
+
+#[derive(Debug, PartialEq)]
+pub struct Cursor {
+    loc: Loc,
+    facing: Direction,
+}
+
+impl Cursor {
+    pub fn as_password(&self) -> usize {
+        let (row, column) = ((self.loc.y + 1) as usize, (self.loc.x + 1) as usize);
+        // The puzzle counts facings clockwise from east
+        let facing = (self.facing.index() + 3) % 4;
+
+        1000 * row + 4 * column + facing
+    }
+
+    fn loc_ahead(&self) -> Loc {
+        self.loc.step(self.facing, YAxis::Down)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Instruction {
+    Go(usize),
+    Turn(isize)
+}
+
+impl Instruction {
+    pub fn parse_all(input: &str) -> Result<Vec<Instruction>, ParseError> {
+        let mut chars = input.chars();
+
+        let mut instructions: Vec<Instruction> = vec![];
+        let mut cur_chars: String = "".to_string();
+
+        for column in 0.. {
+            let c = chars.next();
+
+            match c {
+                None | Some('L') | Some('R') => {
+                    if let Ok(num) = usize::from_str_radix(&cur_chars, 10) {
+                        instructions.push(Instruction::Go(num));
+                    }
+
+                    cur_chars = "".to_string();
+                }
+
+                _ => {}
+            }
+
+            match c {
+                None => { break; }
+                Some('L') => { instructions.push(Instruction::Turn(-1)); }
+                Some('R') => { instructions.push(Instruction::Turn(1));  }
+                Some('0'..='9') => { cur_chars.push(c.unwrap()); }
+                _ => { return Err(ParseError::at_char(input, column, "a number of steps, L or R")); }
+            }
+        }
+
+        Ok(instructions)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Map {
+    // ' ' for anything off the map
+    tiles: Grid<char>,
+    face_size: usize,
+}
+
+impl Map {
+    pub fn import(input: &[String], face_size: usize) -> Result<Self, ParseError> {
+        let map_lines: Vec<String> = input
+            .iter()
+            .take_while(|l| !l.is_empty())
+            .cloned()
+            .collect();
+
+        let tiles = Grid::try_parse(&map_lines, "`.`, `#` or a space", |c| match c {
+            ' ' | '.' | '#' => Some(c),
+            _ => None,
+        })?;
+
+        // We start on the leftmost open tile of the top row
+        if tiles.height() == 0 || !tiles.row(0).contains(&'.') {
+            let line = input.first().map(|l| l.as_str()).unwrap_or("");
+            return Err(ParseError::in_line(line, line, "an open tile (.) in the top row"));
+        }
+
+        Ok(Map {
+            tiles,
+            face_size
+        })
+    }
+
+    // Tile at loc, treating anywhere outside the map as empty space
+    fn tile(&self, loc: Loc) -> char {
+        *self.tiles.get_signed(loc.x, loc.y).unwrap_or(&' ')
+    }
+
+    fn init_cursor(&self) -> Cursor {
+        let first_open_x = self.tiles
+            .row(0)
+            .iter()
+            .position(|c| *c == '.')
+            .unwrap();
+
+        Cursor {
+            loc: Point2::new(first_open_x as isize, 0),
+            facing: Direction::East
+        }
+    }
+
+    fn resolve_loc(&self, loc: Loc, facing: &Direction) -> Loc {
+        // Wraps where needed to not be on unavailable space
+        // Returns a loc of either open space or wall.
+        if self.tile(loc) != ' ' {
+            return loc;
+        }
+
+        let Point2 { x, y } = loc;
+
+        // Does not care if the tile is a wall, just needs to be avail or wall.
+        match *facing {
+            // Find the first tile on this row from the beginning
+            Direction::East => {
+                let row = self.tiles.row(y as usize);
+                Point2::new(row.iter().position(|t| *t != ' ').unwrap() as isize, y)
+            }
+
+            // Or the last one, going the other way
+            Direction::West => {
+                let row = self.tiles.row(y as usize);
+                Point2::new(row.iter().rposition(|t| *t != ' ').unwrap() as isize, y)
+            }
+
+            // Same thing in columns
+            Direction::South => {
+                let column: Vec<&char> = self.tiles.column(x as usize).collect();
+                Point2::new(x, column.iter().position(|t| **t != ' ').unwrap() as isize)
+            }
+
+            Direction::North => {
+                let column: Vec<&char> = self.tiles.column(x as usize).collect();
+                Point2::new(x, column.iter().rposition(|t| **t != ' ').unwrap() as isize)
+            }
+        }
+    }
+
+    // Part two, unfinished
+    #[allow(dead_code)]
+    fn resolve_loc_and_facing_cube(&self, loc: Loc, facing: &Direction) -> (Loc, Direction) {
+        if self.tile(loc) != ' ' {
+            // Coordinate directly accessible, nothing to do
+            return (loc, *facing);
+        }
+
+        // Okay, figure out warping. We want to traverse 4 joints between faces to end up on the
+        // face we need to be on. Then, based on the directions we've had to move to get there,
+        // we can determine where we're facing and how to mangle coordinates.
+        // TODO next
+
+        // Q: how to detect which face we're on and where to go?
+
+
+
+        (Point2::new(0, 0), Direction::East)
+    }
+
+    fn move_cursor(&self, cursor: &Cursor) -> Loc {
+        let target_loc = self.resolve_loc(cursor.loc_ahead(), &cursor.facing);
+
+        if self.tile(target_loc) == '#' {
+            cursor.loc
+        } else {
+            target_loc
+        }
+    }
+
+    pub fn execute(&self, program: &[Instruction]) -> Cursor {
+        let mut cursor = self.init_cursor();
+
+        program
+            .iter()
+            .for_each(|inst| {
+                match inst {
+                    Instruction::Go(n) => {
+                        for _ in 0..*n {
+                            let new_loc = self.move_cursor(&cursor);
+                            if new_loc == cursor.loc { break; }
+                            cursor.loc = new_loc;
+                        }
+                    }
+
+                    Instruction::Turn(dir) => {
+                        cursor.facing = cursor.facing.turn(*dir);
+                    }
+                }
+            });
+
+        cursor
+    }
+}
+
+// Everything below is the start of part two (folding the map into a cube),
+// which isn't finished yet.
+
+// TODO edges might not actually be needed
+#[allow(dead_code)]
+#[derive(Debug, PartialEq)]
+enum Edge {
+    A, B, C, D, E, F, G, H, I, J, K, L,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Copy, Clone)]
+enum ModelCubeFace {
+    Front,
+    Left,
+    Rear,
+    Right,
+    Top,
+    Bottom,
+}
+
+#[allow(dead_code)]
+impl ModelCubeFace {
+    // TODO this is probably not needed
+    fn get_edge(&self, dir: Direction) -> Edge {
+        match self {
+            Self::Front => match dir {
+                Direction::North => Edge::A,
+                Direction::East => Edge::B,
+                Direction::South => Edge::C,
+                Direction::West => Edge::D,
+            }
+
+            Self::Left => match dir {
+                Direction::North => Edge::E,
+                Direction::East => Edge::D,
+                Direction::South => Edge::G,
+                Direction::West =>  Edge::I,
+            }
+
+            Self::Rear => match dir {
+                Direction::East => Edge::I,
+                Direction::South => Edge::L,
+                Direction::West => Edge::K,
+                Direction::North => Edge::J,
+            }
+
+            Self::Right => match dir {
+                Direction::East => Edge::K,
+                Direction::South => Edge::H,
+                Direction::West => Edge::B,
+                Direction::North => Edge::F,
+            }
+
+            Self::Top => match dir {
+                Direction::East => Edge::F,
+                Direction::South => Edge::A,
+                Direction::West => Edge::E,
+                Direction::North => Edge::J,
+            }
+
+            Self::Bottom => match dir {
+                Direction::East => Edge::H,
+                Direction::South => Edge::L,
+                Direction::West => Edge::G,
+                Direction::North => Edge::C,
+            }
+        }
+    }
+
+    fn get_face(&self, dir: Direction) -> Self {
+        match self {
+            Self::Front => match dir {
+                Direction::East => Self::Right,
+                Direction::South => Self::Bottom,
+                Direction::West => Self::Left,
+                Direction::North => Self::Top,
+            }
+
+            Self::Left => match dir {
+                Direction::East => Self::Front,
+                Direction::South => Self::Bottom,
+                Direction::West => Self::Rear,
+                Direction::North => Self::Top,
+            }
+
+            Self::Rear => match dir {
+                Direction::East => Self::Left,
+                Direction::South => Self::Bottom,
+                Direction::West => Self::Right,
+                Direction::North => Self::Top,
+            }
+
+            Self::Right => match dir {
+                Direction::East => Self::Rear,
+                Direction::South => Self::Bottom,
+                Direction::West => Self::Front,
+                Direction::North => Self::Top,
+            }
+
+            Self::Top => match dir {
+                Direction::East => Self::Right,
+                Direction::South => Self::Front,
+                Direction::West => Self::Left,
+                Direction::North => Self::Rear,
+            }
+
+            Self::Bottom => match dir {
+                Direction::East => Self::Right,
+                Direction::South => Self::Rear,
+                Direction::West => Self::Left,
+                Direction::North => Self::Front,
+            }
+        }
+    }
+}
+
+
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct CubeFace {
+    // Top left index in unfolded map
+    loc: Loc,
+    model_face: Option<ModelCubeFace>,
+}
+
+#[allow(dead_code)]
+impl CubeFace {
+    fn new(loc: Loc) -> Self {
+        CubeFace {
+            loc,
+            model_face: None,
+        }
+    }
+}
+
+
+#[allow(dead_code)]
+#[derive(Debug, PartialEq)]
+struct Cube {
+    faces: HashMap<Loc, CubeFace>,
+}
+
+#[allow(unused)]
+impl Cube {
+    fn have_face_at(map: &Map, x: usize, y: usize) -> bool {
+        map.tiles.get(x, y).unwrap_or(&' ') != &' '
+    }
+
+    fn read_map(map: &Map) -> Self {
+
+        // We can have a max grid size of 4x4, so let's scan that.
+
+        let mut faces = HashMap::new();
+
+        // First collect existing faces
+        for y in (0..(4 * map.face_size)).step_by(map.face_size) {
+            for x in (0..(4 * map.face_size)).step_by(map.face_size) {
+                if Self::have_face_at(map, x, y) {
+                    let loc = Point2::new(x as isize, y as isize);
+                    faces.insert(loc, CubeFace::new(loc));
+                }
+            }
+        }
+
+        let face = faces.iter().nth(0).unwrap();
+        let (_, front_face) = face;
+        let mut front_face = front_face.clone();
+
+        front_face.model_face = Some(ModelCubeFace::Front);
+
+        // Okay I've run out of my self-imposed time limit.
+        //
+        // TODO, at some point:
+        // travers from the front face, visiting all adjacent faces, and assigning the
+        // appropriate ModelCubeFace to each of them.
+
+        // Then from that point, we know each side's adjacent faces, and can thus find which
+        // place to continue from to wrap around.
+
+        Cube {
+            faces
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn get_test_input() -> Vec<String> {
+        vec![
+            "        ...#".to_string(),
+            "        .#..".to_string(),
+            "        #...".to_string(),
+            "        ....".to_string(),
+            "...#.......#".to_string(),
+            "........#...".to_string(),
+            "..#....#....".to_string(),
+            "..........#.".to_string(),
+            "        ...#....".to_string(),
+            "        .....#..".to_string(),
+            "        .#......".to_string(),
+            "        ......#.".to_string(),
+            "".to_string(),
+            "10R5L5R10L4R5L5".to_string(),
+        ]
+    }
+
+    #[test]
+    fn test_init_cursor() {
+        let map = Map::import(&get_test_input(), 4).unwrap();
+        let cursor = map.init_cursor();
+
+        assert_eq!(
+            cursor,
+            Cursor {
+                loc: Point2::new(8, 0),
+                facing: Direction::East,
+            }
+        )
+    }
+
+    #[test]
+    fn test_parse_instructions() {
+
+        assert_eq!(
+            Instruction::parse_all("10R5L5R10L4R5L5").unwrap(),
+            vec![
+                Instruction::Go(10),
+                Instruction::Turn(1),
+                Instruction::Go(5),
+                Instruction::Turn(-1),
+                Instruction::Go(5),
+                Instruction::Turn(1),
+                Instruction::Go(10),
+                Instruction::Turn(-1),
+                Instruction::Go(4),
+                Instruction::Turn(1),
+                Instruction::Go(5),
+                Instruction::Turn(-1),
+                Instruction::Go(5),
+            ]
+        );
+
+        let err = Instruction::parse_all("10R5X5").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (4, "X"));
+    }
+
+    #[test]
+    fn test_resolve_loc() {
+        let map = Map::import(&get_test_input(), 4).unwrap();
+
+        // An available location (either open or wall, but not nothing), should resolve to same
+        assert_eq!(map.resolve_loc(Point2::new(9, 2), &Direction::East), Point2::new(9, 2));
+        assert_eq!(map.resolve_loc(Point2::new(9, 2), &Direction::West), Point2::new(9, 2));
+        assert_eq!(map.resolve_loc(Point2::new(9, 2), &Direction::South), Point2::new(9, 2));
+        assert_eq!(map.resolve_loc(Point2::new(9, 2), &Direction::North), Point2::new(9, 2));
+
+        // Locations off the end
+        // Left/right
+        assert_eq!(map.resolve_loc(Point2::new(12, 1), &Direction::East), Point2::new(8, 1));
+        assert_eq!(map.resolve_loc(Point2::new(7, 1), &Direction::West), Point2::new(11, 1));
+        assert_eq!(map.resolve_loc(Point2::new(-1, 4), &Direction::West), Point2::new(11, 4));
+
+        // Up/down
+        assert_eq!(map.resolve_loc(Point2::new(8, -1), &Direction::North), Point2::new(8, 11));
+        assert_eq!(map.resolve_loc(Point2::new(0, 3), &Direction::North), Point2::new(0, 7));
+        assert_eq!(map.resolve_loc(Point2::new(13, 7), &Direction::North), Point2::new(13, 11));
+        assert_eq!(map.resolve_loc(Point2::new(8, 12), &Direction::South), Point2::new(8, 0));
+        assert_eq!(map.resolve_loc(Point2::new(2, 8), &Direction::South), Point2::new(2, 4));
+    }
+
+    #[test]
+    fn test_facing_turn() {
+        assert_eq!(Direction::East.turn(1), Direction::South);
+        assert_eq!(Direction::South.turn(1), Direction::West);
+        assert_eq!(Direction::West.turn(1), Direction::North);
+        assert_eq!(Direction::North.turn(1), Direction::East);
+        assert_eq!(Direction::East.turn(-1), Direction::North);
+        assert_eq!(Direction::South.turn(-1), Direction::East);
+        assert_eq!(Direction::West.turn(-1), Direction::South);
+        assert_eq!(Direction::North.turn(-1), Direction::West);
+    }
+
+    #[test]
+    fn test_execute_program() {
+        let map = Map::import(&get_test_input(), 4).unwrap();
+        let instructions =Instruction::parse_all("10R5L5R10L4R5L5").unwrap();
+
+        let cursor = map.execute(&instructions);
+
+        assert_eq!(cursor.as_password(), 6032);
+    }
+
+    #[test]
+    fn test_cube_read_map() {
+        // TODO check if all the ModelCubeFaces are assigned correctly
+    }
+}
@@ -0,0 +1,367 @@
+use std::collections::{HashMap, HashSet};
+
+use common::{Answer, Bounds2, Direction, Direction8, Frame, Generated, Grid, ParseError, Part, Point2, Rng, Simulated, Simulation, Solution, YAxis};
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = ElfTroop;
+
+    fn parse(input: &[String]) -> Result<ElfTroop, ParseError> {
+        ElfTroop::import(input)
+    }
+
+    fn part_one(troop: &ElfTroop) -> Answer {
+        let mut troop = troop.clone();
+
+        (0..10).for_each(|_| { troop.process_round(); });
+
+        troop.count_empty_squares().into()
+    }
+
+    fn part_two(troop: &ElfTroop) -> Answer {
+        let mut troop = troop.clone();
+
+        let rounds = troop.process_until_done();
+
+        rounds.into()
+    }
+}
+
+impl Simulated for Day23 {
+    fn simulation(troop: &ElfTroop, part: Part) -> Box<dyn Simulation> {
+        Box::new(ElfSimulation {
+            troop: troop.clone(),
+            rounds_left: if part == Part::One { Some(10) } else { None },
+            done: false,
+        })
+    }
+}
+
+// A square patch with about half of it covered in elves
+impl Generated for Day23 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        let side = 5 + size * 2 / 3;
+
+        (0..side)
+            .map(|_| (0..side).map(|_| if rng.one_in(2) { '#' } else { '.' }).collect())
+            .collect()
+    }
+}
+
+type Loc = Point2<isize>;
+
+fn get_surrounding_locs(loc: Loc) -> Vec<Loc> {
+    loc.neighbours8().collect()
+}
+
+// The order the elves consider the directions in, starting one further along
+// each round
+const DIRECTIONS: [Direction; 4] = [Direction::North, Direction::South, Direction::West, Direction::East];
+
+// Gets all the adjacent locations to check if we want to travel in this direction from a
+// current location: straight ahead, and either side of it
+fn locs_to_check(direction: Direction, current: Loc) -> HashSet<Loc> {
+    let ahead = Direction8::from(direction);
+
+    [ahead.turn_left(), ahead, ahead.turn_right()]
+        .iter()
+        .map(|d| current.step(*d, YAxis::Down))
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ElfTroop {
+    // Locations an Elf is standing at the moment
+    elves: HashSet<Loc>,
+
+    // Which direction elves will look to move to next as the first candidate
+    dir_index: usize,
+}
+
+impl ElfTroop {
+    pub fn import(input: &[String]) -> Result<Self, ParseError> {
+        // Extract all coordinates where we find a '#'
+        // (spaces only show up padding out short lines, and are just more ground)
+        let elves: HashSet<Loc> = Grid::try_parse(input, "`#` or `.`", |c| match c {
+                '#' => Some(true),
+                '.' | ' ' => Some(false),
+                _ => None,
+            })?
+            .iter()
+            .filter(|(_, is_elf)| **is_elf)
+            .map(|((x, y), _)| Point2::new(x as isize, y as isize))
+            .collect();
+
+        Ok(ElfTroop { elves, dir_index: 0 })
+    }
+
+    // Based on the logic described in the challenge, proposes a location for an elf to go to next.
+    // - If `current` is fully surrounded by open squares, we're happy where we are and need to stay
+    //   so `None` is returned
+    // - Otherwise we look first in DIRECTIONS[start_index] to see if we can go there. If not, we check the next
+    //   direction in the list. If a viable direction to go is found, returns Some(loc) with the
+    //   adjacent location in that direction
+    //   If no viable direction is found, returns `None`; we'll stay put.
+    fn propose_location(&self, current: Loc, start_index: usize) -> Option<Loc> {
+        if !get_surrounding_locs(current).iter().any(|l| self.elves.contains(l)) {
+            return None;
+        }
+
+        for i in start_index..(start_index + 4) {
+            let dir = DIRECTIONS[i % 4];
+
+            if !locs_to_check(dir, current).iter().any(|l| self.elves.contains(l)) {
+                return Some(current.step(dir, YAxis::Down));
+            }
+        }
+
+        None
+    }
+
+    // Figure out where each elf should go this round, and resolve which ones actually go through
+    // with it. Returns how many elves moved from their spot before this round.
+    pub fn process_round(&mut self) -> usize {
+
+        // Iterates over each elf and matches it up with a target location. If the elf is not to
+        // move (see `propose_location`), it is left out of the set of moves collected here
+        let target_current = self.elves
+            .iter()
+            .filter_map(|current| self.propose_location(*current, self.dir_index).map(|t| (t, *current)))
+            .collect::<Vec<(Loc, Loc)>>();
+
+        // Collect targets seen so far
+        let mut targets: HashSet<Loc> = HashSet::new();
+
+        // Add any targets we've seen once more so we can remove them later.
+        let mut targets_to_remove: HashSet<Loc> = HashSet::new();
+
+        target_current
+            .iter()
+            .for_each(|(t, _)| {
+                // target already encountered? add it to those to remove
+                if targets.contains(t) {
+                    targets_to_remove.insert(*t);
+                } else {
+                    targets.insert(*t);
+                }
+            });
+
+        // Flip to current->target while removing pairs that are in targets_to_remove
+        let proposed_moves = target_current
+            .iter()
+            .filter_map(|(t, c)| {
+                if targets_to_remove.contains(t) {
+                    None
+                } else {
+                    Some((*c, *t))
+                }
+            })
+            .collect::<HashMap<Loc, Loc>>();
+
+        // Find elves that don't have a move queued up
+        let elves_that_stay = self.elves
+            .iter()
+            .filter(|e| {
+                !proposed_moves.contains_key(*e)
+            })
+            .map(|i| *i)
+            .collect::<HashSet<Loc>>();
+
+        // Funny enough, this is kind of all I needed for part 2, everything else was already as-is.
+        let move_count = proposed_moves.len();
+
+        // Combine locations of staying elves with all the move targets
+        self.elves = elves_that_stay
+            .union(&proposed_moves.values().map(|i| *i).collect::<HashSet<Loc>>())
+            .map(|i| *i)
+            .collect();
+
+        // For next round, move to next starting directions
+        self.dir_index = (self.dir_index + 1) % DIRECTIONS.len();
+
+        move_count
+    }
+
+    pub fn process_until_done(&mut self) -> usize {
+        let mut count = 0;
+
+        loop {
+            count += 1;
+            if self.process_round() == 0  { return count }
+        }
+    }
+
+    // Surface area of the smallest rectangle holding every elf, boundary included.
+    fn surface_area(&self) -> isize {
+        Bounds2::from_points(self.elves.iter().copied()).map_or(0, |bounds| bounds.area())
+    }
+
+    pub fn count_empty_squares(&self) -> isize {
+        self.surface_area() - self.elves.len() as isize
+    }
+}
+
+pub struct ElfSimulation {
+    troop: ElfTroop,
+    // Part one stops after 10 rounds, part two keeps going until nobody moves
+    rounds_left: Option<usize>,
+    done: bool,
+}
+
+// A step is one round
+impl Simulation for ElfSimulation {
+    fn step(&mut self) -> bool {
+        if self.done || self.rounds_left == Some(0) {
+            return false;
+        }
+
+        self.rounds_left = self.rounds_left.map(|r| r - 1);
+
+        // A round where nobody moves looks just like the one before it
+        if self.troop.process_round() == 0 {
+            self.done = true;
+            return false;
+        }
+
+        true
+    }
+
+    fn render_frame(&self) -> Frame {
+        Frame::draw(self.troop.elves.iter().map(|loc| ((*loc).into(), '#')), '.')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn elves_at(locs: &[(isize, isize)]) -> HashSet<Loc> {
+        locs.iter().map(|l| Point2::from(*l)).collect()
+    }
+
+    fn get_input_small() -> Vec<String> {
+        vec![
+            ".....".to_string(),
+            "..##.".to_string(),
+            "..#..".to_string(),
+            ".....".to_string(),
+            "..##.".to_string(),
+            ".....".to_string(),
+        ]
+    }
+
+    fn get_input_larger() -> Vec<String> {
+        vec![
+            "....#..".to_string(),
+            "..###.#".to_string(),
+            "#...#.#".to_string(),
+            ".#...##".to_string(),
+            "#.###..".to_string(),
+            "##.#.##".to_string(),
+            ".#..#..".to_string(),
+        ]
+    }
+
+    #[test]
+    fn test_elftroop_import() {
+        assert_eq!(
+            ElfTroop::import(&get_input_small()).unwrap(),
+            ElfTroop {
+                elves: elves_at(&[
+                    (2, 1),
+                    (3, 1),
+                    (2, 2),
+                    (2, 4),
+                    (3, 4),
+                ]),
+
+                dir_index: 0,
+            },
+        );
+    }
+
+    #[test]
+    fn test_elftroop_round() {
+        let mut troop = ElfTroop::import(&get_input_small()).unwrap();
+
+        troop.process_round();
+        assert_eq!(
+            troop,
+            ElfTroop {
+                elves: elves_at(&[
+                    (2, 0),
+                    (3, 0),
+                    (2, 2),
+                    (3, 3),
+                    (2, 4)
+                ]),
+
+                dir_index: 1,
+            }
+        );
+
+        troop.process_round();
+        assert_eq!(
+            troop,
+            ElfTroop {
+                elves: elves_at(&[
+                    (2, 1),
+                    (3, 1),
+                    (1, 2),
+                    (4, 3),
+                    (2, 5),
+                ]),
+
+                dir_index: 2,
+            }
+        );
+
+        troop.process_round();
+        assert_eq!(
+            troop,
+            ElfTroop {
+                elves: elves_at(&[
+                    (2, 0),
+                    (4, 1),
+                    (0, 2),
+                    (4, 3),
+                    (2, 5),
+                ]),
+
+                dir_index: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_empty_tiles() {
+        let mut troop = ElfTroop::import(&get_input_larger()).unwrap();
+        (0..10).for_each(|_| { troop.process_round(); });
+        assert_eq!(troop.surface_area(), 12 * 11);
+        assert_eq!(troop.count_empty_squares(), 110);
+    }
+
+    #[test]
+    fn test_rounds_needed() {
+        let mut troop = ElfTroop::import(&get_input_larger()).unwrap();
+        let rounds = troop.process_until_done();
+        assert_eq!(rounds, 20);
+    }
+
+    #[test]
+    fn test_simulation() {
+        let troop = ElfTroop::import(&get_input_small()).unwrap();
+        let mut elves = Day23::simulation(&troop, Part::Two);
+
+        assert!(elves.step());
+        assert_eq!(elves.render_frame().cells.to_string(), "##\n..\n#.\n.#\n#.");
+
+        // Round 4 is the first where nobody moves
+        assert_eq!(common::frames(elves.as_mut()).count(), 1 + 2);
+
+        let troop = ElfTroop::import(&get_input_larger()).unwrap();
+        assert_eq!(common::frames(Day23::simulation(&troop, Part::One).as_mut()).count(), 1 + 10);
+        assert_eq!(common::frames(Day23::simulation(&troop, Part::Two).as_mut()).count(), 1 + 19);
+    }
+}
@@ -0,0 +1,525 @@
+use std::collections::HashSet;
+
+use common::{search, Answer, Direction, Frame, Generated, Grid, ParseError, Part, Point2, Progress, Rng, Simulated, Simulation, Solution, YAxis};
+
+pub struct Day24;
+
+// TODO since the result is wrong (250 too high, 232 too low), try keeping track of walls
+// and rework based on their positions
+impl Solution for Day24 {
+    type Input = Valley;
+
+    fn parse(input: &[String]) -> Result<Valley, ParseError> {
+        Valley::import(input)
+    }
+
+    fn part_one(valley: &Valley) -> Answer {
+        valley.shortest_path(vec![valley.end]).map_or(Answer::Unsolved, Answer::from)
+    }
+
+    fn part_two(valley: &Valley) -> Answer {
+        valley.shortest_path(vec![valley.end, valley.start, valley.end]).map_or(Answer::Unsolved, Answer::from)
+    }
+}
+
+impl Simulated for Day24 {
+    fn simulation(valley: &Valley, part: Part) -> Box<dyn Simulation> {
+        let goals = match part {
+            Part::One => vec![valley.end],
+            Part::Two => vec![valley.end, valley.start, valley.end],
+        };
+
+        // With no way through, there's still the wind to watch
+        let route = valley.route(goals).unwrap_or(vec![valley.start]);
+
+        Box::new(Crossing { valley: valley.clone(), route, minute: 0 })
+    }
+}
+
+// A valley about five times wider than it's tall, like the real one, with
+// wind over most of it. A valley with no way across and back would leave
+// nothing to solve, so it gets thrown away for another try.
+impl Generated for Day24 {
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+        // Inside the walls
+        let (width, height) = (4 + size * 116 / 100, 2 + size * 23 / 100);
+        let start = (1, 0);
+        let end = (width, height + 1);
+
+        // Each try has less wind than the last, so one gets through eventually
+        let mut calm = 0;
+
+        loop {
+            let mut map = Grid::new(width + 2, height + 2, '#');
+            map[start] = '.';
+            map[end] = '.';
+
+            for y in 1..=height {
+                for x in 1..=width {
+                    // Wind blowing up or down the way in or out would blow
+                    // straight out of the valley
+                    let across_only = x == 1 || x == width;
+
+                    map[(x, y)] = match rng.below(6 + calm) {
+                        0 | 1 => '>',
+                        2 | 3 => '<',
+                        4 if !across_only => '^',
+                        5 if !across_only => 'v',
+                        _ => '.',
+                    };
+                }
+            }
+
+            if can_cross(&map, start, end) && can_cross(&map, end, start) {
+                return map.rows().map(|row| row.iter().collect()).collect();
+            }
+
+            calm += 1;
+        }
+    }
+}
+
+// Whether there's any way to get from `from` to `to` through the wind in
+// `map`, starting at minute 0. The wind's back where it started every
+// lcm(width, height) minutes, so being somewhere at the same point in that
+// cycle twice is no use.
+fn can_cross(map: &Grid<char>, from: (usize, usize), to: (usize, usize)) -> bool {
+    let (width, height) = (map.width() - 2, map.height() - 2);
+    let cycle = width / gcd(width, height) * height;
+
+    let winds: Vec<((usize, usize), char)> = map
+        .iter()
+        .filter(|(_, c)| matches!(c, '<' | '>' | '^' | 'v'))
+        .map(|(loc, c)| (loc, *c))
+        .collect();
+
+    let mut seen: HashSet<(usize, (usize, usize))> = HashSet::from([(0, from)]);
+    let mut here = vec![from];
+    let mut minute = 0;
+
+    loop {
+        minute += 1;
+        let mut windy = Grid::new(map.width(), map.height(), false);
+
+        for ((x, y), wind) in &winds {
+            let (x, y) = (x - 1, y - 1);
+
+            let loc = match wind {
+                '>' => ((x + minute) % width, y),
+                '<' => ((x + width - minute % width) % width, y),
+                'v' => (x, (y + minute) % height),
+                _ => (x, (y + height - minute % height) % height),
+            };
+
+            windy[(loc.0 + 1, loc.1 + 1)] = true;
+        }
+
+        let mut next = vec![];
+
+        for (x, y) in here {
+            let moves = [(x, y), (x + 1, y), (x, y + 1), (x.wrapping_sub(1), y), (x, y.wrapping_sub(1))];
+
+            for loc in moves {
+                if loc == to {
+                    return true;
+                }
+
+                let open = map.get(loc.0, loc.1).is_some_and(|c| *c != '#') && !windy[loc];
+
+                if open && seen.insert((minute % cycle, loc)) {
+                    next.push(loc);
+                }
+            }
+        }
+
+        if next.is_empty() {
+            return false;
+        }
+
+        here = next;
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+type Loc = Point2<isize>;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Valley {
+    width: usize,
+    height: usize,
+    start: Loc,
+    end: Loc,
+    north_winds: Vec<Loc>,
+    east_winds: Vec<Loc>,
+    south_winds: Vec<Loc>,
+    west_winds: Vec<Loc>,
+    walls: Vec<Loc>,
+}
+
+impl Valley {
+    pub fn import(input: &[String]) -> Result<Self, ParseError> {
+        let map = Grid::try_parse(input, "`#`, `.` or a wind (<, >, ^, v)", |c| match c {
+            '#' | '.' | '<' | '>' | '^' | 'v' => Some(c),
+            _ => None,
+        })?;
+        let (width, height) = (map.width(), map.height());
+
+        // We come in through a gap at the top left and leave at the bottom right
+        let gaps = [
+            (1, 0, "the entrance (.) in the top wall"),
+            (width.saturating_sub(2), height.saturating_sub(1), "the exit (.) in the bottom wall"),
+        ];
+
+        for (x, y, expected) in gaps {
+            if width < 3 || height < 3 || map[(x, y)] != '.' {
+                let line = input.get(y).map(|l| l.as_str()).unwrap_or("");
+                return Err(ParseError::at_char(line, x, expected).on_line(y));
+            }
+        }
+
+        let mut north_winds: Vec<Loc> = vec![];
+        let mut east_winds: Vec<Loc> = vec![];
+        let mut south_winds: Vec<Loc> = vec![];
+        let mut west_winds: Vec<Loc> = vec![];
+
+        let mut walls: Vec<Loc> = vec![];
+
+        map.iter().for_each(|((x, y), c)| {
+            let loc = Point2::new(x as isize, y as isize);
+
+            match (c, Direction::from_char(*c)) {
+                ('#', _) => { walls.push(loc); }
+                (_, Some(Direction::West)) => { west_winds.push(loc); }
+                (_, Some(Direction::East)) => { east_winds.push(loc); }
+                (_, Some(Direction::North)) => { north_winds.push(loc); }
+                (_, Some(Direction::South)) => { south_winds.push(loc); }
+                _ => {}
+            }
+        });
+
+        Ok(Self {
+            width,
+            height,
+            start: Point2::new(1, 0),
+            end: Point2::new(width as isize - 2, height as isize - 1),
+            north_winds,
+            east_winds,
+            south_winds,
+            west_winds,
+            walls,
+        })
+    }
+
+    // Calculates next positions of each bit of wind and returns new instance
+    pub fn next_frame(&self) -> Self {
+        let north_winds = self.north_winds
+            .iter()
+            .map(|l| self.next_wind_loc(Direction::North, *l))
+            .collect();
+
+        let east_winds = self.east_winds
+            .iter()
+            .map(|l| self.next_wind_loc(Direction::East, *l))
+            .collect();
+
+        let south_winds = self.south_winds
+            .iter()
+            .map(|l| self.next_wind_loc(Direction::South, *l))
+            .collect();
+
+        let west_winds = self.west_winds
+            .iter()
+            .map(|l| self.next_wind_loc(Direction::West, *l))
+            .collect();
+
+        Valley {
+            north_winds,
+            east_winds,
+            south_winds,
+            west_winds,
+            width: self.width,
+            height: self.height,
+            start: self.start,
+            end: self.end,
+            walls: self.walls.clone(),
+        }
+    }
+
+    // Where wind blowing `direction` from `current` is a minute later: a step
+    // along, or back in from the wall on the far side
+    fn next_wind_loc(&self, direction: Direction, current: Loc) -> Loc {
+        let ahead = current.step(direction, YAxis::Down);
+        let wrap = |v: isize, size: usize| (v - 1).rem_euclid(size as isize - 2) + 1;
+
+        Point2::new(wrap(ahead.x, self.width), wrap(ahead.y, self.height))
+    }
+
+    fn available_positions(&self, current: Loc) -> Vec<Loc> {
+        let Point2 { x, y } = current;
+        [
+            Point2::new(x, y - 1),
+            Point2::new(x - 1, y),
+            Point2::new(x, y),
+            Point2::new(x + 1, y),
+            Point2::new(x, y + 1),
+        ]
+            .into_iter()
+            .filter(|pos| {
+                if pos.x < 0 || pos.x > self.width as isize - 1 || pos.y < 0 || pos.y > self.height as isize - 1 {
+                    return false;
+                }
+
+                !self.walls.contains(pos)
+                    && !self.north_winds.contains(pos)
+                    && !self.east_winds.contains(pos)
+                    && !self.south_winds.contains(pos)
+                    && !self.west_winds.contains(pos)
+            })
+            .collect()
+    }
+
+    pub fn shortest_path(&self, goals: Vec<Loc>) -> Option<usize> {
+        self.route(goals).map(|route| route.len() - 1)
+    }
+
+    // Where the expedition is at each minute, on the quickest way to each of
+    // the goals in turn (popping them off the end), or None if one can't be reached
+    pub fn route(&self, mut goals: Vec<Loc>) -> Option<Vec<Loc>> {
+        // The wind's back where it started every lcm(width, height) minutes
+        // (inside the walls), so there are only that many valleys to work out
+        let (width, height) = (self.width - 2, self.height - 2);
+        let cycle = width / gcd(width, height) * height;
+
+        // The valley at each minute of the cycle so far, worked out as the
+        // search first needs it
+        let mut valley_states: Vec<Self> = vec![self.clone()];
+
+        // Search nodes are (minute of the cycle, position), as the same spot
+        // can be safe one minute and full of wind the next. Having only so many
+        // of them is what lets the search give up when there's no way through.
+        let mut at = (0, self.start);
+        let mut route = vec![self.start];
+
+        let mut progress = Progress::new("valleys worked out", cycle as u64);
+
+        while let Some(goal) = goals.pop() {
+            let leg = search::bfs(
+                at,
+                |&(minute, pos)| {
+                    let next = (minute + 1) % cycle;
+
+                    if valley_states.len() <= next {
+                        progress.set(next as u64 + 1);
+
+                        let next_valley = valley_states[minute].next_frame();
+                        valley_states.push(next_valley);
+                    }
+
+                    valley_states[next]
+                        .available_positions(pos)
+                        .into_iter()
+                        .map(move |loc| (next, loc))
+                },
+                |&(_, pos)| pos == goal,
+            );
+
+            // The next leg starts from wherever, and whenever, this one got to
+            let path = leg.path?;
+            at = *path.end();
+
+            route.extend(path.nodes.iter().skip(1).map(|(_, pos)| *pos));
+            common::debug!(
+                "reached {goal:?} at minute {}, after going through {} positions",
+                route.len() - 1,
+                leg.stats.expanded,
+            );
+        }
+
+        Some(route)
+    }
+
+    // Draws the valley with the expedition at `current`, as `E`. More than one
+    // wind in the same spot shows how many there are, and a `?` means the
+    // expedition is somewhere it shouldn't be.
+    pub fn frame(&self, current: Loc) -> Frame {
+        let cells = (0..self.height as isize).flat_map(|y| (0..self.width as isize).map(move |x| Point2::new(x, y)));
+
+        let cells = cells.map(|pos| {
+            let w = self.walls.contains(&pos);
+            let n_c = self.north_winds.contains(&pos);
+            let e_c = self.east_winds.contains(&pos);
+            let s_c = self.south_winds.contains(&pos);
+            let w_c = self.west_winds.contains(&pos);
+
+            let wind_count = [n_c, e_c, s_c, w_c].iter().filter(|c| **c).count();
+
+            let c = if (w || wind_count > 0) && pos == current {
+                '?'
+            } else if w {
+                '#'
+            } else if pos == current {
+                'E'
+            } else if wind_count == 0 {
+                '.'
+            } else if wind_count > 1 {
+                char::from_digit(wind_count as u32, 10).unwrap()
+            } else {
+                let blowing = [(n_c, Direction::North), (e_c, Direction::East), (s_c, Direction::South), (w_c, Direction::West)];
+                blowing.iter().find(|(here, _)| *here).map_or('.', |(_, direction)| direction.arrow())
+            };
+
+            (pos.into(), c)
+        });
+
+        Frame::draw(cells, '.')
+    }
+}
+
+pub struct Crossing {
+    valley: Valley,
+    // Where the expedition is at each minute
+    route: Vec<Loc>,
+    minute: usize,
+}
+
+// A step is one minute of wind, with the expedition keeping out of its way
+impl Simulation for Crossing {
+    fn step(&mut self) -> bool {
+        if self.minute + 1 >= self.route.len() {
+            return false;
+        }
+
+        self.valley = self.valley.next_frame();
+        self.minute += 1;
+        true
+    }
+
+    fn render_frame(&self) -> Frame {
+        self.valley.frame(self.route[self.minute])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn get_input_simple() -> Vec<String> {
+        vec![
+            "#.#####".to_string(),
+            "#.....#".to_string(),
+            "#>....#".to_string(),
+            "#.....#".to_string(),
+            "#...v.#".to_string(),
+            "#.....#".to_string(),
+            "#####.#".to_string(),
+        ]
+    }
+
+    fn get_input_complex() -> Vec<String> {
+        vec![
+            "#.######".to_string(),
+            "#>>.<^<#".to_string(),
+            "#.<..<<#".to_string(),
+            "#>v.><>#".to_string(),
+            "#<^v^^>#".to_string(),
+            "######.#".to_string(),
+        ]
+    }
+
+    #[test]
+    fn test_valley_import() {
+        let valley = Valley::import(&get_input_simple()).unwrap();
+
+        assert_eq!(
+            valley,
+            Valley {
+                width: 7,
+                height: 7,
+                start: Point2::new(1, 0),
+                end: Point2::new(5, 6),
+                north_winds: vec![],
+                east_winds: vec![Point2::new(1, 2)],
+                south_winds: vec![Point2::new(4, 4)],
+                west_winds: vec![],
+                walls: vec![
+                    Point2::new(0, 0), Point2::new(2, 0), Point2::new(3, 0), Point2::new(4, 0), Point2::new(5, 0), Point2::new(6, 0),
+                    Point2::new(0, 1), Point2::new(6, 1),
+                    Point2::new(0, 2), Point2::new(6, 2),
+                    Point2::new(0, 3), Point2::new(6, 3),
+                    Point2::new(0, 4), Point2::new(6, 4),
+                    Point2::new(0, 5), Point2::new(6, 5),
+                    Point2::new(0, 6), Point2::new(1, 6), Point2::new(2, 6), Point2::new(3, 6), Point2::new(4, 6), Point2::new(6, 6),
+                ]
+            }
+        )
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let valley = Valley::import(&get_input_complex()).unwrap();
+
+        assert_eq!(valley.shortest_path(vec![valley.end]), Some(18));
+    }
+
+    #[test]
+    fn test_shortes_path_multiple() {
+        let valley = Valley::import(&get_input_complex()).unwrap();
+
+        assert_eq!(valley.shortest_path(vec![valley.end, valley.start, valley.end]), Some(54));
+    }
+
+    #[test]
+    fn test_no_way_through() {
+        // A wall right across the valley, with wind blowing along either side
+        let valley = Valley::import(&[
+            "#.####".to_string(),
+            "#>...#".to_string(),
+            "######".to_string(),
+            "#..<.#".to_string(),
+            "####.#".to_string(),
+        ]).unwrap();
+
+        assert_eq!(valley.shortest_path(vec![valley.end]), None);
+        assert_eq!(Day24::part_one(&valley), Answer::Unsolved);
+        assert_eq!(Day24::part_two(&valley), Answer::Unsolved);
+    }
+
+    #[test]
+    fn test_simulation() {
+        let valley = Valley::import(&get_input_complex()).unwrap();
+        let mut crossing = Day24::simulation(&valley, Part::One);
+
+        assert_eq!(
+            crossing.render_frame().cells.to_string(),
+            [
+                "#E######",
+                "#>>.<^<#",
+                "#.<..<<#",
+                "#>v.><>#",
+                "#<^v^^>#",
+                "######.#",
+            ].join("\n"),
+        );
+
+        // Waiting a minute works out just as quick as the puzzle's route
+        assert!(crossing.step());
+        assert_eq!(
+            crossing.render_frame().cells.to_string(),
+            [
+                "#E######",
+                "#.>3.<.#",
+                "#<..<<.#",
+                "#>2.22.#",
+                "#>v..^<#",
+                "######.#",
+            ].join("\n"),
+        );
+
+        // Minutes 1 to 18, ending up at the exit
+        assert_eq!(common::frames(crossing.as_mut()).count(), 18);
+        assert!(crossing.render_frame().cells.to_string().ends_with("######E#"));
+    }
+}
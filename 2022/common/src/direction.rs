@@ -0,0 +1,270 @@
+/// One of the four ways along a grid, for the puzzles that walk, blow or get
+/// pushed around one. In order, clockwise from north.
+///
+/// The puzzles call them all sorts (`^>v<`, `UDLR`, `NESW`), and `from_char`
+/// takes any of them. Which way north is in `(x, y)` depends on the day, so
+/// `delta` asks: `YAxis::Down` for a map read from the input, where y counts
+/// down the rows the same as `Grid`, and `YAxis::Up` for the days that
+/// count it up, like day 9's rope.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+/// `Direction`, with the diagonals between them as well. In order, clockwise
+/// from north.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+/// Which way y counts, going north to south.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum YAxis {
+    // Down the screen, with north at -y
+    Down,
+    // Up, as in maths, with north at +y
+    Up,
+}
+
+impl YAxis {
+    // What a step south does to y
+    fn south(self) -> isize {
+        match self {
+            YAxis::Down => 1,
+            YAxis::Up => -1,
+        }
+    }
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    /// Where it is in `ALL`, counting clockwise from north.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The direction `index` quarter turns clockwise from north, any number
+    /// of times round.
+    pub fn from_index(index: usize) -> Direction {
+        Direction::ALL[index % 4]
+    }
+
+    /// A quarter turn clockwise for each of `turns`, or anticlockwise for a
+    /// negative number.
+    pub fn turn(self, turns: isize) -> Direction {
+        Direction::from_index((self.index() as isize + turns).rem_euclid(4) as usize)
+    }
+
+    pub fn turn_right(self) -> Direction {
+        self.turn(1)
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.turn(-1)
+    }
+
+    pub fn reverse(self) -> Direction {
+        self.turn(2)
+    }
+
+    /// The `(x, y)` offset of one step this way.
+    pub fn delta(self, y_axis: YAxis) -> (isize, isize) {
+        match self {
+            Direction::North => (0, -y_axis.south()),
+            Direction::East => (1, 0),
+            Direction::South => (0, y_axis.south()),
+            Direction::West => (-1, 0),
+        }
+    }
+
+    /// `from` moved a step this way.
+    pub fn step(self, from: (isize, isize), y_axis: YAxis) -> (isize, isize) {
+        let (dx, dy) = self.delta(y_axis);
+        (from.0 + dx, from.1 + dy)
+    }
+
+    /// From an arrow (`^>v<`), a letter for up, right, down or left
+    /// (`URDL`) or a compass point (`NESW`).
+    pub fn from_char(c: char) -> Option<Direction> {
+        match c {
+            '^' | 'U' | 'N' => Some(Direction::North),
+            '>' | 'R' | 'E' => Some(Direction::East),
+            'v' | 'D' | 'S' => Some(Direction::South),
+            '<' | 'L' | 'W' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    /// As an arrow, the way the puzzles draw them.
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+        }
+    }
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    /// Where it is in `ALL`, counting clockwise from north.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn from_index(index: usize) -> Direction8 {
+        Direction8::ALL[index % 8]
+    }
+
+    /// An eighth of a turn clockwise for each of `turns`, or anticlockwise
+    /// for a negative number.
+    pub fn turn(self, turns: isize) -> Direction8 {
+        Direction8::from_index((self.index() as isize + turns).rem_euclid(8) as usize)
+    }
+
+    pub fn turn_right(self) -> Direction8 {
+        self.turn(1)
+    }
+
+    pub fn turn_left(self) -> Direction8 {
+        self.turn(-1)
+    }
+
+    pub fn reverse(self) -> Direction8 {
+        self.turn(4)
+    }
+
+    /// The `(x, y)` offset of one step this way, diagonals moving on both.
+    pub fn delta(self, y_axis: YAxis) -> (isize, isize) {
+        let (dx, dy) = match self {
+            Direction8::North => (0, -1),
+            Direction8::NorthEast => (1, -1),
+            Direction8::East => (1, 0),
+            Direction8::SouthEast => (1, 1),
+            Direction8::South => (0, 1),
+            Direction8::SouthWest => (-1, 1),
+            Direction8::West => (-1, 0),
+            Direction8::NorthWest => (-1, -1),
+        };
+
+        (dx, dy * y_axis.south())
+    }
+
+    /// `from` moved a step this way.
+    pub fn step(self, from: (isize, isize), y_axis: YAxis) -> (isize, isize) {
+        let (dx, dy) = self.delta(y_axis);
+        (from.0 + dx, from.1 + dy)
+    }
+
+    /// From a compass point, `N` to `NW`, or anything `Direction::from_char`
+    /// takes on its own.
+    pub fn parse(s: &str) -> Option<Direction8> {
+        match s {
+            "NE" => Some(Direction8::NorthEast),
+            "SE" => Some(Direction8::SouthEast),
+            "SW" => Some(Direction8::SouthWest),
+            "NW" => Some(Direction8::NorthWest),
+            _ => {
+                let mut chars = s.chars();
+                let direction = chars.next().and_then(Direction::from_char)?;
+                chars.next().is_none().then_some(direction.into())
+            }
+        }
+    }
+
+    /// The straight direction, for the four that are one.
+    pub fn straight(self) -> Option<Direction> {
+        match self {
+            Direction8::North => Some(Direction::North),
+            Direction8::East => Some(Direction::East),
+            Direction8::South => Some(Direction::South),
+            Direction8::West => Some(Direction::West),
+            _ => None,
+        }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Direction8 {
+        Direction8::from_index(direction.index() * 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::direction::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::East.reverse(), Direction::West);
+        assert_eq!(Direction::South.turn(-3), Direction::West);
+        assert_eq!(Direction::West.turn(9), Direction::North);
+        assert_eq!(Direction::from_index(6), Direction::South);
+
+        assert_eq!(Direction8::North.turn_right(), Direction8::NorthEast);
+        assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+        assert_eq!(Direction8::SouthEast.reverse(), Direction8::NorthWest);
+        assert_eq!(Direction8::West.turn(-10), Direction8::South);
+    }
+
+    #[test]
+    fn test_deltas() {
+        let down: Vec<(isize, isize)> = Direction::ALL.iter().map(|d| d.delta(YAxis::Down)).collect();
+        assert_eq!(down, vec![(0, -1), (1, 0), (0, 1), (-1, 0)]);
+
+        let up: Vec<(isize, isize)> = Direction::ALL.iter().map(|d| d.delta(YAxis::Up)).collect();
+        assert_eq!(up, vec![(0, 1), (1, 0), (0, -1), (-1, 0)]);
+
+        assert_eq!(Direction::North.step((3, 3), YAxis::Down), (3, 2));
+        assert_eq!(Direction8::NorthEast.step((3, 3), YAxis::Down), (4, 2));
+        assert_eq!(Direction8::NorthEast.step((3, 3), YAxis::Up), (4, 4));
+
+        // The straight ones agree either way
+        for direction in Direction::ALL {
+            for y_axis in [YAxis::Down, YAxis::Up] {
+                assert_eq!(Direction8::from(direction).delta(y_axis), direction.delta(y_axis));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("^>v<".chars().map(Direction::from_char).collect::<Vec<_>>(), Direction::ALL.map(Some).to_vec());
+        assert_eq!("URDL".chars().map(Direction::from_char).collect::<Vec<_>>(), Direction::ALL.map(Some).to_vec());
+        assert_eq!("NESW".chars().map(Direction::from_char).collect::<Vec<_>>(), Direction::ALL.map(Some).to_vec());
+        assert_eq!(Direction::from_char('x'), None);
+        assert_eq!(Direction::South.arrow(), 'v');
+
+        assert_eq!(Direction8::parse("NE"), Some(Direction8::NorthEast));
+        assert_eq!(Direction8::parse("<"), Some(Direction8::West));
+        assert_eq!(Direction8::parse("NN"), None);
+        assert_eq!(Direction8::parse(""), None);
+        assert_eq!(Direction8::SouthWest.straight(), None);
+        assert_eq!(Direction8::South.straight(), Some(Direction::South));
+    }
+}
@@ -0,0 +1,182 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Why some input couldn't be parsed, and where.
+///
+/// Parsers for a single line don't know which line they're on, so they leave
+/// `line` at 0 and whoever is going through the lines fills it in with
+/// `on_line` (or lets `parse_lines` do it).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    /// Index into the input lines, starting at 0.
+    pub line: usize,
+    /// Char offset into the line, starting at 0.
+    pub column: usize,
+    /// What was found instead. Empty when the line (or input) ended early.
+    pub text: String,
+    /// What should have been there, e.g. "a number".
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(column: usize, text: &str, expected: impl Into<String>) -> Self {
+        ParseError {
+            line: 0,
+            column,
+            text: text.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Error about `token`, which should be a slice of `line`: the column is
+    /// worked out from where it sits in there. A `token` that was built
+    /// separately is looked up in `line` instead, and failing that the error
+    /// points past the end of the line.
+    pub fn in_line(line: &str, token: &str, expected: impl Into<String>) -> Self {
+        let line_start = line.as_ptr() as usize;
+        let token_start = token.as_ptr() as usize;
+
+        let offset = if token_start >= line_start && token_start + token.len() <= line_start + line.len() {
+            token_start - line_start
+        } else {
+            line.find(token).unwrap_or(line.len())
+        };
+
+        Self::new(line[..offset].chars().count(), token, expected)
+    }
+
+    /// Error about the char at `column` of `line`, or its end if it's shorter.
+    pub fn at_char(line: &str, column: usize, expected: impl Into<String>) -> Self {
+        match line.chars().nth(column) {
+            Some(c) => Self::new(column, &c.to_string(), expected),
+            None => Self::end_of_line(line, expected),
+        }
+    }
+
+    /// Error about a line that ran out before `expected` turned up.
+    pub fn end_of_line(line: &str, expected: impl Into<String>) -> Self {
+        Self::new(line.chars().count(), "", expected)
+    }
+
+    /// For an error from parsing `part`, a slice of `line`: moves the column
+    /// so it counts from the start of `line` instead.
+    pub fn within(mut self, line: &str, part: &str) -> Self {
+        self.column += Self::in_line(line, part, "").column;
+        self
+    }
+
+    /// Sets which line of the input this is about.
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    /// Multi-line report pointing at the problem, for showing to whoever
+    /// supplied the input:
+    ///
+    /// ```text
+    /// line 2, column 6: expected a number, found `x`
+    ///   |
+    /// 2 | move x from 1 to 3
+    ///   |      ^
+    /// ```
+    pub fn diagnostic(&self, input: &[String]) -> String {
+        match input.get(self.line) {
+            Some(source) => self.diagnostic_for(source),
+            None => self.to_string(),
+        }
+    }
+
+    /// `diagnostic`, given only the line the error is about.
+    pub fn diagnostic_for(&self, source: &str) -> String {
+        let number = (self.line + 1).to_string();
+        let gutter = " ".repeat(number.len());
+        let carets = "^".repeat(self.text.chars().count().max(1));
+
+        format!(
+            "{self}\n{gutter} |\n{number} | {source}\n{gutter} | {}{carets}",
+            " ".repeat(self.column),
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: expected {}", self.line + 1, self.column + 1, self.expected)?;
+
+        if self.text.is_empty() {
+            write!(f, ", found nothing")
+        } else {
+            write!(f, ", found `{}`", self.text)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses each line with `f`, filling in the line number on any error.
+pub fn parse_lines<T, F>(input: &[String], mut f: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&str) -> Result<T, ParseError>,
+{
+    input
+        .iter()
+        .enumerate()
+        .map(|(i, l)| f(l).map_err(|e| e.on_line(i)))
+        .collect()
+}
+
+/// Parses `token`, a slice of `line`, as a number.
+pub fn parse_number<T: FromStr>(line: &str, token: &str) -> Result<T, ParseError> {
+    token.parse().map_err(|_| ParseError::in_line(line, token, "a number"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_column_from_token() {
+        let line = "move 1 from x to 3";
+        let token = line.split(' ').nth(3).unwrap();
+
+        assert_eq!(
+            ParseError::in_line(line, token, "a stack number"),
+            ParseError {
+                line: 0,
+                column: 12,
+                text: "x".to_string(),
+                expected: "a stack number".to_string(),
+            },
+        );
+
+        assert_eq!(ParseError::end_of_line(line, "more").column, 18);
+        assert_eq!(ParseError::at_char(line, 2, "a letter").text, "v");
+        assert_eq!(ParseError::at_char(line, 30, "a letter").column, 18);
+
+        let part = &line[5..];
+        assert_eq!(ParseError::in_line(part, &part[7..8], "a stack number").within(line, part).column, 12);
+        assert_eq!(parse_number::<usize>(line, token).unwrap_err().expected, "a number");
+        assert_eq!(parse_number::<usize>(line, &line[5..6]), Ok(1));
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let input = vec!["1".to_string(), "2".to_string(), "three".to_string()];
+        let err = parse_lines(&input, |l| parse_number::<i64>(l, l)).unwrap_err();
+
+        assert_eq!(err.line, 2);
+        assert_eq!(err.to_string(), "line 3, column 1: expected a number, found `three`");
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let input = vec!["move 1 from 2 to 3".to_string(), "move x from 1 to 3".to_string()];
+        let err = ParseError::in_line(&input[1], &input[1][5..6], "a number").on_line(1);
+
+        assert_eq!(
+            err.diagnostic(&input),
+            "line 2, column 6: expected a number, found `x`\n  |\n2 | move x from 1 to 3\n  |      ^",
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use crate::Solution;
+
+/// Small, seedable random number generator (SplitMix64), so that the same
+/// seed always makes the same input, on any machine and Rust version.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from 0 up to, but not including, `n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "nothing below 0 to pick from");
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A number from `low` to `high`, both included.
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        assert!(low <= high, "empty range {low}..={high}");
+        low + (self.next_u64() % (high - low + 1) as u64) as i64
+    }
+
+    /// True one time in `n`, on average.
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// A solution that can make up random inputs for itself, in exactly the
+/// format its parser takes, and that it can solve with the default `Params`.
+pub trait Generated: Solution {
+    /// `size` is how big to make the input, roughly: 1 is about as small as
+    /// it gets, and 100 about the size of the real puzzle input.
+    fn generate(rng: &mut Rng, size: usize) -> Vec<String>;
+}
+
+pub type GenerateFn = fn(u64, usize) -> Vec<String>;
+
+/// Makes an input for solution `S` from `seed`. `generate::<Day20>` coerces
+/// to a `GenerateFn`.
+pub fn generate<S: Generated>(seed: u64, size: usize) -> Vec<String> {
+    S::generate(&mut Rng::new(seed), size)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_rng() {
+        let mut one = Rng::new(7);
+        let mut other = Rng::new(7);
+        let rolls: Vec<u64> = (0..5).map(|_| one.next_u64()).collect();
+
+        assert_eq!(rolls, (0..5).map(|_| other.next_u64()).collect::<Vec<u64>>());
+        assert_ne!(rolls, (0..5).map(|_| Rng::new(8).next_u64()).collect::<Vec<u64>>());
+
+        for _ in 0..1000 {
+            assert!(one.below(3) < 3);
+            assert!((-2..=2).contains(&one.range(-2, 2)));
+        }
+
+        let mut items: Vec<usize> = (0..10).collect();
+        one.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, (0..10).collect::<Vec<usize>>());
+    }
+}
@@ -0,0 +1,428 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::ParseError;
+
+/// A rectangular 2D map of cells, for all the puzzles whose input is a picture.
+///
+/// Coordinates are always `(x, y)`: x is the column, counting right, and y is
+/// the row, counting down from the first line of input.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+// Offsets for the 4 orthogonal neighbours, clockwise from north
+const NEIGHBOURS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+// Offsets for all 8 surrounding cells, clockwise from north
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1),
+];
+
+impl<T> Grid<T> {
+    /// Builds a grid out of row-major cells. Panics if they don't fill
+    /// `width` columns exactly.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells don't make rows of {width}",
+            cells.len(),
+        );
+
+        Grid { width, height: cells.len() / width, cells }
+    }
+
+    /// Parses lines of input, turning each char into a cell with `f`.
+    ///
+    /// Lines shorter than the longest one are padded out with spaces (day 22's
+    /// map has ragged edges), so `f` should know what to do with `' '`.
+    pub fn parse<F>(lines: &[String], mut f: F) -> Self
+    where
+        F: FnMut(char) -> T,
+    {
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+
+        let cells = lines
+            .iter()
+            .flat_map(|l| l.chars().chain(std::iter::repeat(' ')).take(width))
+            .map(&mut f)
+            .collect();
+
+        Grid { width, height: lines.len(), cells }
+    }
+
+    /// Like `parse`, but `f` can turn down a char, in which case the error
+    /// points at it and says what was `expected` instead.
+    pub fn try_parse<F>(lines: &[String], expected: &str, mut f: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for (y, line) in lines.iter().enumerate() {
+            for (x, c) in line.chars().chain(std::iter::repeat(' ')).take(width).enumerate() {
+                let cell = f(c).ok_or_else(|| ParseError::at_char(line, x, expected).on_line(y))?;
+                cells.push(cell);
+            }
+        }
+
+        Ok(Grid { width, height: lines.len(), cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Same as `get`, for the days that keep their locations signed.
+    pub fn get_signed(&self, x: isize, y: isize) -> Option<&T> {
+        if self.contains(x, y) {
+            self.get(x as usize, y as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Replaces the cell at (x, y). Returns false, leaving the grid alone, if
+    /// that's outside the grid.
+    pub fn set(&mut self, x: usize, y: usize, value: T) -> bool {
+        match self.get_mut(x, y) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+
+            None => false,
+        }
+    }
+
+    /// Locations of the (up to) 4 cells sharing an edge with (x, y),
+    /// clockwise from north.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets(x, y, &NEIGHBOURS_4)
+    }
+
+    /// Locations of the (up to) 8 cells around (x, y), diagonals included,
+    /// clockwise from north.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets(x, y, &NEIGHBOURS_8)
+    }
+
+    fn offsets<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets
+            .iter()
+            .map(move |(dx, dy)| (x as isize + dx, y as isize + dy))
+            .filter(|(nx, ny)| self.contains(*nx, *ny))
+            .map(|(nx, ny)| (nx as usize, ny as usize))
+    }
+
+    /// Locations walking away from (x, y) in steps of (dx, dy), up to the
+    /// edge. The starting location itself isn't included.
+    pub fn ray(
+        &self,
+        x: usize,
+        y: usize,
+        dx: isize,
+        dy: isize,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (1..)
+            .map(move |n| (x as isize + dx * n, y as isize + dy * n))
+            .take_while(|(rx, ry)| self.contains(*rx, *ry))
+            .map(|(rx, ry)| (rx as usize, ry as usize))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells down column `x`, of which there are none past the right edge.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let height = if x < self.width { self.height } else { 0 };
+        self.cells.iter().skip(x).step_by(self.width.max(1)).take(height)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Every diagonal running down and to the right, starting with the one
+    /// in the bottom left corner and ending with the top right one.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        // Starting points go up the left edge, then along the top. A grid
+        // with no columns has no edge to start from.
+        let starts = (0..self.height)
+            .filter(|_| self.width > 0)
+            .rev()
+            .map(|y| (0, y))
+            .chain((1..self.width).map(|x| (x, 0)));
+
+        starts.map(move |(x, y)| {
+            std::iter::once((x, y))
+                .chain(self.ray(x, y, 1, 1))
+                .map(move |(dx, dy)| &self[(dx, dy)])
+        })
+    }
+
+    /// Every diagonal running down and to the left, starting with the one in
+    /// the top left corner and ending with the bottom right one.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        // Starting points go along the top, then down the right edge (if
+        // there is one)
+        let starts = (0..self.width)
+            .map(|x| (x, 0))
+            .chain((1..self.height).filter_map(move |y| Some((self.width.checked_sub(1)?, y))));
+
+        starts.map(move |(x, y)| {
+            std::iter::once((x, y))
+                .chain(self.ray(x, y, -1, 1))
+                .map(move |(dx, dy)| &self[(dx, dy)])
+        })
+    }
+
+    /// All locations, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width.max(1);
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    /// All cells along with their locations, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Location of the first cell, row by row, that matches `predicate`.
+    pub fn find<P>(&self, mut predicate: P) -> Option<(usize, usize)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().find(|(_, c)| predicate(c)).map(|(loc, _)| loc)
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid { width, height, cells: vec![fill; width * height] }
+    }
+
+    /// Flips the grid over its top-left to bottom-right diagonal, so rows
+    /// become columns.
+    pub fn transpose(&self) -> Self {
+        let cells = self.columns().flat_map(|c| c.cloned()).collect();
+
+        Grid { width: self.height, height: self.width, cells }
+    }
+
+    /// Turns the grid a quarter clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        let cells = (0..self.width)
+            .flat_map(|x| (0..self.height).rev().map(move |y| (x, y)))
+            .map(|(x, y)| self[(x, y)].clone())
+            .collect();
+
+        Grid { width: self.height, height: self.width, cells }
+    }
+
+    /// Turns the grid a quarter anticlockwise.
+    pub fn rotate_ccw(&self) -> Self {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| (0..self.height).map(move |y| (x, y)))
+            .map(|(x, y)| self[(x, y)].clone())
+            .collect();
+
+        Grid { width: self.height, height: self.width, cells }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).unwrap_or_else(|| panic!("({x}, {y}) is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y).unwrap_or_else(|| panic!("({x}, {y}) is outside the grid"))
+    }
+}
+
+/// One line per row, cells written side by side.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn lines(input: &[&str]) -> Vec<String> {
+        input.iter().map(|l| l.to_string()).collect()
+    }
+
+    fn digits() -> Grid<u32> {
+        Grid::parse(&lines(&["123", "456"]), |c| c.to_digit(10).unwrap())
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = digits();
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[(0, 0)], 1);
+        assert_eq!(grid[(2, 0)], 3);
+        assert_eq!(grid[(1, 1)], 5);
+
+        // Ragged lines get padded
+        let ragged = Grid::parse(&lines(&["  .#", ".#"]), |c| c);
+        assert_eq!(ragged.row(1), &['.', '#', ' ', ' ']);
+
+        let err = Grid::try_parse(&lines(&["123", "4x6"]), "a digit", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 1, "x"));
+    }
+
+    #[test]
+    fn test_get_and_set() {
+        let mut grid = digits();
+
+        assert_eq!(grid.get(2, 1), Some(&6));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+        assert_eq!(grid.get_signed(-1, 0), None);
+        assert_eq!(grid.get_signed(1, 1), Some(&5));
+
+        assert!(grid.set(0, 1, 9));
+        assert_eq!(grid[(0, 1)], 9);
+        assert!(!grid.set(5, 5, 9));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::new(3, 3, 0);
+
+        assert_eq!(
+            grid.neighbours4(1, 1).collect::<Vec<_>>(),
+            vec![(1, 0), (2, 1), (1, 2), (0, 1)],
+        );
+        assert_eq!(grid.neighbours4(0, 0).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+
+        assert_eq!(grid.neighbours8(1, 1).count(), 8);
+        assert_eq!(
+            grid.neighbours8(2, 2).collect::<Vec<_>>(),
+            vec![(2, 1), (1, 2), (1, 1)],
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = digits();
+
+        assert_eq!(grid.rows().collect::<Vec<_>>(), vec![&[1, 2, 3], &[4, 5, 6]]);
+        assert_eq!(grid.column(1).collect::<Vec<_>>(), vec![&2, &5]);
+        assert_eq!(grid.columns().count(), 3);
+
+        let diagonals: Vec<Vec<u32>> = grid.diagonals().map(|d| d.cloned().collect()).collect();
+        assert_eq!(diagonals, vec![vec![4], vec![1, 5], vec![2, 6], vec![3]]);
+
+        let anti: Vec<Vec<u32>> = grid.anti_diagonals().map(|d| d.cloned().collect()).collect();
+        assert_eq!(anti, vec![vec![1], vec![2, 4], vec![3, 5], vec![6]]);
+
+        assert_eq!(grid.ray(0, 0, 1, 0).collect::<Vec<_>>(), vec![(1, 0), (2, 0)]);
+        assert_eq!(grid.ray(2, 1, -1, -1).collect::<Vec<_>>(), vec![(1, 0)]);
+
+        // Nothing past the right edge, rather than the next row's cells
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(grid.column(4).count(), 0);
+    }
+
+    #[test]
+    fn test_lines_without_columns() {
+        let grid = Grid::parse(&["".to_string(), "".to_string()], |c| c);
+
+        assert_eq!((grid.width(), grid.height()), (0, 2));
+        assert_eq!(grid.column(0).count(), 0);
+        assert_eq!(grid.columns().count(), 0);
+        assert_eq!(grid.diagonals().count(), 0);
+        assert_eq!(grid.anti_diagonals().count(), 0);
+    }
+
+    #[test]
+    fn test_transform() {
+        let grid = digits();
+
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36");
+        assert_eq!(grid.rotate_cw().to_string(), "41\n52\n63");
+        assert_eq!(grid.rotate_ccw().to_string(), "36\n25\n14");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    }
+
+    #[test]
+    fn test_find_and_map() {
+        let grid = digits();
+
+        assert_eq!(grid.find(|c| *c == 5), Some((1, 1)));
+        assert_eq!(grid.find(|c| *c == 7), None);
+        assert_eq!(grid.map(|c| c % 2 == 0).iter().filter(|(_, c)| **c).count(), 3);
+    }
+}
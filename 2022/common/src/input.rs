@@ -0,0 +1,13 @@
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+pub fn read_stdin() -> io::Result<Vec<String>> {
+    io::stdin().lock().lines().collect()
+}
+
+pub fn read_file(path: &Path) -> io::Result<Vec<String>> {
+    let file = File::open(path)?;
+    BufReader::new(file).lines().collect()
+}
@@ -0,0 +1,139 @@
+mod direction;
+mod error;
+mod generate;
+mod grid;
+mod input;
+pub mod log;
+mod params;
+mod point;
+pub mod search;
+mod simulation;
+mod solution;
+mod stream;
+
+use std::env;
+use std::io::{self, BufRead};
+
+pub use direction::{Direction, Direction8, YAxis};
+pub use error::{parse_lines, parse_number, ParseError};
+pub use generate::{generate, GenerateFn, Generated, Rng};
+pub use grid::Grid;
+pub use input::{read_file, read_stdin};
+pub use log::Progress;
+pub use params::{NumberParam, Param, Params, TextParam, Value};
+pub use point::{Bounds2, Bounds3, Coordinate, Point2, Point3};
+pub use simulation::{frames, simulate, Frame, SimulateFn, Simulated, Simulation};
+pub use solution::{parse, Answer, ParseFn, Parsed, Solution};
+pub use stream::{parse_streamed, stream, Lines, StreamError, StreamFn, Streaming};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn parse(input: &str) -> Option<Part> {
+        match input {
+            "1" => Some(Part::One),
+            "2" => Some(Part::Two),
+            _ => None,
+        }
+    }
+
+    pub fn number(&self) -> usize {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// Entry point for a single day's own binary, so `cat input.txt | cargo run`
+/// keeps working from inside a day's folder.
+///
+/// Runs both parts, unless `--part <1|2>` picks one. `-v` and `-vv` show
+/// progress and debugging output on stderr.
+pub fn run<S>()
+where
+    S: Solution + 'static,
+    S::Input: 'static,
+{
+    let parts = parts_from_args();
+
+    let input = match read_stdin() {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("error: couldn't read stdin: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    let parsed = match parse::<S>(&input, &Params::new()) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("error: couldn't parse input, {}", e.diagnostic(&input));
+            std::process::exit(1);
+        }
+    };
+
+    for part in parts {
+        println!("{}", parsed.solve(part));
+    }
+}
+
+/// `run`, for a `Streaming` day: stdin goes through a line at a time rather
+/// than being read in whole first.
+pub fn run_streamed<S>()
+where
+    S: Streaming + 'static,
+    S::Input: 'static,
+{
+    let parts = parts_from_args();
+
+    let parsed = match stream::<S>(&mut io::stdin().lock().lines()) {
+        Ok(parsed) => parsed,
+        Err(e @ StreamError::Io(_)) => {
+            eprintln!("error: {e}");
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("error: couldn't parse input, {e}");
+            std::process::exit(1);
+        }
+    };
+
+    for part in parts {
+        println!("{}", parsed.solve(part));
+    }
+}
+
+fn parts_from_args() -> Vec<Part> {
+    let (verbosity, args) = log::take_verbosity(env::args().skip(1).collect());
+    log::set_verbosity(verbosity);
+
+    match args.iter().position(|a| a == "--part") {
+        Some(i) => match args.get(i + 1).and_then(|p| Part::parse(p)) {
+            Some(part) => vec![part],
+            None => {
+                eprintln!("--part expects 1 or 2");
+                std::process::exit(2);
+            }
+        },
+
+        None => vec![Part::One, Part::Two],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_part_parse() {
+        assert_eq!(Part::parse("1"), Some(Part::One));
+        assert_eq!(Part::parse("2"), Some(Part::Two));
+        assert_eq!(Part::parse("3"), None);
+        assert_eq!(Part::parse("one"), None);
+    }
+}
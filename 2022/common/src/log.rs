@@ -0,0 +1,337 @@
+//! Logging for the days and the runner, quiet unless asked for: `-v` shows
+//! `info!` messages and progress bars, `-vv` the `debug!` ones as well.
+//! It all goes to stderr, so the answers on stdout stay easy to pick out.
+
+use std::fmt;
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+// Every bar being drawn in a terminal, by id, with its latest line in full and
+// in brief. They all share the one line of stderr, so whichever of them moves
+// redraws the lot.
+static BARS: Mutex<Vec<(u64, String, String)>> = Mutex::new(vec![]);
+static NEXT_BAR: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+pub enum Level {
+    // Always shown
+    Warn,
+    // -v
+    Info,
+    // -vv
+    Debug,
+}
+
+impl Level {
+    fn label(&self) -> &'static str {
+        match self {
+            Level::Warn => "warning",
+            Level::Info => "info",
+            Level::Debug => "debug",
+        }
+    }
+}
+
+pub fn set_verbosity(verbosity: u8) {
+    VERBOSITY.store(verbosity, Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= VERBOSITY.load(Ordering::Relaxed)
+}
+
+/// Takes `-v`, `-vv` and `--verbose` (which can be given twice) out of
+/// `args`, up to a `--`, and returns how verbose they asked for along with
+/// the rest. Everything after the `--` is left as it is (without the `--`),
+/// so a value that happens to be `-v` can still be given.
+pub fn take_verbosity(args: Vec<String>) -> (u8, Vec<String>) {
+    let mut verbosity = 0u8;
+    let mut rest = vec![];
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let more = match arg.as_str() {
+            "-v" | "--verbose" => 1,
+            "-vv" => 2,
+            "--" => {
+                rest.extend(args.by_ref());
+                break;
+            }
+            _ => {
+                rest.push(arg);
+                continue;
+            }
+        };
+
+        verbosity = verbosity.saturating_add(more);
+    }
+
+    (verbosity, rest)
+}
+
+#[doc(hidden)]
+pub fn write(level: Level, message: fmt::Arguments) {
+    if enabled(level) {
+        eprintln!("{}: {message}", level.label());
+    }
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)*) => { $crate::log::write($crate::log::Level::Warn, format_args!($($arg)*)) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => { $crate::log::write($crate::log::Level::Info, format_args!($($arg)*)) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => { $crate::log::write($crate::log::Level::Debug, format_args!($($arg)*)) };
+}
+
+/// A progress bar on stderr for the loops that take a while, with how fast
+/// it's going and how long there is to go. Only shown with `-v`, and only
+/// once it's been going long enough to be worth watching. In a terminal it
+/// redraws in place, sharing the line with any other thread's, otherwise it
+/// writes a line every few seconds.
+///
+/// It's finished off when dropped, so stopping early (say, out of a `find`)
+/// needs nothing special.
+pub struct Progress {
+    id: u64,
+    label: String,
+    // None when there's no telling how far there is to go
+    total: Option<u64>,
+    done: u64,
+    started: Instant,
+    drawn: Option<Instant>,
+    shown: bool,
+    terminal: bool,
+}
+
+impl Progress {
+    pub fn new(label: &str, total: u64) -> Self {
+        Self::start(label, Some(total))
+    }
+
+    /// For when only how far it's got is known.
+    pub fn unbounded(label: &str) -> Self {
+        Self::start(label, None)
+    }
+
+    fn start(label: &str, total: Option<u64>) -> Self {
+        Progress {
+            id: NEXT_BAR.fetch_add(1, Ordering::Relaxed),
+            label: label.to_string(),
+            total,
+            done: 0,
+            started: Instant::now(),
+            drawn: None,
+            shown: enabled(Level::Info),
+            terminal: io::stderr().is_terminal(),
+        }
+    }
+
+    pub fn set(&mut self, done: u64) {
+        self.done = done;
+
+        if !self.shown {
+            return;
+        }
+
+        let now = Instant::now();
+        let wait = if self.terminal { Duration::from_millis(100) } else { Duration::from_secs(2) };
+
+        if now.duration_since(self.drawn.unwrap_or(self.started)) >= wait {
+            self.draw(false);
+            self.drawn = Some(now);
+        }
+    }
+
+    pub fn add(&mut self, done: u64) {
+        self.set(self.done + done);
+    }
+
+    fn draw(&self, last: bool) {
+        let elapsed = self.started.elapsed();
+        let line = describe(&self.label, self.done, self.total, elapsed);
+
+        if !self.terminal {
+            eprintln!("{line}");
+            return;
+        }
+
+        let mut bars = BARS.lock().unwrap_or_else(|e| e.into_inner());
+        let mut stderr = io::stderr().lock();
+
+        // A finished bar gets a line of its own, and the rest carry on after it
+        if last {
+            bars.retain(|(id, _, _)| *id != self.id);
+            let _ = writeln!(stderr, "\r\x1b[K{line}");
+        } else {
+            let brief = summarise(&self.label, self.done, self.total, elapsed);
+
+            match bars.iter_mut().find(|(id, _, _)| *id == self.id) {
+                Some(bar) => *bar = (self.id, line, brief),
+                None => bars.push((self.id, line, brief)),
+            }
+        }
+
+        if !bars.is_empty() {
+            let _ = write!(stderr, "\r\x1b[K{}", combine(&bars));
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        // Anything quick enough never to have been drawn isn't worth a line
+        if self.shown && self.drawn.is_some() {
+            self.draw(true);
+        }
+    }
+}
+
+// One bar's line in full, or several in brief so that they fit side by side:
+// "scanning rows 35% ETA 2s | mixing 80% ETA 1s"
+fn combine(bars: &[(u64, String, String)]) -> String {
+    match bars {
+        [(_, line, _)] => line.clone(),
+        bars => bars.iter().map(|(_, _, brief)| brief.as_str()).collect::<Vec<_>>().join(" | "),
+    }
+}
+
+// "scanning rows [#######.............] 35% 1.4M/4M 2.1M/s ETA 1s"
+fn describe(label: &str, done: u64, total: Option<u64>, elapsed: Duration) -> String {
+    let secs = elapsed.as_secs_f64();
+    let rate = if secs > 0.0 { done as f64 / secs } else { 0.0 };
+
+    let total = match total {
+        None => return format!("{label} {} {}/s", count(done as f64), count(rate)),
+        Some(total) => total.max(1),
+    };
+
+    let fraction = (done as f64 / total as f64).min(1.0);
+    let filled = (fraction * 20.0) as usize;
+
+    format!(
+        "{label} [{}{}] {:.0}% {}/{} {}/s {}",
+        "#".repeat(filled),
+        ".".repeat(20 - filled),
+        fraction * 100.0,
+        count(done as f64),
+        count(total as f64),
+        count(rate),
+        eta(done, total, secs),
+    )
+}
+
+// "scanning rows 35% ETA 2s", for when there's more than one bar to fit
+fn summarise(label: &str, done: u64, total: Option<u64>, elapsed: Duration) -> String {
+    let secs = elapsed.as_secs_f64();
+
+    match total {
+        None => format!("{label} {}", count(done as f64)),
+        Some(total) => {
+            let total = total.max(1);
+            let fraction = (done as f64 / total as f64).min(1.0);
+            format!("{label} {:.0}% {}", fraction * 100.0, eta(done, total, secs))
+        }
+    }
+}
+
+// "ETA 1s", or how long it took once it's done
+fn eta(done: u64, total: u64, secs: f64) -> String {
+    let rate = if secs > 0.0 { done as f64 / secs } else { 0.0 };
+
+    if done >= total {
+        format!("in {}", duration(secs as u64))
+    } else if rate > 0.0 {
+        format!("ETA {}", duration(((total - done) as f64 / rate).ceil() as u64))
+    } else {
+        "ETA ?".to_string()
+    }
+}
+
+// 950, 12.3k, 4M
+fn count(n: f64) -> String {
+    let (n, suffix) = match n {
+        n if n >= 1e9 => (n / 1e9, "G"),
+        n if n >= 1e6 => (n / 1e6, "M"),
+        n if n >= 1e3 => (n / 1e3, "k"),
+        n => return format!("{n:.0}"),
+    };
+
+    let text = format!("{n:.1}");
+    format!("{}{suffix}", text.strip_suffix(".0").unwrap_or(&text))
+}
+
+// 7s, 2m05s, 1h02m
+fn duration(secs: u64) -> String {
+    match secs {
+        s if s >= 3600 => format!("{}h{:02}m", s / 3600, s % 3600 / 60),
+        s if s >= 60 => format!("{}m{:02}s", s / 60, s % 60),
+        s => format!("{s}s"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::log::*;
+
+    fn args(input: &str) -> Vec<String> {
+        input.split(' ').map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_take_verbosity() {
+        assert_eq!(take_verbosity(args("run -v --day 7")), (1, args("run --day 7")));
+        assert_eq!(take_verbosity(args("-vv run --day 7")), (2, args("run --day 7")));
+        assert_eq!(take_verbosity(args("--verbose run --verbose")), (2, args("run")));
+        assert_eq!(take_verbosity(args("run --day 7")), (0, args("run --day 7")));
+
+        // After a `--` it's someone else's
+        assert_eq!(take_verbosity(args("submit -v --answer -- -v")), (1, args("submit --answer -v")));
+        assert_eq!(take_verbosity(args("submit -- --answer --")), (0, args("submit --answer --")));
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(
+            describe("scanning rows", 1_400_000, Some(4_000_000), Duration::from_millis(700)),
+            "scanning rows [#######.............] 35% 1.4M/4M 2M/s ETA 2s",
+        );
+
+        assert_eq!(
+            describe("mixing", 50, Some(50), Duration::from_secs(125)),
+            "mixing [####################] 100% 50/50 0/s in 2m05s",
+        );
+
+        assert_eq!(describe("searching", 12_345, None, Duration::from_secs(1)), "searching 12.3k 12.3k/s");
+    }
+
+    #[test]
+    fn test_combine() {
+        let bar = |id, label, done, total| {
+            let elapsed = Duration::from_millis(700);
+            (id, describe(label, done, total, elapsed), summarise(label, done, total, elapsed))
+        };
+
+        // One bar has the line to itself
+        assert_eq!(
+            combine(&[bar(0, "scanning rows", 1_400_000, Some(4_000_000))]),
+            "scanning rows [#######.............] 35% 1.4M/4M 2M/s ETA 2s",
+        );
+
+        assert_eq!(
+            combine(&[bar(0, "scanning rows", 1_400_000, Some(4_000_000)), bar(3, "searching", 12_345, None)]),
+            "scanning rows 35% ETA 2s | searching 12.3k",
+        );
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Named values a day can be tweaked with: numbers, mostly, or the odd name
+/// (day 16's starting valve).
+///
+/// Some puzzles use different numbers for their example than for the real
+/// input (day 15 looks at row 10 instead of row 2000000), and others are just
+/// fun to play with (day 11's rounds). Each day declares the ones it takes as
+/// a `NumberParam` or `TextParam`, with the real input's values as defaults,
+/// lists them in `Solution::PARAMS`, and reads them from here in
+/// `Solution::parse_with`.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Params(BTreeMap<String, Value>);
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Value {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Number(n) => write!(f, "{n}"),
+            Value::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+/// One of the values a day takes, what it's for, and what it is for the real
+/// input.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Param {
+    name: &'static str,
+    default: Default,
+    // Lowest a number can be set to, for the ones that count something
+    min: i64,
+    about: &'static str,
+}
+
+// `Value`, but fit for a `const`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Default {
+    Number(i64),
+    Text(&'static str),
+}
+
+/// A number a day takes, to read with `Params::number`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct NumberParam {
+    name: &'static str,
+    default: i64,
+    min: i64,
+    about: &'static str,
+}
+
+/// Text a day takes, to read with `Params::text`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TextParam {
+    name: &'static str,
+    default: &'static str,
+    about: &'static str,
+}
+
+impl NumberParam {
+    pub const fn new(name: &'static str, default: i64, about: &'static str) -> Self {
+        NumberParam { name, default, min: i64::MIN, about }
+    }
+
+    /// Refuses anything lower than `min`, such as a negative count.
+    pub const fn at_least(self, min: i64) -> Self {
+        NumberParam { min, ..self }
+    }
+
+    /// For `Solution::PARAMS`.
+    pub const fn param(self) -> Param {
+        Param { name: self.name, default: Default::Number(self.default), min: self.min, about: self.about }
+    }
+}
+
+impl TextParam {
+    pub const fn new(name: &'static str, default: &'static str, about: &'static str) -> Self {
+        TextParam { name, default, about }
+    }
+
+    /// For `Solution::PARAMS`.
+    pub const fn param(self) -> Param {
+        Param { name: self.name, default: Default::Text(self.default), min: i64::MIN, about: self.about }
+    }
+}
+
+impl Param {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn about(&self) -> &'static str {
+        self.about
+    }
+
+    pub fn default(&self) -> Value {
+        match self.default {
+            Default::Number(n) => Value::Number(n),
+            Default::Text(s) => Value::Text(s.to_string()),
+        }
+    }
+
+    /// The lowest a number can be, if there's a limit.
+    pub fn min(&self) -> Option<i64> {
+        match self.default {
+            Default::Number(_) if self.min > i64::MIN => Some(self.min),
+            _ => None,
+        }
+    }
+
+    // Reads `raw` as whichever kind of value this takes
+    fn value(&self, raw: &str) -> Result<Value, String> {
+        match self.default {
+            Default::Number(_) => {
+                let n: i64 = raw.trim().parse().map_err(|_| format!("{} should be a number, not `{raw}`", self.name))?;
+
+                if n < self.min {
+                    return Err(format!("{} should be at least {}, not {n}", self.name, self.min));
+                }
+
+                Ok(Value::Number(n))
+            }
+            Default::Text(_) => Ok(Value::Text(raw.to_string())),
+        }
+    }
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, name: &str, value: i64) {
+        self.0.insert(name.to_string(), Value::Number(value));
+    }
+
+    pub fn with(mut self, name: &str, value: i64) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Sets `name` from text, such as `--param` or a config file gives, as
+    /// long as it's in `schema` and reads as the right kind of value. Setting
+    /// it to its default leaves it unset, so `is_empty` still tells whether
+    /// anything's different from the real puzzle.
+    pub fn apply(&mut self, schema: &[Param], name: &str, raw: &str) -> Result<(), String> {
+        let param = schema.iter().find(|p| p.name == name).ok_or_else(|| match schema {
+            [] => format!("unknown param `{name}`, this day doesn't take any"),
+            _ => {
+                let names: Vec<&str> = schema.iter().map(|p| p.name).collect();
+                format!("unknown param `{name}`, expected one of {}", names.join(", "))
+            }
+        })?;
+
+        let value = param.value(raw)?;
+
+        if value == param.default() {
+            self.0.remove(name);
+        } else {
+            self.0.insert(name.to_string(), value);
+        }
+
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<i64> {
+        match self.0.get(name) {
+            Some(Value::Number(n)) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn get_or(&self, name: &str, default: i64) -> i64 {
+        self.get(name).unwrap_or(default)
+    }
+
+    /// The number `param` is set to, or its default.
+    pub fn number(&self, param: NumberParam) -> i64 {
+        match self.0.get(param.name) {
+            Some(Value::Number(n)) => *n,
+            _ => param.default,
+        }
+    }
+
+    /// The text `param` is set to, or its default.
+    pub fn text(&self, param: TextParam) -> String {
+        match self.0.get(param.name) {
+            Some(Value::Text(s)) => s.clone(),
+            _ => param.default.to_string(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.0.iter().map(|(name, value)| (name.as_str(), value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const ROW: NumberParam = NumberParam::new("row", 2000000, "The row to count");
+    const ROUNDS: NumberParam = NumberParam::new("rounds", 10, "How many rounds").at_least(0);
+    const START: TextParam = TextParam::new("start", "AA", "Where to start");
+    const SCHEMA: &[Param] = &[ROW.param(), ROUNDS.param(), START.param()];
+
+    #[test]
+    fn test_params() {
+        let params = Params::new().with("row", 10);
+
+        assert_eq!(params.get("row"), Some(10));
+        assert_eq!(params.get_or("row", 2000000), 10);
+        assert_eq!(params.get_or("limit", 4000000), 4000000);
+        assert_eq!(params.iter().collect::<Vec<_>>(), vec![("row", &Value::Number(10))]);
+        assert!(Params::new().is_empty());
+    }
+
+    #[test]
+    fn test_params_schema() {
+        let mut params = Params::new();
+
+        assert_eq!(params.number(ROW), 2000000);
+        assert_eq!(params.text(START), "AA");
+
+        assert_eq!(params.apply(SCHEMA, "row", "10"), Ok(()));
+        assert_eq!(params.apply(SCHEMA, "start", "BB"), Ok(()));
+        assert_eq!(params.number(ROW), 10);
+        assert_eq!(params.text(START), "BB");
+
+        assert_eq!(params.apply(SCHEMA, "row", "ten"), Err("row should be a number, not `ten`".to_string()));
+        assert_eq!(
+            params.apply(SCHEMA, "rows", "10"),
+            Err("unknown param `rows`, expected one of row, rounds, start".to_string()),
+        );
+        assert!(params.apply(&[], "row", "10").is_err());
+
+        // Only the ones with a minimum have one
+        assert_eq!(params.apply(SCHEMA, "row", "-5"), Ok(()));
+        assert_eq!(params.apply(SCHEMA, "rounds", "0"), Ok(()));
+        assert_eq!(params.apply(SCHEMA, "rounds", "-1"), Err("rounds should be at least 0, not -1".to_string()));
+        assert_eq!(params.number(ROUNDS), 0);
+        assert_eq!((SCHEMA[0].min(), SCHEMA[1].min(), SCHEMA[2].min()), (None, Some(0), None));
+
+        assert_eq!(params.apply(SCHEMA, "row", "2000000"), Ok(()));
+        assert_eq!(params.apply(SCHEMA, "rounds", "10"), Ok(()));
+        assert_eq!(params.apply(SCHEMA, "start", "AA"), Ok(()));
+        assert!(params.is_empty());
+
+        assert_eq!(SCHEMA[0].default().to_string(), "2000000");
+    }
+}
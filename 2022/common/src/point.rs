@@ -0,0 +1,484 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use crate::{Direction8, YAxis};
+
+/// What a point's coordinates can be: any of the integer types the days use.
+/// Unsigned ones work too, their neighbours just stop at 0.
+pub trait Coordinate:
+    Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self>
+{
+    const ONE: Self;
+
+    /// How far apart two of them are, whichever way round.
+    fn distance(self, other: Self) -> Self;
+
+    /// One more (`1`) or less (`-1`), or `None` if that doesn't fit.
+    fn offset(self, delta: i8) -> Option<Self>;
+
+    /// `self - other`, or `None` if that goes below what the type holds.
+    fn checked_sub(self, other: Self) -> Option<Self>;
+
+    fn as_f64(self) -> f64;
+}
+
+macro_rules! coordinate {
+    ($($t:ty),*) => {$(
+        impl Coordinate for $t {
+            const ONE: $t = 1;
+
+            fn distance(self, other: $t) -> $t {
+                if self > other { self - other } else { other - self }
+            }
+
+            fn offset(self, delta: i8) -> Option<$t> {
+                match delta {
+                    -1 => self.checked_sub(1),
+                    1 => self.checked_add(1),
+                    _ => Some(self),
+                }
+            }
+
+            fn checked_sub(self, other: $t) -> Option<$t> {
+                <$t>::checked_sub(self, other)
+            }
+
+            fn as_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    )*};
+}
+
+coordinate!(i32, i64, isize, u32, u64, usize);
+
+// The same offsets as `Grid`'s, clockwise from north with y counting down
+const NEIGHBOURS_4: [(i8, i8); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const NEIGHBOURS_8: [(i8, i8); 8] = [(0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1)];
+
+// Either way along each axis in turn
+const NEIGHBOURS_6: [(i8, i8, i8); 6] = [(1, 0, 0), (-1, 0, 0), (0, 1, 0), (0, -1, 0), (0, 0, 1), (0, 0, -1)];
+
+/// An `(x, y)` location, or the offset between two. Adds and subtracts as
+/// one, and scales by a single number.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord, Default)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// `Point2`, with a z as well, for the days out in three dimensions.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord, Default)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Coordinate> Point2<T> {
+    /// Steps between the two going only across or down, as on a grid.
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    /// Steps between the two when diagonals count as one, like a king's.
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x.distance(other.x).max(self.y.distance(other.y))
+    }
+
+    /// Straight-line distance.
+    pub fn euclidean(self, other: Self) -> f64 {
+        let (dx, dy) = (self.x.distance(other.x).as_f64(), self.y.distance(other.y).as_f64());
+        dx.hypot(dy)
+    }
+
+    /// The (up to) 4 points sharing an edge with this one, clockwise from
+    /// north, like `Grid::neighbours4`.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point2<T>> {
+        NEIGHBOURS_4.into_iter().filter_map(move |delta| self.offset(delta))
+    }
+
+    /// The (up to) 8 points around this one, diagonals included, clockwise
+    /// from north.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point2<T>> {
+        NEIGHBOURS_8.into_iter().filter_map(move |delta| self.offset(delta))
+    }
+
+    fn offset(self, (dx, dy): (i8, i8)) -> Option<Point2<T>> {
+        Some(Point2::new(self.x.offset(dx)?, self.y.offset(dy)?))
+    }
+}
+
+impl Point2<isize> {
+    /// Moved a step `direction`'s way, straight or diagonal.
+    pub fn step(self, direction: impl Into<Direction8>, y_axis: YAxis) -> Point2<isize> {
+        self + direction.into().delta(y_axis).into()
+    }
+}
+
+impl<T: Coordinate> Point3<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y) + self.z.distance(other.z)
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x.distance(other.x).max(self.y.distance(other.y)).max(self.z.distance(other.z))
+    }
+
+    pub fn euclidean(self, other: Self) -> f64 {
+        let (dx, dy, dz) = (
+            self.x.distance(other.x).as_f64(),
+            self.y.distance(other.y).as_f64(),
+            self.z.distance(other.z).as_f64(),
+        );
+
+        (dx * dx + dy * dy + dz * dz).sqrt()
+    }
+
+    /// The (up to) 6 points sharing a face with this one: either way along
+    /// x, then y, then z.
+    pub fn neighbours6(self) -> impl Iterator<Item = Point3<T>> {
+        NEIGHBOURS_6
+            .into_iter()
+            .filter_map(move |(dx, dy, dz)| Some(Point3::new(self.x.offset(dx)?, self.y.offset(dy)?, self.z.offset(dz)?)))
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point2<T> {
+    type Output = Self;
+
+    fn mul(self, n: T) -> Self {
+        Point2::new(self.x * n, self.y * n)
+    }
+}
+
+impl<T: Div<Output = T> + Copy> Div<T> for Point2<T> {
+    type Output = Self;
+
+    fn div(self, n: T) -> Self {
+        Point2::new(self.x / n, self.y / n)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point2<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, n: T) -> Self {
+        Point3::new(self.x * n, self.y * n, self.z * n)
+    }
+}
+
+impl<T: Div<Output = T> + Copy> Div<T> for Point3<T> {
+    type Output = Self;
+
+    fn div(self, n: T) -> Self {
+        Point3::new(self.x / n, self.y / n, self.z / n)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point3<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+        self.z += other.z;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+        self.z -= other.z;
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point2::new(x, y)
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(point: Point2<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(point: Point3<T>) -> Self {
+        (point.x, point.y, point.z)
+    }
+}
+
+/// The way the puzzles write them: `1,2`.
+impl<T: fmt::Display> fmt::Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// `1,2,5`.
+impl<T: fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// The smallest box around some points, with both corners included.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Bounds2<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+/// `Bounds2`, in three dimensions.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Bounds3<T> {
+    pub min: Point3<T>,
+    pub max: Point3<T>,
+}
+
+impl<T: Coordinate> Bounds2<T> {
+    /// The box around all of `points`, or `None` if there aren't any.
+    pub fn from_points<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Point2<T>>,
+    {
+        points.into_iter().fold(None, |bounds, p| match bounds {
+            None => Some(Bounds2 { min: p, max: p }),
+            Some(Bounds2 { min, max }) => Some(Bounds2 {
+                min: Point2::new(min.x.min(p.x), min.y.min(p.y)),
+                max: Point2::new(max.x.max(p.x), max.y.max(p.y)),
+            }),
+        })
+    }
+
+    pub fn contains(&self, p: Point2<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    /// Bigger by `n` on every side, or `None` if the low side would go
+    /// below 0 with unsigned coordinates.
+    pub fn grow(&self, n: T) -> Option<Self> {
+        let min = Point2::new(self.min.x.checked_sub(n)?, self.min.y.checked_sub(n)?);
+        Some(Bounds2 { min, max: self.max + Point2::new(n, n) })
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+
+    /// How many points it holds.
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+}
+
+impl<T: Coordinate> Bounds3<T> {
+    /// The box around all of `points`, or `None` if there aren't any.
+    pub fn from_points<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Point3<T>>,
+    {
+        points.into_iter().fold(None, |bounds, p| match bounds {
+            None => Some(Bounds3 { min: p, max: p }),
+            Some(Bounds3 { min, max }) => Some(Bounds3 {
+                min: Point3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
+                max: Point3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)),
+            }),
+        })
+    }
+
+    pub fn contains(&self, p: Point3<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x)
+            && (self.min.y..=self.max.y).contains(&p.y)
+            && (self.min.z..=self.max.z).contains(&p.z)
+    }
+
+    /// Bigger by `n` on every side, or `None` if the low side would go
+    /// below 0 with unsigned coordinates.
+    pub fn grow(&self, n: T) -> Option<Self> {
+        let min = Point3::new(self.min.x.checked_sub(n)?, self.min.y.checked_sub(n)?, self.min.z.checked_sub(n)?);
+        Some(Bounds3 { min, max: self.max + Point3::new(n, n, n) })
+    }
+
+    /// How many points it holds.
+    pub fn volume(&self) -> T {
+        let size = self.max - self.min;
+        (size.x + T::ONE) * (size.y + T::ONE) * (size.z + T::ONE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::point::*;
+    use crate::Direction;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point2::new(3, -2);
+        let b = Point2::new(1, 4);
+
+        assert_eq!(a + b, Point2::new(4, 2));
+        assert_eq!(a - b, Point2::new(2, -6));
+        assert_eq!(-a, Point2::new(-3, 2));
+        assert_eq!(a * 3, Point2::new(9, -6));
+        assert_eq!(b / 2, Point2::new(0, 2));
+
+        let mut c = a;
+        c += b;
+        c -= Point2::new(0, 1);
+        assert_eq!(c, Point2::new(4, 1));
+
+        assert_eq!(Point3::new(1, 2, 3) + Point3::new(1, 1, 1) * 2, Point3::new(3, 4, 5));
+        assert_eq!(Point3::from((1, 2, 5)).to_string(), "1,2,5");
+        assert_eq!(<(isize, isize)>::from(Point2::new(7, 8)), (7, 8));
+
+        assert_eq!(Point2::new(3, 3).step(Direction::North, YAxis::Down), Point2::new(3, 2));
+        assert_eq!(Point2::new(3, 3).step(Direction8::NorthEast, YAxis::Up), Point2::new(4, 4));
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point2::new(2, 18);
+        let b = Point2::new(-2, 15);
+
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.euclidean(b), 5.0);
+
+        // Unsigned ones measure either way round
+        assert_eq!(Point2::<usize>::new(1, 5).manhattan(Point2::new(4, 2)), 6);
+
+        let c = Point3::new(1, 2, 3);
+        assert_eq!(c.manhattan(Point3::new(3, 0, 4)), 5);
+        assert_eq!(c.chebyshev(Point3::new(3, 0, 4)), 2);
+        assert_eq!(c.euclidean(Point3::new(3, 0, 4)), 3.0);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let p = Point2::new(1isize, 1);
+
+        assert_eq!(
+            p.neighbours4().collect::<Vec<_>>(),
+            vec![Point2::new(1, 0), Point2::new(2, 1), Point2::new(1, 2), Point2::new(0, 1)],
+        );
+        assert!(p.neighbours8().all(|n| n.chebyshev(p) == 1));
+        assert_eq!(p.neighbours8().count(), 8);
+
+        // The same as a grid's, up against its top left corner
+        assert_eq!(Point2::<usize>::new(0, 0).neighbours4().map(<(usize, usize)>::from).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+        assert_eq!(Point2::<usize>::new(0, 0).neighbours8().count(), 3);
+
+        assert_eq!(Point3::new(0isize, 0, 0).neighbours6().count(), 6);
+        assert_eq!(Point3::<usize>::new(0, 0, 0).neighbours6().count(), 3);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let points = [Point2::new(2, 1), Point2::new(-1, 4), Point2::new(0, 0)];
+        let bounds = Bounds2::from_points(points).unwrap();
+
+        assert_eq!(bounds, Bounds2 { min: Point2::new(-1, 0), max: Point2::new(2, 4) });
+        assert_eq!((bounds.width(), bounds.height(), bounds.area()), (4, 5, 20));
+        assert!(bounds.contains(Point2::new(2, 4)));
+        assert!(!bounds.contains(Point2::new(3, 4)));
+        assert!(bounds.grow(1).unwrap().contains(Point2::new(3, 5)));
+        assert_eq!(Bounds2::<isize>::from_points([]), None);
+
+        let bounds = Bounds3::from_points([Point3::new(1, 1, 1), Point3::new(3, 2, 1)]).unwrap();
+        assert_eq!(bounds.volume(), 6);
+        assert!(bounds.contains(Point3::new(2, 2, 1)));
+        assert!(!bounds.contains(Point3::new(2, 2, 0)));
+        assert_eq!(bounds.grow(1).unwrap().volume(), 5 * 4 * 3);
+
+        let bounds = Bounds2::<usize>::from_points([Point2::new(0, 3), Point2::new(2, 5)]).unwrap();
+        assert_eq!(bounds.grow(1), None);
+        assert_eq!(Bounds3::<u32>::from_points([Point3::new(1, 1, 1)]).unwrap().grow(1).map(|b| b.volume()), Some(27));
+    }
+}
@@ -0,0 +1,303 @@
+//! Shortest-path searches over any graph you can describe with a neighbour
+//! function.
+//!
+//! Nodes can be anything hashable: a grid location, a valve name, a
+//! (minute, location) pair. `bfs` is for graphs where every step costs the
+//! same, `dijkstra` takes a cost for each edge, and `astar` also takes a
+//! heuristic guessing the remaining cost (it must never overestimate it, or
+//! the path found may not be the shortest one).
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// The way from the start to a goal, both ends included.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Path<N> {
+    pub cost: usize,
+    pub nodes: Vec<N>,
+}
+
+impl<N> Path<N> {
+    /// Number of moves made, which is the cost for `bfs`.
+    pub fn steps(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    /// The goal that was reached.
+    pub fn end(&self) -> &N {
+        self.nodes.last().unwrap()
+    }
+}
+
+/// How much work a search did.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Stats {
+    /// Nodes taken off the frontier and looked at.
+    pub expanded: usize,
+    /// Distinct nodes seen at all, including those still waiting on the
+    /// frontier when the search stopped.
+    pub discovered: usize,
+    /// Largest the frontier got.
+    pub max_frontier: usize,
+}
+
+#[derive(Debug)]
+pub struct SearchResult<N> {
+    /// `None` when every reachable node was tried without finding a goal.
+    pub path: Option<Path<N>>,
+    pub stats: Stats,
+    // For each node seen: where we came from and the best cost so far
+    came_from: HashMap<N, (Option<N>, usize)>,
+}
+
+impl<N: Eq + Hash + Clone> SearchResult<N> {
+    fn finish(goal: Option<N>, came_from: HashMap<N, (Option<N>, usize)>, mut stats: Stats) -> Self {
+        stats.discovered = came_from.len();
+
+        let path = goal.map(|goal| {
+            let cost = came_from[&goal].1;
+            let mut nodes = vec![goal];
+
+            while let Some(prev) = &came_from[nodes.last().unwrap()].0 {
+                nodes.push(prev.clone());
+            }
+
+            nodes.reverse();
+            Path { cost, nodes }
+        });
+
+        SearchResult { path, stats, came_from }
+    }
+
+    pub fn cost(&self) -> Option<usize> {
+        self.path.as_ref().map(|p| p.cost)
+    }
+
+    /// Every node the search came across. When no goal was found, that's
+    /// everything reachable from the start.
+    pub fn visited(&self) -> impl Iterator<Item = &N> {
+        self.came_from.keys()
+    }
+
+    pub fn was_visited(&self, node: &N) -> bool {
+        self.came_from.contains_key(node)
+    }
+
+    /// Cheapest cost found from the start to `node`. Only final for nodes
+    /// that were expanded before the search stopped.
+    pub fn cost_to(&self, node: &N) -> Option<usize> {
+        self.came_from.get(node).map(|(_, cost)| *cost)
+    }
+}
+
+/// Breadth-first search, each step costing 1.
+///
+/// Without a goal (`|_| false`) this is a flood fill: `visited` on the
+/// result is everything reachable from `start`.
+pub fn bfs<N, F, I, G>(start: N, mut neighbours: F, mut is_goal: G) -> SearchResult<N>
+where
+    N: Eq + Hash + Clone,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    G: FnMut(&N) -> bool,
+{
+    let mut stats = Stats::default();
+    let mut came_from = HashMap::from([(start.clone(), (None, 0))]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        stats.expanded += 1;
+
+        if is_goal(&node) {
+            return SearchResult::finish(Some(node), came_from, stats);
+        }
+
+        let cost = came_from[&node].1 + 1;
+
+        for next in neighbours(&node) {
+            if !came_from.contains_key(&next) {
+                came_from.insert(next.clone(), (Some(node.clone()), cost));
+                queue.push_back(next);
+            }
+        }
+
+        stats.max_frontier = stats.max_frontier.max(queue.len());
+    }
+
+    SearchResult::finish(None, came_from, stats)
+}
+
+/// Dijkstra's algorithm: `neighbours` gives each next node along with the
+/// cost of moving there.
+pub fn dijkstra<N, F, I, G>(start: N, neighbours: F, is_goal: G) -> SearchResult<N>
+where
+    N: Eq + Hash + Clone,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, usize)>,
+    G: FnMut(&N) -> bool,
+{
+    astar(start, neighbours, |_| 0, is_goal)
+}
+
+/// A*: Dijkstra, but trying the nodes that `heuristic` reckons are closest to
+/// a goal first.
+pub fn astar<N, F, I, H, G>(
+    start: N,
+    mut neighbours: F,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> SearchResult<N>
+where
+    N: Eq + Hash + Clone,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, usize)>,
+    H: FnMut(&N) -> usize,
+    G: FnMut(&N) -> bool,
+{
+    let mut stats = Stats::default();
+    let mut came_from = HashMap::from([(start.clone(), (None, 0))]);
+    let mut frontier = BinaryHeap::new();
+
+    frontier.push(Entry { priority: heuristic(&start), cost: 0, node: start });
+
+    while let Some(Entry { cost, node, .. }) = frontier.pop() {
+        // A cheaper way here was found after this entry was queued
+        if cost > came_from[&node].1 {
+            continue;
+        }
+
+        stats.expanded += 1;
+
+        if is_goal(&node) {
+            return SearchResult::finish(Some(node), came_from, stats);
+        }
+
+        for (next, step_cost) in neighbours(&node) {
+            let next_cost = cost + step_cost;
+            let is_better = came_from
+                .get(&next)
+                .is_none_or(|(_, known)| next_cost < *known);
+
+            if is_better {
+                came_from.insert(next.clone(), (Some(node.clone()), next_cost));
+                frontier.push(Entry {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+
+        stats.max_frontier = stats.max_frontier.max(frontier.len());
+    }
+
+    SearchResult::finish(None, came_from, stats)
+}
+
+// Frontier entry, ordered so the max-heap pops the lowest priority first
+struct Entry<N> {
+    priority: usize,
+    cost: usize,
+    node: N,
+}
+
+impl<N> PartialEq for Entry<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N> Eq for Entry<N> {}
+
+impl<N> PartialOrd for Entry<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> Ord for Entry<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::search::*;
+
+    // Open cells of a little maze, as (x, y)
+    fn maze_neighbours(&(x, y): &(isize, isize)) -> Vec<(isize, isize)> {
+        let maze = [
+            "..#.....",
+            ".##.###.",
+            "....#...",
+            "#.###.#.",
+            "......#.",
+        ];
+
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .iter()
+            .map(|(dx, dy)| (x + dx, y + dy))
+            .filter(|&(nx, ny)| {
+                nx >= 0 && ny >= 0 && maze.get(ny as usize)
+                    .and_then(|row| row.chars().nth(nx as usize)) == Some('.')
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let result = bfs((0, 0), maze_neighbours, |&n| n == (7, 4));
+        let path = result.path.unwrap();
+
+        assert_eq!(path.cost, 15);
+        assert_eq!(path.steps(), 15);
+        assert_eq!(path.nodes[0], (0, 0));
+        assert_eq!(path.end(), &(7, 4));
+
+        // Each step moves to a neighbour
+        for pair in path.nodes.windows(2) {
+            assert!(maze_neighbours(&pair[0]).contains(&pair[1]));
+        }
+
+        assert!(result.stats.expanded <= result.stats.discovered);
+    }
+
+    #[test]
+    fn test_bfs_unreachable_floods() {
+        // Off the edge of the maze, so never reached
+        let result = bfs((0, 0), maze_neighbours, |&n| n == (9, 9));
+
+        assert_eq!(result.path, None);
+        // Every open cell, but no walls
+        assert_eq!(result.visited().count(), 27);
+        assert!(!result.was_visited(&(2, 0)));
+        assert_eq!(result.cost_to(&(7, 4)), Some(15));
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        // The direct edge from a to d is dearer than going round
+        let edges = |n: &char| -> Vec<(char, usize)> {
+            match n {
+                'a' => vec![('b', 1), ('d', 10)],
+                'b' => vec![('c', 2)],
+                'c' => vec![('d', 3)],
+                _ => vec![],
+            }
+        };
+
+        let result = dijkstra('a', edges, |n| *n == 'd');
+        assert_eq!(result.cost(), Some(6));
+        assert_eq!(result.path.unwrap().nodes, vec!['a', 'b', 'c', 'd']);
+
+        let result = astar((0, 0), |n| maze_neighbours(n).into_iter().map(|m| (m, 1)), |&(x, y)| {
+            (7 - x).unsigned_abs() + (4 - y).unsigned_abs()
+        }, |&n| n == (7, 4));
+        assert_eq!(result.cost(), Some(15));
+
+        let plain = dijkstra((0, 0), |n| maze_neighbours(n).into_iter().map(|m| (m, 1)), |&n| n == (7, 4));
+        assert!(result.stats.expanded <= plain.stats.expanded);
+    }
+}
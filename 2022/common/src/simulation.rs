@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use crate::{Grid, ParseError, Params, Part, Solution};
+
+/// What a simulation looks like at one point, drawn a char per cell the way
+/// the puzzle descriptions draw it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Frame {
+    // Where the top-left cell is, in the simulation's own coordinates (y
+    // counting down), so frames of different sizes can be lined up
+    pub origin: (isize, isize),
+    pub cells: Grid<char>,
+}
+
+impl Frame {
+    pub fn new(origin: (isize, isize), cells: Grid<char>) -> Self {
+        Frame { origin, cells }
+    }
+
+    /// Draws a frame just big enough for `cells`, filling the gaps between
+    /// them with `background`. Cells drawn later go over earlier ones.
+    pub fn draw<I>(cells: I, background: char) -> Self
+    where
+        I: IntoIterator<Item = ((isize, isize), char)>,
+    {
+        let mut drawn: HashMap<(isize, isize), char> = HashMap::new();
+
+        for (loc, c) in cells {
+            drawn.insert(loc, c);
+        }
+
+        if drawn.is_empty() {
+            return Frame::new((0, 0), Grid::new(1, 1, background));
+        }
+
+        let min_x = drawn.keys().map(|(x, _)| *x).min().unwrap();
+        let max_x = drawn.keys().map(|(x, _)| *x).max().unwrap();
+        let min_y = drawn.keys().map(|(_, y)| *y).min().unwrap();
+        let max_y = drawn.keys().map(|(_, y)| *y).max().unwrap();
+
+        let width = (max_x - min_x + 1) as usize;
+        let height = (max_y - min_y + 1) as usize;
+        let mut grid = Grid::new(width, height, background);
+
+        for ((x, y), c) in drawn {
+            grid[((x - min_x) as usize, (y - min_y) as usize)] = c;
+        }
+
+        Frame::new((min_x, min_y), grid)
+    }
+}
+
+/// A puzzle that plays out a step at a time (sand falling, elves spreading
+/// out), so it can be watched rather than only giving an answer at the end.
+pub trait Simulation {
+    /// Moves on by one step. Returns false once there's nothing left to
+    /// happen, in which case there's no new frame either.
+    fn step(&mut self) -> bool;
+
+    fn render_frame(&self) -> Frame;
+}
+
+/// The frame `simulation` starts on, then one more after every step it takes
+/// until it's done.
+pub fn frames(simulation: &mut dyn Simulation) -> impl Iterator<Item = Frame> + '_ {
+    let mut started = false;
+
+    std::iter::from_fn(move || {
+        if started && !simulation.step() {
+            return None;
+        }
+
+        started = true;
+        Some(simulation.render_frame())
+    })
+}
+
+/// A solution that can show its working as a `Simulation`, set up the way
+/// solving `part` would go about it.
+pub trait Simulated: Solution {
+    fn simulation(input: &Self::Input, part: Part) -> Box<dyn Simulation>;
+}
+
+pub type SimulateFn = fn(&[String], &Params, Part) -> Result<Box<dyn Simulation>, ParseError>;
+
+/// Parses input for solution `S` and sets up its simulation of `part`.
+/// `simulate::<Day14>` coerces to a `SimulateFn`.
+pub fn simulate<S: Simulated>(input: &[String], params: &Params, part: Part) -> Result<Box<dyn Simulation>, ParseError> {
+    Ok(S::simulation(&S::parse_with(input, params)?, part))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    // Counts down to zero, a step at a time
+    struct Countdown(usize);
+
+    impl Simulation for Countdown {
+        fn step(&mut self) -> bool {
+            if self.0 == 0 {
+                return false;
+            }
+
+            self.0 -= 1;
+            true
+        }
+
+        fn render_frame(&self) -> Frame {
+            Frame::draw((0..self.0).map(|x| ((x as isize, 0), '#')), '.')
+        }
+    }
+
+    #[test]
+    fn test_frames() {
+        let rendered: Vec<String> = frames(&mut Countdown(3)).map(|f| f.cells.to_string()).collect();
+        assert_eq!(rendered, vec!["###", "##", "#", "."]);
+    }
+
+    #[test]
+    fn test_frame_draw() {
+        let frame = Frame::draw([((-1, 2), '#'), ((1, 3), 'o'), ((1, 3), '@')], '.');
+
+        assert_eq!(frame.origin, (-1, 2));
+        assert_eq!(frame.cells.to_string(), "#..\n..@");
+    }
+}
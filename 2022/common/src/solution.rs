@@ -0,0 +1,210 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::{Param, ParseError, Params, Part};
+
+/// What a part of a day produces.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Answer {
+    // Wide enough for anything a u64 or an i64 can hold
+    Number(i128),
+    Text(String),
+
+    // Multi-line drawings, like the CRT image from day 10. Read the letters
+    // off yourself.
+    Art(String),
+
+    // Part two that never got finished, or day 25 which doesn't have one.
+    Unsolved,
+}
+
+impl Answer {
+    pub fn is_solved(&self) -> bool {
+        *self != Answer::Unsolved
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Art(s) => write!(f, "{}", s.trim_end()),
+            Answer::Unsolved => write!(f, "(unsolved)"),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Number(n.into())
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(n: isize) -> Self {
+        Answer::Number(n as i128)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Number(n.into())
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Self {
+        Answer::Number(n.into())
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Number(n as i128)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer::Number(n.into())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// A day's solution.
+///
+/// The input is parsed once into `Input`, and both parts work off that. Parts
+/// that need to mutate things (most simulations) clone what they need.
+/// Malformed input is reported from `parse` rather than panicking, so the
+/// runner can point at what's wrong with it.
+pub trait Solution {
+    // Shared between threads when the runner solves both parts at once
+    type Input: Send + Sync;
+
+    /// The values `parse_with` reads out of its `Params`, with what each is
+    /// for and its value for the real input. The runner checks `--param` and
+    /// the config file against these, and lists them in `aoc params`.
+    const PARAMS: &'static [Param] = &[];
+
+    fn parse(input: &[String]) -> Result<Self::Input, ParseError>;
+
+    /// `parse`, for days with numbers that differ between the example and
+    /// the real input, or that are worth playing with. Anything not in
+    /// `params` gets its default from `PARAMS`, so that plain `parse` can just
+    /// pass an empty `Params`.
+    fn parse_with(input: &[String], _params: &Params) -> Result<Self::Input, ParseError> {
+        Self::parse(input)
+    }
+
+    fn part_one(input: &Self::Input) -> Answer;
+
+    fn part_two(_input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+/// A parsed input with the day it belongs to erased, so that tooling can keep
+/// a list of every day without caring about their `Input` types.
+pub trait Parsed: Send + Sync {
+    fn solve(&self, part: Part) -> Answer;
+}
+
+struct ParsedInput<S: Solution> {
+    input: S::Input,
+    // Only ever the day's type, never one of it, so that doesn't need to be Sync
+    solution: PhantomData<fn() -> S>,
+}
+
+impl<S: Solution> Parsed for ParsedInput<S> {
+    fn solve(&self, part: Part) -> Answer {
+        match part {
+            Part::One => S::part_one(&self.input),
+            Part::Two => S::part_two(&self.input),
+        }
+    }
+}
+
+pub type ParseFn = fn(&[String], &Params) -> Result<Box<dyn Parsed>, ParseError>;
+
+/// Parses input for solution `S`. `parse::<Day07>` coerces to a `ParseFn`.
+pub fn parse<S>(input: &[String], params: &Params) -> Result<Box<dyn Parsed>, ParseError>
+where
+    S: Solution + 'static,
+    S::Input: 'static,
+{
+    Ok(boxed::<S>(S::parse_with(input, params)?))
+}
+
+pub(crate) fn boxed<S>(input: S::Input) -> Box<dyn Parsed>
+where
+    S: Solution + 'static,
+    S::Input: 'static,
+{
+    Box::new(ParsedInput::<S> {
+        input,
+        solution: PhantomData,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    struct Summer;
+
+    impl Solution for Summer {
+        type Input = Vec<i64>;
+
+        fn parse(input: &[String]) -> Result<Vec<i64>, ParseError> {
+            parse_lines(input, |l| parse_number(l, l))
+        }
+
+        fn parse_with(input: &[String], params: &Params) -> Result<Vec<i64>, ParseError> {
+            let mut numbers = Self::parse(input)?;
+            numbers.push(params.get_or("extra", 0));
+            Ok(numbers)
+        }
+
+        fn part_one(input: &Vec<i64>) -> Answer {
+            input.iter().sum::<i64>().into()
+        }
+    }
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::from(42usize).to_string(), "42");
+        assert_eq!(Answer::from(-3isize).to_string(), "-3");
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::from(usize::MAX).to_string(), usize::MAX.to_string());
+        assert_eq!(Answer::from("CMZ").to_string(), "CMZ");
+        assert_eq!(Answer::Art("#..#\n.##.\n".to_string()).to_string(), "#..#\n.##.");
+        assert_eq!(Answer::Unsolved.to_string(), "(unsolved)");
+    }
+
+    #[test]
+    fn test_parse_and_solve() {
+        let input = vec!["1".to_string(), "2".to_string(), "39".to_string()];
+        let parsed = parse::<Summer>(&input, &Params::new()).unwrap();
+
+        assert_eq!(parsed.solve(Part::One), Answer::Number(42));
+        assert_eq!(parsed.solve(Part::Two), Answer::Unsolved);
+
+        let parsed = parse::<Summer>(&input, &Params::new().with("extra", 8)).unwrap();
+        assert_eq!(parsed.solve(Part::One), Answer::Number(50));
+
+        let err = parse::<Summer>(&["1".to_string(), "2x".to_string()], &Params::new()).err().unwrap();
+        assert_eq!((err.line, err.text.as_str()), (1, "2x"));
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt;
+use std::io;
+
+use crate::solution::boxed;
+use crate::{ParseError, Parsed, Solution};
+
+/// A solution that takes its input in a line at a time, keeping only what the
+/// parts need from it (running totals, say) rather than every line. Its input
+/// can then be streamed through in constant memory, however big it is.
+///
+/// `Input` starts out as its `Default`, and each line gets added to it in
+/// turn. Such a day's `Solution::parse` is just `parse_streamed::<Self>`.
+pub trait Streaming: Solution<Input: Default> {
+    fn add_line(input: &mut Self::Input, line: &str) -> Result<(), ParseError>;
+}
+
+/// `Solution::parse` for a `Streaming` day, for when the lines are already in
+/// memory anyway.
+pub fn parse_streamed<S: Streaming>(input: &[String]) -> Result<S::Input, ParseError> {
+    let mut parsed = S::Input::default();
+
+    for (i, line) in input.iter().enumerate() {
+        S::add_line(&mut parsed, line).map_err(|e| e.on_line(i))?;
+    }
+
+    Ok(parsed)
+}
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    // Along with the line it's about, as that's gone by the time this is shown
+    Parse(ParseError, String),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "couldn't read input: {e}"),
+            StreamError::Parse(e, line) => write!(f, "{}", e.diagnostic_for(line)),
+        }
+    }
+}
+
+impl std::error::Error for StreamError {}
+
+pub type Lines<'a> = &'a mut dyn Iterator<Item = io::Result<String>>;
+
+pub type StreamFn = fn(Lines) -> Result<Box<dyn Parsed>, StreamError>;
+
+/// Feeds `lines` through solution `S` one at a time, without keeping them.
+/// `stream::<Day01>` coerces to a `StreamFn`.
+pub fn stream<S>(lines: Lines) -> Result<Box<dyn Parsed>, StreamError>
+where
+    S: Streaming + 'static,
+    S::Input: 'static,
+{
+    let mut input = S::Input::default();
+
+    for (i, line) in lines.enumerate() {
+        let line = line.map_err(StreamError::Io)?;
+        S::add_line(&mut input, &line).map_err(|e| StreamError::Parse(e.on_line(i), line))?;
+    }
+
+    Ok(boxed::<S>(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, Cursor};
+
+    use crate::*;
+
+    // Sums every number, without keeping any of them
+    struct Summer;
+
+    impl Solution for Summer {
+        type Input = i64;
+
+        fn parse(input: &[String]) -> Result<i64, ParseError> {
+            parse_streamed::<Self>(input)
+        }
+
+        fn part_one(total: &i64) -> Answer {
+            (*total).into()
+        }
+    }
+
+    impl Streaming for Summer {
+        fn add_line(total: &mut i64, line: &str) -> Result<(), ParseError> {
+            *total += parse_number::<i64>(line, line)?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_stream() {
+        let mut lines = Cursor::new("1\n2\n39\n").lines();
+        let parsed = stream::<Summer>(&mut lines).unwrap();
+
+        assert_eq!(parsed.solve(Part::One), Answer::Number(42));
+        assert_eq!(Summer::parse(&["1".to_string(), "2".to_string()]), Ok(3));
+
+        let mut lines = Cursor::new("1\n2x\n").lines();
+        let err = stream::<Summer>(&mut lines).err().unwrap();
+
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: expected a number, found `2x`\n  |\n2 | 2x\n  | ^^",
+        );
+    }
+}
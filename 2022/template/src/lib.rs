@@ -0,0 +1,35 @@
+use common::{Answer, Generated, ParseError, Rng, Solution};
+
+pub struct DayNN;
+
+impl Solution for DayNN {
+    type Input = Vec<String>;
+
+    fn parse(input: &[String]) -> Result<Vec<String>, ParseError> {
+        Ok(input.to_vec())
+    }
+
+    fn part_one(_input: &Vec<String>) -> Answer {
+        Answer::Unsolved
+    }
+
+    fn part_two(_input: &Vec<String>) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+impl Generated for DayNN {
+    fn generate(_rng: &mut Rng, _size: usize) -> Vec<String> {
+        vec![]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn testing_works() {
+        assert_eq!(DayNN::part_one(&DayNN::parse(&[]).unwrap()), Answer::Unsolved);
+    }
+}